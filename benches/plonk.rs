@@ -1,11 +1,9 @@
 //! Benchmarks
 
-use ark_bls12_381::{Bls12_381, Fr as BlsScalar};
+use ark_bls12_381::Bls12_381;
 use ark_ec::{PairingEngine, TEModelParameters};
 use ark_ed_on_bls12_381::EdwardsParameters;
 use ark_plonk::prelude::*;
-use ark_poly::univariate::DensePolynomial;
-use ark_poly_commit::kzg10::KZG10;
 use core::marker::PhantomData;
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use rand_core::OsRng;
@@ -71,12 +69,9 @@ fn constraint_system_benchmark(c: &mut Criterion) {
     const MINIMUM_DEGREE: usize = 5;
     const MAXIMUM_DEGREE: usize = 19;
 
-    let pp = KZG10::<Bls12_381, DensePolynomial<BlsScalar>>::setup(
-        1 << MAXIMUM_DEGREE,
-        false,
-        &mut OsRng,
-    )
-    .expect("Unable to sample public parameters.");
+    let pp =
+        PublicParameters::<Bls12_381>::setup(1 << MAXIMUM_DEGREE, &mut OsRng)
+            .expect("Unable to sample public parameters.");
 
     let mut compiling_benchmarks = c.benchmark_group("compile");
     for degree in MINIMUM_DEGREE..MAXIMUM_DEGREE {
@@ -108,13 +103,47 @@ fn constraint_system_benchmark(c: &mut Criterion) {
     }
     proving_benchmarks.finish();
 
+    // Proving on a single thread against the default thread pool
+    #[cfg(feature = "parallel")]
+    {
+        let mut threading_benchmarks = c.benchmark_group("prove_threads");
+        let thread_pools = [
+            ("sequential", rayon::ThreadPoolBuilder::new().num_threads(1)),
+            ("parallel", rayon::ThreadPoolBuilder::new()),
+        ]
+        .map(|(mode, builder)| {
+            (mode, builder.build().expect("Unable to build thread pool."))
+        });
+        for degree in MINIMUM_DEGREE..MAXIMUM_DEGREE {
+            let mut circuit = BenchCircuit::<_, EdwardsParameters>::new(degree);
+            let (pk_p, _) =
+                circuit.compile(&pp).expect("Unable to compile circuit.");
+            for (mode, thread_pool) in &thread_pools {
+                threading_benchmarks.bench_with_input(
+                    BenchmarkId::new(*mode, degree),
+                    &degree,
+                    |b, _| {
+                        b.iter(|| {
+                            thread_pool.install(|| {
+                                circuit
+                                    .gen_proof(&pp, pk_p.clone(), label)
+                                    .unwrap()
+                            })
+                        })
+                    },
+                );
+            }
+        }
+        threading_benchmarks.finish();
+    }
+
     let mut verifying_benchmarks = c.benchmark_group("verify");
     for degree in MINIMUM_DEGREE..MAXIMUM_DEGREE {
         let mut circuit = BenchCircuit::<_, EdwardsParameters>::new(degree);
         let (pk_p, verifier_data) =
             circuit.compile(&pp).expect("Unable to compile circuit.");
         let proof = circuit.gen_proof(&pp, pk_p.clone(), &label).unwrap();
-        let VerifierData { key, pi_pos } = verifier_data;
+        let VerifierData { key, pi_pos, .. } = verifier_data;
         verifying_benchmarks.bench_with_input(
             BenchmarkId::from_parameter(degree),
             &degree,
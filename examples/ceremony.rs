@@ -0,0 +1,112 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Runs a local setup ceremony over BLS12-381, exchanging its state as files.
+//!
+//! ```text
+//! cargo run --release --example ceremony -- new <max_degree> <out>
+//! cargo run --release --example ceremony -- contribute <in> <out>
+//! cargo run --release --example ceremony -- verify <in>
+//! cargo run --release --example ceremony -- export <in> <out> [degree]
+//! ```
+//!
+//! `export` writes the [`PublicParameters`] of a verified ceremony, optionally
+//! truncated to polynomials of degree at most `degree`.
+
+use ark_bls12_381::Bls12_381;
+use ark_plonk::error::Error;
+use ark_plonk::setup::{self, Ceremony, PublicParameters};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand_core::OsRng;
+use std::env;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::process;
+
+/// Reads and verifies the ceremony at `path`.
+fn read(path: &str) -> Result<Ceremony<Bls12_381>, Error> {
+    let file =
+        File::open(path).map_err(ark_serialize::SerializationError::from)?;
+    let ceremony = Ceremony::deserialize(BufReader::new(file))?;
+    ceremony.verify(&mut OsRng)?;
+    Ok(ceremony)
+}
+
+/// Writes `value` to `path`.
+fn write<T>(path: &str, value: &T) -> Result<(), Error>
+where
+    T: CanonicalSerialize,
+{
+    let file =
+        File::create(path).map_err(ark_serialize::SerializationError::from)?;
+    let mut writer = BufWriter::new(file);
+    value.serialize(&mut writer)?;
+    writer
+        .flush()
+        .map_err(ark_serialize::SerializationError::from)?;
+    Ok(())
+}
+
+/// Parses a degree argument.
+fn degree(arg: &str) -> usize {
+    arg.parse().unwrap_or_else(|_| usage())
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "usage: ceremony new <max_degree> <out>\n       \
+         ceremony contribute <in> <out>\n       \
+         ceremony verify <in>\n       \
+         ceremony export <in> <out> [degree]"
+    );
+    process::exit(2)
+}
+
+fn run(args: &[String]) -> Result<(), Error> {
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    match args[..] {
+        ["new", max_degree, out] => {
+            write(out, &Ceremony::<Bls12_381>::new(degree(max_degree))?)
+        }
+        ["contribute", input, out] => {
+            let mut ceremony = read(input)?;
+            ceremony.contribute(&mut OsRng);
+            println!(
+                "contribution {} added",
+                ceremony.contributions().len() - 1
+            );
+            write(out, &ceremony)
+        }
+        ["verify", input] => {
+            let ceremony = read(input)?;
+            println!(
+                "{} contributions verified for degree {}",
+                ceremony.contributions().len(),
+                ceremony.params().powers_of_g.len() - 1
+            );
+            Ok(())
+        }
+        ["export", input, out] => {
+            write(out, &PublicParameters::from(read(input)?.into_params()))
+        }
+        ["export", input, out, degree_arg] => {
+            let params =
+                setup::truncate(read(input)?.params(), degree(degree_arg))?;
+            write(out, &PublicParameters::from(params))
+        }
+        _ => usage(),
+    }
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if let Err(error) = run(&args) {
+        eprintln!("error: {:?}", error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,90 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Deserializes a [`Proof`] from the fuzzer input and verifies it against a
+//! fixed circuit. Neither step may panic, whatever the bytes.
+//!
+//! The lowest bit of the first byte picks the [`ProofMode`] of the proof and
+//! of the verifier key, and the rest of the bytes hold the proof.
+//!
+//! Run with `cargo fuzz run proof_verify` from the repository root.
+
+#![no_main]
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ed_on_bls12_381::EdwardsParameters;
+use ark_plonk::prelude::*;
+use ark_plonk::proof_system::ProofMode;
+use ark_poly_commit::kzg10::VerifierKey;
+use libfuzzer_sys::fuzz_target;
+use rand_core::OsRng;
+use std::sync::OnceLock;
+
+/// Preprocessed verifiers of the fuzzed circuit, one per [`ProofMode`], with
+/// its public inputs.
+struct Target {
+    standard_verifier: Verifier<Bls12_381, EdwardsParameters>,
+    compact_verifier: Verifier<Bls12_381, EdwardsParameters>,
+    verifier_key: VerifierKey<Bls12_381>,
+    public_inputs: Vec<Fr>,
+}
+
+/// Adds the constraints of the fuzzed circuit, `a * b = c` with `c` public.
+fn gadget(composer: &mut StandardComposer<Bls12_381, EdwardsParameters>) {
+    let a = composer.add_input(Fr::from(3u64));
+    let b = composer.add_input(Fr::from(5u64));
+    let c = composer.mul(Fr::from(1u64), a, b, Fr::from(0u64), None);
+    composer.constrain_to_constant(c, Fr::from(0u64), Some(-Fr::from(15u64)));
+}
+
+fn target() -> &'static Target {
+    static TARGET: OnceLock<Target> = OnceLock::new();
+    TARGET.get_or_init(|| {
+        let pp = PublicParameters::<Bls12_381>::setup(1 << 4, &mut OsRng)
+            .expect("setup");
+        let (commit_key, verifier_key) = pp.trim(1 << 3).expect("trim");
+        let verifier = |mode| {
+            let mut verifier = Verifier::new(b"fuzz");
+            gadget(verifier.mut_cs());
+            verifier.preprocess(&commit_key).expect("preprocess");
+            if let Some(key) = verifier.verifier_key.as_mut() {
+                key.set_mode(mode);
+            }
+            verifier
+        };
+        let mut standard_verifier = verifier(ProofMode::Standard);
+        let compact_verifier = verifier(ProofMode::Compact);
+        let public_inputs = standard_verifier.mut_cs().construct_dense_pi_vec();
+        Target {
+            standard_verifier,
+            compact_verifier,
+            verifier_key,
+            public_inputs,
+        }
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let target = target();
+    let Some((mode, data)) = data.split_first() else {
+        return;
+    };
+    let (mode, verifier) = match mode & 1 {
+        0 => (ProofMode::Standard, &target.standard_verifier),
+        _ => (ProofMode::Compact, &target.compact_verifier),
+    };
+    if let Ok(proof) =
+        Proof::<Bls12_381, EdwardsParameters>::deserialize_with_mode(data, mode)
+    {
+        let _ = verifier.verify(
+            &proof,
+            &target.verifier_key,
+            &target.public_inputs,
+        );
+    }
+});
@@ -10,18 +10,22 @@
 
 use crate::constraint_system::StandardComposer;
 use crate::error::Error;
+#[cfg(feature = "std")]
+use crate::keystore::KeyStore;
 use crate::proof_system::{Proof, Prover, ProverKey, Verifier, VerifierKey};
+use crate::setup::PublicParameters;
+use crate::transcript::TranscriptWrapper;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
 use ark_ec::models::TEModelParameters;
 use ark_ec::{
     twisted_edwards_extended::{GroupAffine, GroupProjective},
     PairingEngine, ProjectiveCurve,
 };
 use ark_ff::PrimeField;
-use ark_poly::univariate::DensePolynomial;
-use ark_poly_commit::kzg10::{self, Powers, UniversalParams};
-use ark_poly_commit::sonic_pc::SonicKZG10;
-use ark_poly_commit::PolynomialCommitment;
 use ark_serialize::*;
+use rand_core::{CryptoRng, RngCore};
 
 /// Field Element Into Public Input
 ///
@@ -102,10 +106,77 @@ where
     }
 }
 
+impl<P> PublicInputValue<P>
+where
+    P: TEModelParameters,
+{
+    /// Returns the type of public input these values represent, if any.
+    fn input_type(&self) -> Option<PublicInputType> {
+        match self.values.len() {
+            1 => Some(PublicInputType::FieldElement),
+            2 => Some(PublicInputType::Point),
+            _ => None,
+        }
+    }
+}
+
+/// Type of a named Public Input.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PublicInputType {
+    /// A single field element.
+    FieldElement,
+    /// An embedded curve point, stored as its affine coordinates.
+    Point,
+}
+
+impl CanonicalSerialize for PublicInputType {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        let tag: u8 = match self {
+            Self::FieldElement => 0,
+            Self::Point => 1,
+        };
+        tag.serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        1
+    }
+}
+
+impl CanonicalDeserialize for PublicInputType {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        match u8::deserialize(reader)? {
+            0 => Ok(Self::FieldElement),
+            1 => Ok(Self::Point),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+/// Public Input registered by a [`Circuit`] under a name, together with its
+/// type and the gate positions it occupies.
+#[derive(
+    CanonicalDeserialize, CanonicalSerialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct NamedPublicInput {
+    /// Name of the Public Input
+    pub name: String,
+
+    /// Type of the Public Input
+    pub ty: PublicInputType,
+
+    /// Positions of the values of the Public Input
+    pub positions: Vec<usize>,
+}
+
 /// Collection of structs/objects that the Verifier will use in order to
 /// de/serialize data needed for Circuit proof verification.
 /// This structure can be seen as a link between the [`Circuit`] public input
 /// positions and the [`VerifierKey`] that the Verifier needs to use.
+///
+/// The named Public Inputs are serialized after the positions. Data
+/// serialized before they were added lacks them, and must be read with
+/// [`VerifierData::deserialize_unnamed`].
 #[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
 #[derivative(
     Clone(bound = ""),
@@ -123,6 +194,9 @@ where
 
     /// Public Input Positions
     pub pi_pos: Vec<usize>,
+
+    /// Named Public Inputs
+    pub named_pi: Vec<NamedPublicInput>,
 }
 
 impl<E, P> VerifierData<E, P>
@@ -133,7 +207,40 @@ where
     /// Creates a new `VerifierData` from a [`VerifierKey`] and the public
     /// input positions of the circuit that it represents.
     pub fn new(key: VerifierKey<E, P>, pi_pos: Vec<usize>) -> Self {
-        Self { key, pi_pos }
+        Self {
+            key,
+            pi_pos,
+            named_pi: Vec::new(),
+        }
+    }
+
+    /// Creates a new `VerifierData` from a [`VerifierKey`], the public input
+    /// positions and the named public inputs of the circuit that it
+    /// represents.
+    pub fn with_named_pi(
+        key: VerifierKey<E, P>,
+        pi_pos: Vec<usize>,
+        named_pi: Vec<NamedPublicInput>,
+    ) -> Self {
+        Self {
+            key,
+            pi_pos,
+            named_pi,
+        }
+    }
+
+    /// Deserializes a `VerifierData` serialized before named Public Inputs
+    /// were added, that is, a [`VerifierKey`] followed by the Public Input
+    /// positions.
+    pub fn deserialize_unnamed<R>(
+        mut reader: R,
+    ) -> Result<Self, SerializationError>
+    where
+        R: Read,
+    {
+        let key = VerifierKey::deserialize(&mut reader)?;
+        let pi_pos = Vec::deserialize(&mut reader)?;
+        Ok(Self::new(key, pi_pos))
     }
 
     /// Returns a reference to the contained [`VerifierKey`].
@@ -145,6 +252,70 @@ where
     pub fn pi_pos(&self) -> &[usize] {
         &self.pi_pos
     }
+
+    /// Returns a reference to the contained named Public Inputs.
+    pub fn named_pi(&self) -> &[NamedPublicInput] {
+        &self.named_pi
+    }
+
+    /// Orders the given named Public Input values by position, so they can
+    /// be passed to [`verify_proof`] along with [`Self::pi_pos`].
+    ///
+    /// Fails with [`Error::PublicInputMismatch`] if an input is missing,
+    /// unknown or of the wrong type. Public inputs of the circuit that were
+    /// not registered under a name are reported as missing.
+    pub fn order_named_pi(
+        &self,
+        public_inputs: &BTreeMap<String, PublicInputValue<P>>,
+    ) -> Result<Vec<PublicInputValue<P>>, Error> {
+        let mut missing = Vec::new();
+        let mut wrong_type = Vec::new();
+        let mut values = BTreeMap::new();
+        for named in &self.named_pi {
+            match public_inputs.get(&named.name) {
+                None => missing.push(named.name.clone()),
+                Some(value) if value.input_type() != Some(named.ty) => {
+                    wrong_type.push(named.name.clone())
+                }
+                Some(value) => {
+                    values.extend(
+                        named.positions.iter().copied().zip(&value.values),
+                    );
+                }
+            }
+        }
+        let extra: Vec<String> = public_inputs
+            .keys()
+            .filter(|name| self.named_pi.iter().all(|pi| &pi.name != *name))
+            .cloned()
+            .collect();
+        let named_positions: Vec<usize> = self
+            .named_pi
+            .iter()
+            .flat_map(|pi| pi.positions.iter().copied())
+            .collect();
+        missing.extend(
+            self.pi_pos
+                .iter()
+                .filter(|pos| !named_positions.contains(pos))
+                .map(|pos| format!("<unnamed at {}>", pos)),
+        );
+
+        if !missing.is_empty() || !extra.is_empty() || !wrong_type.is_empty() {
+            return Err(Error::PublicInputMismatch {
+                missing,
+                extra,
+                wrong_type,
+            });
+        }
+        Ok(self
+            .pi_pos
+            .iter()
+            .map(|pos| PublicInputValue {
+                values: vec![*values[pos]],
+            })
+            .collect())
+    }
 }
 
 /// Trait that should be implemented for any circuit function to provide to it
@@ -167,8 +338,7 @@ where
 /// use ark_plonk::constraint_system::StandardComposer;
 /// use ark_plonk::error::Error;
 /// use ark_plonk::prelude::VerifierData;
-/// use ark_poly::polynomial::univariate::DensePolynomial;
-/// use ark_poly_commit::kzg10::KZG10;
+/// use ark_plonk::setup::PublicParameters;
 /// use num_traits::{Zero, One};
 /// use rand_core::OsRng;
 ///
@@ -234,8 +404,8 @@ where
 ///         composer.assert_equal(add_result, composer.zero_var());
 ///
 ///         // Check that a and b are in range
-///         composer.range_gate(a, 1 << 6);
-///         composer.range_gate(b, 1 << 5);
+///         composer.range_gate(a, 1 << 6)?;
+///         composer.range_gate(b, 1 << 5)?;
 ///
 ///         // Make second constraint a * b = d
 ///         let mul_result = composer.mul(E::Fr::one(), a, b, E::Fr::zero(), Some(-self.d));
@@ -258,9 +428,7 @@ where
 ///     }
 /// }
 ///
-/// let pp = KZG10::<Bls12_381,DensePolynomial<BlsScalar>,>::setup(
-///     1 << 12, false, &mut OsRng
-///  )?;
+/// let pp = PublicParameters::<Bls12_381>::setup(1 << 12, &mut OsRng)?;
 ///
 /// // Initialize the circuit
 /// let mut circuit = TestCircuit::<Bls12_381, JubjubParameters>::default();
@@ -294,7 +462,7 @@ where
 ///     BlsScalar::from(100u64).into_pi(),
 ///     GeIntoPubInput::into_pi(point_f_pi),
 /// ];
-/// let VerifierData { key, pi_pos } = vd;
+/// let VerifierData { key, pi_pos, .. } = vd;
 /// verify_proof(
 ///     &pp,
 ///     key,
@@ -324,70 +492,53 @@ where
     #[allow(clippy::type_complexity)] // NOTE: Clippy is too hash here.
     fn compile(
         &mut self,
-        u_params: &UniversalParams<E>,
+        pp: &PublicParameters<E>,
     ) -> Result<(ProverKey<E::Fr, P>, VerifierData<E, P>), Error> {
-        // Setup PublicParams
-        // XXX: KZG10 does not have a trim function so we use sonics and
-        // then do a transformation between sonic CommiterKey to KZG10
-        // powers
-        let circuit_size = self.padded_circuit_size();
-        let (ck, _) = SonicKZG10::<E, DensePolynomial<E::Fr>>::trim(
-            u_params,
-            circuit_size,
-            0,
-            None,
-        )
-        .unwrap();
-        let powers = Powers {
-            powers_of_g: ck.powers_of_g.into(),
-            powers_of_gamma_g: ck.powers_of_gamma_g.into(),
-        };
-        //Generate & save `ProverKey` with some random values.
-        let mut prover = Prover::new(b"CircuitCompilation");
-        self.gadget(prover.mut_cs())?;
-        let pi_pos = prover.mut_cs().pi_positions();
-        prover.preprocess(&powers)?;
-
-        // Generate & save `VerifierKey` with some random values.
-        let mut verifier = Verifier::new(b"CircuitCompilation");
-        self.gadget(verifier.mut_cs())?;
-        verifier.preprocess(&powers)?;
+        let powers = pp.commit_key(self.padded_circuit_size())?;
+        // Generate the `ProverKey` and `VerifierKey` in a single pass.
+        let mut composer = StandardComposer::new();
+        self.gadget(&mut composer)?;
+        let pi_pos = composer.pi_positions();
+        let named_pi = composer.named_public_inputs().to_vec();
+        let (prover_key, verifier_key) = composer.preprocess(
+            &powers,
+            &mut TranscriptWrapper::new(b"CircuitCompilation"),
+        )?;
         Ok((
-            prover
-                .prover_key
-                .expect("Unexpected error. Missing ProverKey in compilation"),
-            VerifierData::new(
-                verifier.verifier_key.expect(
-                    "Unexpected error. Missing VerifierKey in compilation",
-                ),
-                pi_pos,
-            ),
+            prover_key,
+            VerifierData::with_named_pi(verifier_key, pi_pos, named_pi),
         ))
     }
 
+    /// Compiles the circuit, reusing the keys saved in `keystore` for the same
+    /// [`CIRCUIT_ID`](Self::CIRCUIT_ID), setup parameters and library version.
+    /// Missing or stale keys are recompiled and saved.
+    #[cfg(feature = "std")]
+    #[allow(clippy::type_complexity)] // NOTE: Clippy is too hash here.
+    fn compile_with_keystore(
+        &mut self,
+        pp: &PublicParameters<E>,
+        keystore: &KeyStore,
+    ) -> Result<(ProverKey<E::Fr, P>, VerifierData<E, P>), Error> {
+        match keystore.load(Self::CIRCUIT_ID, pp) {
+            Ok(Some(keys)) => return Ok(keys),
+            Ok(None) | Err(Error::StaleKey { .. }) => {}
+            Err(error) => return Err(error),
+        }
+        let (prover_key, verifier_data) = self.compile(pp)?;
+        keystore.save(Self::CIRCUIT_ID, pp, &prover_key, &verifier_data)?;
+        Ok((prover_key, verifier_data))
+    }
+
     /// Generates a proof using the provided `CircuitInputs` & `ProverKey`
     /// instances.
     fn gen_proof(
         &mut self,
-        u_params: &UniversalParams<E>,
+        pp: &PublicParameters<E>,
         prover_key: ProverKey<E::Fr, P>,
         transcript_init: &'static [u8],
     ) -> Result<Proof<E, P>, Error> {
-        // XXX: KZG10 does not have a trim function so we use sonics and
-        // then do a transformation between sonic CommiterKey to KZG10
-        // powers
-        let circuit_size = self.padded_circuit_size();
-        let (ck, _) = SonicKZG10::<E, DensePolynomial<E::Fr>>::trim(
-            u_params,
-            circuit_size,
-            0,
-            None,
-        )
-        .unwrap();
-        let powers = Powers {
-            powers_of_g: ck.powers_of_g.into(),
-            powers_of_gamma_g: ck.powers_of_gamma_g.into(),
-        };
+        let powers = pp.commit_key(self.padded_circuit_size())?;
         // New Prover instance
         let mut prover = Prover::new(transcript_init);
         // Fill witnesses for Prover
@@ -403,44 +554,90 @@ where
 
 /// Verifies a proof using the provided `CircuitInputs` & `VerifierKey`
 /// instances.
+#[cfg(feature = "std")]
 pub fn verify_proof<E, P>(
-    u_params: &UniversalParams<E>,
+    pp: &PublicParameters<E>,
+    plonk_verifier_key: VerifierKey<E, P>,
+    proof: &Proof<E, P>,
+    pub_inputs_values: &[PublicInputValue<P>],
+    pub_inputs_positions: &[usize],
+    transcript_init: &'static [u8],
+) -> Result<(), Error>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    verify_proof_with_rng(
+        pp,
+        plonk_verifier_key,
+        proof,
+        pub_inputs_values,
+        pub_inputs_positions,
+        transcript_init,
+        &mut rand_core::OsRng,
+    )
+}
+
+/// Verifies a proof like `verify_proof`, drawing the randomness of the
+/// batched opening check from `rng`, which must be unpredictable to the
+/// prover.
+///
+/// Unlike `verify_proof`, this is available without the `std` feature.
+pub fn verify_proof_with_rng<E, P, R>(
+    pp: &PublicParameters<E>,
     plonk_verifier_key: VerifierKey<E, P>,
     proof: &Proof<E, P>,
     pub_inputs_values: &[PublicInputValue<P>],
     pub_inputs_positions: &[usize],
     transcript_init: &'static [u8],
+    rng: &mut R,
 ) -> Result<(), Error>
 where
     E: PairingEngine,
     P: TEModelParameters<BaseField = E::Fr>,
+    R: CryptoRng + RngCore,
 {
     let mut verifier: Verifier<E, P> = Verifier::new(transcript_init);
     let padded_circuit_size = plonk_verifier_key.padded_circuit_size();
     // let key: VerifierKey<E, P> = *plonk_verifier_key;
     verifier.verifier_key = Some(plonk_verifier_key);
-    let (_, sonic_vk) = SonicKZG10::<E, DensePolynomial<E::Fr>>::trim(
-        u_params,
-        padded_circuit_size,
-        0,
-        None,
-    )
-    .unwrap();
-
-    let vk = kzg10::VerifierKey {
-        g: sonic_vk.g,
-        gamma_g: sonic_vk.gamma_g,
-        h: sonic_vk.h,
-        beta_h: sonic_vk.beta_h,
-        prepared_h: sonic_vk.prepared_h,
-        prepared_beta_h: sonic_vk.prepared_beta_h,
-    };
-
-    verifier.verify(
+    let vk = pp.verifier_key()?;
+
+    verifier.verify_with_rng(
         proof,
         &vk,
-        build_pi(pub_inputs_values, pub_inputs_positions, padded_circuit_size)
+        build_pi(pub_inputs_values, pub_inputs_positions, padded_circuit_size)?
             .as_slice(),
+        rng,
+    )
+}
+
+/// Verifies a proof against the named Public Inputs registered in the
+/// given [`VerifierData`].
+///
+/// Unlike [`verify_proof`], the values do not need to be ordered: they are
+/// looked up by name, and any missing, unknown or wrongly typed input is
+/// reported through [`Error::PublicInputMismatch`].
+#[cfg(feature = "std")]
+pub fn verify_proof_with_named_inputs<E, P>(
+    pp: &PublicParameters<E>,
+    verifier_data: &VerifierData<E, P>,
+    proof: &Proof<E, P>,
+    pub_inputs: &BTreeMap<String, PublicInputValue<P>>,
+    transcript_init: &'static [u8],
+) -> Result<(), Error>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    let pub_inputs_values = verifier_data.order_named_pi(pub_inputs)?;
+    verify_proof(
+        pp,
+        verifier_data.key.clone(),
+        proof,
+        &pub_inputs_values,
+        &verifier_data.pi_pos,
+        transcript_init,
     )
 }
 
@@ -449,20 +646,23 @@ fn build_pi<F, P>(
     pub_input_values: &[PublicInputValue<P>],
     pub_input_pos: &[usize],
     trim_size: usize,
-) -> Vec<F>
+) -> Result<Vec<F>, Error>
 where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
 {
     let mut pi = vec![F::zero(); trim_size];
-    pub_input_values
+    for (value, pos) in pub_input_values
         .iter()
         .flat_map(|pub_input| pub_input.values.clone())
         .zip(pub_input_pos.iter().copied())
-        .for_each(|(value, pos)| {
-            pi[pos] = -value;
-        });
-    pi
+    {
+        *pi.get_mut(pos).ok_or(Error::PublicInputOutOfRange {
+            position: pos,
+            circuit_size: trim_size,
+        })? = -value;
+    }
+    Ok(pi)
 }
 
 #[cfg(test)]
@@ -473,7 +673,6 @@ mod test {
     use ark_bls12_381::Bls12_381;
     use ark_ec::twisted_edwards_extended::GroupAffine;
     use ark_ec::AffineCurve;
-    use ark_poly_commit::kzg10::KZG10;
     use num_traits::{One, Zero};
 
     // Implements a circuit that checks:
@@ -520,8 +719,8 @@ mod test {
             composer.assert_equal(add_result, composer.zero_var());
 
             // Check that a and b are in range
-            composer.range_gate(a, 1 << 6);
-            composer.range_gate(b, 1 << 5);
+            composer.range_gate(a, 1 << 6)?;
+            composer.range_gate(b, 1 << 5)?;
             // Make second constraint a * b = d
             let mul_result =
                 composer.mul(E::Fr::one(), a, b, E::Fr::zero(), Some(-self.d));
@@ -549,11 +748,7 @@ mod test {
         use rand_core::OsRng;
 
         // Generate CRS
-        let pp = KZG10::<E, DensePolynomial<E::Fr>>::setup(
-            1 << 12,
-            false,
-            &mut OsRng,
-        )?;
+        let pp = PublicParameters::<E>::setup(1 << 12, &mut OsRng)?;
 
         let mut circuit = TestCircuit::<E, P>::default();
 
@@ -598,7 +793,7 @@ mod test {
             GeIntoPubInput::into_pi(point_f_pi),
         ];
 
-        let VerifierData { key, pi_pos } = verifier_data;
+        let VerifierData { key, pi_pos, .. } = verifier_data;
 
         // TODO: non-ideal hack for a first functional version.
         assert!(verify_proof::<E, P>(
@@ -614,6 +809,211 @@ mod test {
         Ok(())
     }
 
+    // Implements a circuit that checks:
+    // 1) a + b = sum where sum is a PI named "sum"
+    // 2) JubJub::GENERATOR * e(JubJubScalar) = f where f is a PI named "f"
+    #[derive(derivative::Derivative)]
+    #[derivative(Debug(bound = ""), Default(bound = ""))]
+    pub struct NamedCircuit<
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    > {
+        a: E::Fr,
+        b: E::Fr,
+        e: P::ScalarField,
+    }
+
+    impl<E, P> Circuit<E, P> for NamedCircuit<E, P>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        const CIRCUIT_ID: [u8; 32] = [0xfe; 32];
+
+        fn gadget(
+            &mut self,
+            composer: &mut StandardComposer<E, P>,
+        ) -> Result<(), Error> {
+            let a = composer.add_input(self.a);
+            let b = composer.add_input(self.b);
+            // Unused gate, removed by the optimizer
+            composer.mul(E::Fr::one(), a, b, E::Fr::zero(), None);
+            let sum = composer.add(
+                (E::Fr::one(), a),
+                (E::Fr::one(), b),
+                E::Fr::zero(),
+                None,
+            );
+            composer.public_input("sum", sum)?;
+
+            let e = composer
+                .add_input(util::from_embedded_curve_scalar::<E, P>(self.e));
+            let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+            let generator = GroupAffine::new(x, y);
+            let f = composer.fixed_base_scalar_mul(e, generator);
+            composer.public_point("f", f)?;
+            composer.optimize();
+            Ok(())
+        }
+
+        fn padded_circuit_size(&self) -> usize {
+            1 << 11
+        }
+    }
+
+    fn test_named_public_inputs<
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    >() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::<E>::setup(1 << 12, &mut OsRng)?;
+        let (pk_p, verifier_data) =
+            NamedCircuit::<E, P>::default().compile(&pp)?;
+
+        let names: Vec<&str> = verifier_data
+            .named_pi()
+            .iter()
+            .map(|pi| pi.name.as_str())
+            .collect();
+        assert_eq!(names, ["sum", "f"]);
+        let positions: Vec<usize> = verifier_data
+            .named_pi()
+            .iter()
+            .flat_map(|pi| pi.positions.clone())
+            .collect();
+        assert_eq!(positions, verifier_data.pi_pos);
+
+        let mut verifier_data_bytes = Vec::new();
+        verifier_data.serialize(&mut verifier_data_bytes).unwrap();
+        let verif_data: VerifierData<E, P> =
+            VerifierData::deserialize(verifier_data_bytes.as_slice()).unwrap();
+        assert!(verif_data == verifier_data);
+
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator: GroupAffine<P> = GroupAffine::new(x, y);
+        let point_f: GroupAffine<P> = AffineCurve::mul(
+            &generator,
+            P::ScalarField::from(3u64).into_repr(),
+        )
+        .into_affine();
+
+        let proof = NamedCircuit::<E, P> {
+            a: E::Fr::from(20u64),
+            b: E::Fr::from(5u64),
+            e: P::ScalarField::from(3u64),
+        }
+        .gen_proof(&pp, pk_p, b"Test")?;
+
+        let mut public_inputs = BTreeMap::new();
+        public_inputs.insert("f".to_string(), GeIntoPubInput::into_pi(point_f));
+        public_inputs.insert("sum".to_string(), E::Fr::from(25u64).into_pi());
+        assert!(verify_proof_with_named_inputs(
+            &pp,
+            &verifier_data,
+            &proof,
+            &public_inputs,
+            b"Test",
+        )
+        .is_ok());
+
+        // Wrong value
+        let mut wrong_value = public_inputs.clone();
+        wrong_value.insert("sum".to_string(), E::Fr::from(26u64).into_pi());
+        assert!(matches!(
+            verify_proof_with_named_inputs(
+                &pp,
+                &verifier_data,
+                &proof,
+                &wrong_value,
+                b"Test",
+            ),
+            Err(Error::ProofVerificationError)
+        ));
+
+        // Missing, extra and wrongly typed inputs
+        let mut mismatched = public_inputs.clone();
+        mismatched.remove("sum");
+        mismatched.insert("g".to_string(), E::Fr::from(1u64).into_pi());
+        mismatched.insert("f".to_string(), E::Fr::from(1u64).into_pi());
+        match verify_proof_with_named_inputs(
+            &pp,
+            &verifier_data,
+            &proof,
+            &mismatched,
+            b"Test",
+        ) {
+            Err(Error::PublicInputMismatch {
+                missing,
+                extra,
+                wrong_type,
+            }) => {
+                assert_eq!(missing, ["sum"]);
+                assert_eq!(extra, ["g"]);
+                assert_eq!(wrong_type, ["f"]);
+            }
+            _ => panic!("expected a public input mismatch"),
+        }
+
+        // Circuits with unnamed public inputs can't be verified by name
+        let unnamed = VerifierData::new(
+            verifier_data.key.clone(),
+            verifier_data.pi_pos.clone(),
+        );
+
+        // Verifier data serialized without names can still be read
+        let mut unnamed_bytes = Vec::new();
+        unnamed.key.serialize(&mut unnamed_bytes).unwrap();
+        unnamed.pi_pos.serialize(&mut unnamed_bytes).unwrap();
+        assert!(
+            VerifierData::deserialize_unnamed(unnamed_bytes.as_slice())
+                .unwrap()
+                == unnamed
+        );
+        assert!(matches!(
+            unnamed.order_named_pi(&BTreeMap::new()),
+            Err(Error::PublicInputMismatch { missing, .. })
+                if missing.len() == 3
+        ));
+
+        // Names can only be registered once
+        let mut composer = StandardComposer::<E, P>::new();
+        let a = composer.add_input(E::Fr::one());
+        composer.public_input("a", a)?;
+        let circuit_size = composer.circuit_size();
+        assert!(matches!(
+            composer.public_input("a", a),
+            Err(Error::DuplicatePublicInputName { name }) if name == "a"
+        ));
+        let point = composer.add_affine(generator);
+        assert!(matches!(
+            composer.public_point("a", point),
+            Err(Error::DuplicatePublicInputName { .. })
+        ));
+        assert_eq!(composer.circuit_size(), circuit_size);
+        assert_eq!(composer.named_public_inputs().len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_named_public_inputs_on_Bls12_381() -> Result<(), Error> {
+        test_named_public_inputs::<
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters,
+        >()
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_named_public_inputs_on_Bls12_377() -> Result<(), Error> {
+        test_named_public_inputs::<
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters,
+        >()
+    }
+
     #[test]
     #[allow(non_snake_case)]
     fn test_full_on_Bls12_381() -> Result<(), Error> {
@@ -0,0 +1,386 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Static Circuit Analysis
+//!
+//! [`StandardComposer::analyze`] looks at the selectors and wires of a
+//! circuit, without its witness, for the usual signs of an under-constrained
+//! gadget.
+
+use crate::constraint_system::{StandardComposer, Variable, WireData};
+use alloc::vec::Vec;
+use ark_ec::{PairingEngine, TEModelParameters};
+use core::fmt;
+use core::ops::Range;
+use num_traits::Zero;
+
+/// Gates added by [`StandardComposer::new`] to blind the witness
+/// polynomials, whose variables are free on purpose.
+const BLINDING_GATES: Range<usize> = 1..3;
+
+/// Issue found by [`StandardComposer::analyze`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Diagnostic {
+    /// No gate equation depends on the variable, so any value satisfies the
+    /// circuit.
+    UnconstrainedVariable {
+        /// Variable
+        variable: Variable,
+    },
+
+    /// A single arithmetic gate depends on the variable, so it is either a
+    /// result no other gate uses or only tied to the other wires of `gate`.
+    WeaklyConstrainedVariable {
+        /// Variable
+        variable: Variable,
+        /// Gate depending on the variable
+        gate: usize,
+    },
+
+    /// The public input of `gate` is not compared to any wire, so it doesn't
+    /// bind the witness.
+    UnusedPublicInput {
+        /// Gate holding the public input
+        gate: usize,
+    },
+
+    /// The gate has all of its selectors set to zero without holding the
+    /// next wires of a custom gate, so it enforces nothing.
+    EmptyGate {
+        /// Gate
+        gate: usize,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnconstrainedVariable { variable } => {
+                write!(f, "variable {} is unconstrained", variable.0)
+            }
+            Self::WeaklyConstrainedVariable { variable, gate } => write!(
+                f,
+                "variable {} is only constrained by gate {}",
+                variable.0, gate
+            ),
+            Self::UnusedPublicInput { gate } => write!(
+                f,
+                "public input of gate {} is not bound to any wire",
+                gate
+            ),
+            Self::EmptyGate { gate } => {
+                write!(f, "gate {} has no selector enabled", gate)
+            }
+        }
+    }
+}
+
+impl<E, P> StandardComposer<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Returns the variables no gate depends on, or only an arithmetic gate
+    /// also depending on other variables, the public inputs not bound to a
+    /// wire and the gates without any selector.
+    ///
+    /// A gate depends on the wires a non-zero selector multiplies, and on
+    /// every wire of a custom gate, including the ones it reads from the next
+    /// gate. Custom gates relate all of their wires, so variables local to
+    /// one of them are not reported. Variables are reported first, ordered by
+    /// index, followed by gates.
+    pub fn analyze(&self) -> Vec<Diagnostic> {
+        let mut variables: Vec<_> = self.perm.variable_map.iter().collect();
+        variables.sort_by_key(|(var, _)| var.0);
+
+        // Gates depending on each variable, and variables of each gate
+        let mut var_gates = Vec::with_capacity(variables.len());
+        let mut gate_vars: Vec<Vec<Variable>> = vec![Vec::new(); self.n];
+        for (var, wires) in &variables {
+            let mut gates: Vec<usize> = wires
+                .iter()
+                .flat_map(|wire| self.wire_gates(*wire))
+                .collect();
+            gates.sort_unstable();
+            gates.dedup();
+            for gate in &gates {
+                gate_vars[*gate].push(**var);
+            }
+            var_gates.push(gates);
+        }
+
+        let mut diagnostics = Vec::new();
+        for ((var, wires), gates) in variables.iter().zip(var_gates) {
+            let is_blinding = !wires.is_empty()
+                && wires
+                    .iter()
+                    .all(|wire| BLINDING_GATES.contains(&wire_gate(*wire)));
+            match gates.as_slice() {
+                _ if is_blinding => {}
+                [] => diagnostics.push(Diagnostic::UnconstrainedVariable {
+                    variable: **var,
+                }),
+                [gate]
+                    if !self.is_custom_gate(*gate)
+                        && gate_vars[*gate].len() > 1 =>
+                {
+                    diagnostics.push(Diagnostic::WeaklyConstrainedVariable {
+                        variable: **var,
+                        gate: *gate,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        for gate in self.public_inputs_sparse_store.keys() {
+            if gate_vars[*gate].is_empty() {
+                diagnostics.push(Diagnostic::UnusedPublicInput { gate: *gate });
+            }
+        }
+
+        for gate in 0..self.n {
+            let selectors = [
+                self.q_m[gate],
+                self.q_l[gate],
+                self.q_r[gate],
+                self.q_o[gate],
+                self.q_4[gate],
+                self.q_c[gate],
+            ];
+            let is_empty = selectors.iter().all(Zero::is_zero)
+                && !self.is_custom_gate(gate)
+                && !self.public_inputs_sparse_store.contains_key(&gate)
+                && !(gate > 0 && self.is_custom_gate(gate - 1));
+            if is_empty {
+                diagnostics.push(Diagnostic::EmptyGate { gate });
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Returns `true` if a custom gate selector is enabled at `gate`.
+    fn is_custom_gate(&self, gate: usize) -> bool {
+        !(self.q_range[gate].is_zero()
+            && self.q_logic[gate].is_zero()
+            && self.q_fixed_group_add[gate].is_zero()
+            && self.q_variable_group_add[gate].is_zero()
+            && self.q_variable_group_double[gate].is_zero())
+    }
+
+    /// Returns the gates whose equation depends on the value of `wire`: the
+    /// gate holding it and the custom gate before it reading its next wires.
+    fn wire_gates(&self, wire: WireData) -> impl Iterator<Item = usize> {
+        let gate = wire_gate(wire);
+        let q_m = self.q_m[gate];
+        let used = self.is_custom_gate(gate)
+            || (!self.q_arith[gate].is_zero()
+                && match wire {
+                    WireData::Left(_) => {
+                        !(self.q_l[gate].is_zero() && q_m.is_zero())
+                    }
+                    WireData::Right(_) => {
+                        !(self.q_r[gate].is_zero() && q_m.is_zero())
+                    }
+                    WireData::Output(_) => !self.q_o[gate].is_zero(),
+                    WireData::Fourth(_) => !self.q_4[gate].is_zero(),
+                });
+        let read_as_next = gate > 0
+            && self.is_custom_gate(gate - 1)
+            && !matches!(wire, WireData::Output(_));
+        used.then_some(gate)
+            .into_iter()
+            .chain(read_as_next.then(|| gate - 1))
+    }
+}
+
+/// Returns the gate holding `wire`.
+fn wire_gate(wire: WireData) -> usize {
+    match wire {
+        WireData::Left(gate)
+        | WireData::Right(gate)
+        | WireData::Output(gate)
+        | WireData::Fourth(gate) => gate,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::twisted_edwards_extended::GroupAffine;
+    use num_traits::One;
+
+    fn test_analyze_new_composer<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let composer = StandardComposer::<E, P>::new();
+        assert_eq!(composer.analyze(), []);
+    }
+
+    fn test_analyze_variables<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut composer = StandardComposer::<E, P>::new();
+        let unused = composer.add_input(E::Fr::one());
+        let a = composer.add_input(E::Fr::from(3u64));
+        let b = composer.add_input(E::Fr::from(5u64));
+        // `b` is wired with a zero coefficient
+        let gate = composer.circuit_size();
+        let c = composer.add(
+            (E::Fr::one(), a),
+            (E::Fr::zero(), b),
+            E::Fr::zero(),
+            None,
+        );
+        composer.boolean_gate(c);
+        let d = composer.mul(E::Fr::one(), c, c, E::Fr::zero(), None);
+
+        assert_eq!(
+            composer.analyze(),
+            [
+                Diagnostic::UnconstrainedVariable { variable: unused },
+                Diagnostic::WeaklyConstrainedVariable { variable: a, gate },
+                Diagnostic::UnconstrainedVariable { variable: b },
+                Diagnostic::WeaklyConstrainedVariable {
+                    variable: d,
+                    gate: gate + 2
+                },
+            ]
+        );
+        assert_eq!(
+            composer.analyze()[0].to_string(),
+            format!("variable {} is unconstrained", unused.0)
+        );
+    }
+
+    fn test_analyze_gates<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut composer = StandardComposer::<E, P>::new();
+        let zero = composer.zero_var();
+        let a = composer.add_input(E::Fr::from(7u64));
+        composer.assert_equal(a, a);
+
+        // A public input compared to a constant only
+        let unused_pi = composer.circuit_size();
+        composer.poly_gate(
+            zero,
+            zero,
+            zero,
+            E::Fr::zero(),
+            E::Fr::zero(),
+            E::Fr::zero(),
+            E::Fr::zero(),
+            E::Fr::zero(),
+            Some(E::Fr::zero()),
+        );
+        let empty = composer.circuit_size();
+        composer.poly_gate(
+            zero,
+            zero,
+            zero,
+            E::Fr::zero(),
+            E::Fr::zero(),
+            E::Fr::zero(),
+            E::Fr::zero(),
+            E::Fr::zero(),
+            None,
+        );
+        composer.constrain_to_constant(
+            a,
+            E::Fr::zero(),
+            Some(-E::Fr::from(7u64)),
+        );
+
+        assert_eq!(
+            composer.analyze(),
+            [
+                Diagnostic::UnusedPublicInput { gate: unused_pi },
+                Diagnostic::EmptyGate { gate: empty },
+            ]
+        );
+    }
+
+    fn test_analyze_gadgets<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut composer = StandardComposer::<E, P>::new();
+        let a = composer.add_input(E::Fr::from(5u64));
+        let b = composer.add_input(E::Fr::from(6u64));
+        composer.range_gate(a, 8).unwrap();
+        let xor = composer.xor_gate(a, b, 4).unwrap();
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let point = composer.fixed_base_scalar_mul(a, GroupAffine::new(x, y));
+        let point = composer.point_doubling_gate(point);
+        composer
+            .assert_equal_public_point(point, point_value(&composer, point));
+        composer.public_input("xor", xor).unwrap();
+
+        // Custom gates and the rows holding their next wires are neither
+        // empty nor reported, but the logic gate never ties its inputs to the
+        // quads it accumulates.
+        assert_eq!(
+            composer.analyze(),
+            [Diagnostic::UnconstrainedVariable { variable: b }]
+        );
+    }
+
+    fn point_value<E, P>(
+        composer: &StandardComposer<E, P>,
+        point: crate::constraint_system::ecc::Point<E, P>,
+    ) -> GroupAffine<P>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        GroupAffine::new(
+            composer.variables[point.x()],
+            composer.variables[point.y()],
+        )
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_analyze_new_composer,
+            test_analyze_variables,
+            test_analyze_gates,
+            test_analyze_gadgets
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_analyze_new_composer,
+            test_analyze_variables,
+            test_analyze_gates,
+            test_analyze_gadgets
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
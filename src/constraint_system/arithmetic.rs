@@ -10,6 +10,7 @@
 
 use crate::constraint_system::StandardComposer;
 use crate::constraint_system::Variable;
+use alloc::vec::Vec;
 use ark_ec::{PairingEngine, TEModelParameters};
 use num_traits::{One, Zero};
 
@@ -80,6 +81,7 @@ where
         self.q_logic.push(E::Fr::zero());
         self.q_fixed_group_add.push(E::Fr::zero());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::zero());
 
         if let Some(pi) = pi {
             assert!(self.public_inputs_sparse_store.insert(self.n, pi).is_none(),"The invariant of already having a PI inserted for this position should never exist");
@@ -159,6 +161,7 @@ where
         self.q_logic.push(E::Fr::zero());
         self.q_fixed_group_add.push(E::Fr::zero());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::zero());
 
         if let Some(pi) = pi {
             assert!(
@@ -225,6 +228,7 @@ where
         self.q_logic.push(E::Fr::zero());
         self.q_fixed_group_add.push(E::Fr::zero());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::zero());
 
         if let Some(pi) = pi {
             assert!(
@@ -431,13 +435,70 @@ where
             pi,
         )
     }
+
+    /// Computes `sum(q_i * w_i) + constant` into a new [`Variable`], packing
+    /// the terms into as few [`StandardComposer::big_add_gate`]s as possible.
+    ///
+    /// The first row absorbs three terms and every following row two more
+    /// plus the running accumulator, so a sum of `k` terms costs
+    /// `1 + ceil((k - 3) / 2)` rows instead of the `k - 1` needed by chaining
+    /// [`StandardComposer::add`] calls. A single unscaled term without
+    /// constant is returned as is, without adding any gate.
+    pub fn linear_combination(
+        &mut self,
+        terms: &[(E::Fr, Variable)],
+        constant: E::Fr,
+    ) -> Variable {
+        match terms {
+            [] if constant.is_zero() => self.zero_var,
+            [] => self.add_witness_to_circuit_description(constant),
+            [(q, var)] if q.is_one() && constant.is_zero() => *var,
+            terms => {
+                let last = self.reduce_linear_terms(terms, constant, 3);
+                let q_c = if last.len() == terms.len() {
+                    constant
+                } else {
+                    E::Fr::zero()
+                };
+                let zero = (E::Fr::zero(), self.zero_var);
+                self.big_add(
+                    last[0],
+                    last.get(1).copied().unwrap_or(zero),
+                    last.get(2).copied(),
+                    q_c,
+                    None,
+                )
+            }
+        }
+    }
+
+    /// Packs `terms` into rows of three inputs, chaining the partial sums,
+    /// until at most `remaining` terms are left and returns those. The
+    /// `constant` is absorbed by the first row that gets emitted.
+    pub(crate) fn reduce_linear_terms(
+        &mut self,
+        terms: &[(E::Fr, Variable)],
+        constant: E::Fr,
+        remaining: usize,
+    ) -> Vec<(E::Fr, Variable)> {
+        let mut terms = terms.to_vec();
+        let mut q_c = constant;
+        while terms.len() > remaining {
+            let rest = terms.split_off(3);
+            let acc =
+                self.big_add(terms[0], terms[1], Some(terms[2]), q_c, None);
+            q_c = E::Fr::zero();
+            terms = core::iter::once((E::Fr::one(), acc)).chain(rest).collect();
+        }
+        terms
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::batch_test;
-    use crate::constraint_system::helper::*;
+    use crate::testing::*;
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
 
@@ -729,6 +790,112 @@ mod test {
         assert!(res.is_err());
     }
 
+    fn test_linear_combination<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                // 1*1 + 2*2 + ... + 10*10 + 5 = 390
+                let terms: Vec<_> = (1..=10u64)
+                    .map(|i| {
+                        let var = composer.add_input(E::Fr::from(i));
+                        (E::Fr::from(i), var)
+                    })
+                    .collect();
+
+                // Naive chain of `add` calls costs one row per extra term.
+                let size = composer.circuit_size();
+                let mut naive =
+                    composer.add(terms[0], terms[1], E::Fr::from(5u64), None);
+                for term in &terms[2..] {
+                    naive = composer.add(
+                        (E::Fr::one(), naive),
+                        *term,
+                        E::Fr::zero(),
+                        None,
+                    );
+                }
+                assert_eq!(composer.circuit_size() - size, 9);
+
+                let size = composer.circuit_size();
+                let packed =
+                    composer.linear_combination(&terms, E::Fr::from(5u64));
+                assert_eq!(composer.circuit_size() - size, 5);
+
+                composer.assert_equal(naive, packed);
+                composer.constrain_to_constant(
+                    packed,
+                    E::Fr::from(390u64),
+                    None,
+                );
+            },
+            64,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_linear_combination_small<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let a = composer.add_input(E::Fr::from(3u64));
+                let b = composer.add_input(E::Fr::from(4u64));
+
+                // Plain variables and empty sums don't need any gate.
+                let size = composer.circuit_size();
+                assert_eq!(
+                    composer.linear_combination(
+                        &[(E::Fr::one(), a)],
+                        E::Fr::zero()
+                    ),
+                    a
+                );
+                assert_eq!(
+                    composer.linear_combination(&[], E::Fr::zero()),
+                    composer.zero_var()
+                );
+                assert_eq!(composer.circuit_size(), size);
+
+                // Up to three terms fit in a single row.
+                let c = composer.linear_combination(
+                    &[
+                        (E::Fr::from(2u64), a),
+                        (-E::Fr::one(), b),
+                        (E::Fr::one(), a),
+                    ],
+                    E::Fr::one(),
+                );
+                assert_eq!(composer.circuit_size() - size, 1);
+                composer.constrain_to_constant(c, E::Fr::from(6u64), None);
+            },
+            32,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_incorrect_linear_combination<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let terms: Vec<_> = (1..=5u64)
+                    .map(|i| (E::Fr::one(), composer.add_input(E::Fr::from(i))))
+                    .collect();
+                let sum = composer.linear_combination(&terms, E::Fr::zero());
+                composer.constrain_to_constant(sum, E::Fr::from(16u64), None);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
     // Bls12-381 tests
     batch_test!(
         [
@@ -738,7 +905,10 @@ mod test {
             test_correct_big_add_mul_gate,
             test_correct_big_arith_gate,
             test_incorrect_add_mul_gate,
-            test_incorrect_big_arith_gate
+            test_incorrect_big_arith_gate,
+            test_linear_combination,
+            test_linear_combination_small,
+            test_incorrect_linear_combination
         ],
         [] => (
             Bls12_381,
@@ -755,7 +925,10 @@ mod test {
             test_correct_big_add_mul_gate,
             test_correct_big_arith_gate,
             test_incorrect_add_mul_gate,
-            test_incorrect_big_arith_gate
+            test_incorrect_big_arith_gate,
+            test_linear_combination,
+            test_linear_combination_small,
+            test_incorrect_linear_combination
         ],
         [] => (
             Bls12_377,
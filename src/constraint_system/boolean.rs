@@ -42,6 +42,7 @@ where
         self.q_logic.push(E::Fr::zero());
         self.q_fixed_group_add.push(E::Fr::zero());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::zero());
 
         self.perm
             .add_variables_to_map(a, a, a, self.zero_var, self.n);
@@ -56,7 +57,7 @@ where
 mod test {
     use super::*;
     use crate::batch_test;
-    use crate::constraint_system::helper::*;
+    use crate::testing::*;
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
     use num_traits::One;
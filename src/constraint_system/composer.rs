@@ -16,14 +16,20 @@
 //! It allows us not only to build Add and Mul constraints but also to build
 //! ECC op. gates, Range checks, Logical gates (Bitwise ops) etc.
 
+use crate::circuit::{NamedPublicInput, PublicInputType};
 use crate::constraint_system::Variable;
+use crate::error::Error;
 use crate::permutation::Permutation;
 use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
 use ark_ec::models::TEModelParameters;
 use ark_ec::PairingEngine;
 #[cfg(feature = "trace")]
 use ark_ff::{BigInteger, PrimeField};
 use core::marker::PhantomData;
+use core::ops::Range;
 use hashbrown::HashMap;
 use num_traits::{One, Zero};
 
@@ -85,6 +91,8 @@ where
     pub(crate) q_fixed_group_add: Vec<E::Fr>,
     /// Variable base group addition selector
     pub(crate) q_variable_group_add: Vec<E::Fr>,
+    /// Variable base group doubling selector
+    pub(crate) q_variable_group_double: Vec<E::Fr>,
 
     /// Sparse representation of the Public Inputs linking the positions of the
     /// non-zero ones to it's actual values.
@@ -112,6 +120,16 @@ where
     /// Permutation argument.
     pub(crate) perm: Permutation<E::Fr>,
 
+    /// Names of the namespaces currently open, outermost first.
+    pub(crate) namespace_stack: Vec<String>,
+
+    /// Gates covered by every namespace opened so far, identified by their
+    /// full `/`-separated path.
+    pub(crate) namespaces: Vec<(String, Range<usize>)>,
+
+    /// Public inputs registered under a name, in registration order.
+    pub(crate) named_public_inputs: Vec<NamedPublicInput>,
+
     /// Type Parameter Marker
     __: PhantomData<P>,
 }
@@ -145,6 +163,38 @@ where
         // or Iterator.
         self.public_inputs_sparse_store.keys().copied().collect()
     }
+
+    /// Returns the public inputs registered under a name in this Composer
+    /// instance.
+    pub fn named_public_inputs(&self) -> &[NamedPublicInput] {
+        &self.named_public_inputs
+    }
+
+    /// Records a named public input occupying the given gate positions.
+    pub(crate) fn register_public_input(
+        &mut self,
+        name: &str,
+        ty: PublicInputType,
+        positions: Vec<usize>,
+    ) {
+        self.named_public_inputs.push(NamedPublicInput {
+            name: name.to_string(),
+            ty,
+            positions,
+        });
+    }
+
+    /// Returns an error if a public input named `name` was already
+    /// registered.
+    pub(crate) fn check_public_input_name(
+        &self,
+        name: &str,
+    ) -> Result<(), Error> {
+        if self.named_public_inputs.iter().any(|pi| pi.name == name) {
+            return Err(Error::DuplicatePublicInputName { name: name.into() });
+        }
+        Ok(())
+    }
 }
 
 impl<E, P> Default for StandardComposer<E, P>
@@ -204,6 +254,7 @@ where
             q_logic: Vec::with_capacity(expected_size),
             q_fixed_group_add: Vec::with_capacity(expected_size),
             q_variable_group_add: Vec::with_capacity(expected_size),
+            q_variable_group_double: Vec::with_capacity(expected_size),
             public_inputs_sparse_store: BTreeMap::new(),
             w_l: Vec::with_capacity(expected_size),
             w_r: Vec::with_capacity(expected_size),
@@ -212,6 +263,9 @@ where
             zero_var: Variable(0),
             variables: HashMap::with_capacity(expected_size),
             perm: Permutation::new(),
+            namespace_stack: Vec::new(),
+            namespaces: Vec::new(),
+            named_public_inputs: Vec::new(),
             __: PhantomData,
         };
 
@@ -286,6 +340,7 @@ where
         self.q_logic.push(E::Fr::zero());
         self.q_fixed_group_add.push(E::Fr::zero());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::zero());
 
         if let Some(pi) = pi {
             assert!(self
@@ -324,6 +379,32 @@ where
         );
     }
 
+    /// Constrain a [`Variable`] to be equal to the Public Input named `name`,
+    /// whose value is taken from the witness.
+    ///
+    /// The name and type are stored in the
+    /// [`VerifierData`](crate::circuit::VerifierData) of the compiled circuit
+    /// so the proof can be checked with
+    /// [`verify_proof_with_named_inputs`](crate::circuit::verify_proof_with_named_inputs).
+    /// Returns an error, without adding any gate, if a public input with the
+    /// same name was already registered.
+    pub fn public_input(
+        &mut self,
+        name: &str,
+        a: Variable,
+    ) -> Result<(), Error> {
+        self.check_public_input_name(name)?;
+        let value = self.variables[&a];
+        let pos = self.n;
+        self.constrain_to_constant(a, E::Fr::zero(), Some(-value));
+        self.register_public_input(
+            name,
+            PublicInputType::FieldElement,
+            vec![pos],
+        );
+        Ok(())
+    }
+
     /// Add a constraint into the circuit description that states that two
     /// [`Variable`]s are equal.
     pub fn assert_equal(&mut self, a: Variable, b: Variable) {
@@ -356,26 +437,18 @@ where
         choice_a: Variable,
         choice_b: Variable,
     ) -> Variable {
-        // bit * choice_a
-        let bit_times_a =
-            self.mul(E::Fr::one(), bit, choice_a, E::Fr::zero(), None);
-
-        // 1 - bit
-        let one_min_bit = self.add(
-            (-E::Fr::one(), bit),
-            (E::Fr::zero(), self.zero_var),
-            E::Fr::one(),
-            None,
+        // a - b
+        let a_min_b = self.linear_combination(
+            &[(E::Fr::one(), choice_a), (-E::Fr::one(), choice_b)],
+            E::Fr::zero(),
         );
 
-        // (1 - bit) * b
-        let one_min_bit_choice_b =
-            self.mul(E::Fr::one(), one_min_bit, choice_b, E::Fr::zero(), None);
-
-        // [ (1 - bit) * b ] + [ bit * a ]
-        self.add(
-            (E::Fr::one(), one_min_bit_choice_b),
-            (E::Fr::one(), bit_times_a),
+        // bit * (a - b) + b
+        self.big_mul(
+            E::Fr::one(),
+            bit,
+            a_min_b,
+            Some((E::Fr::one(), choice_b)),
             E::Fr::zero(),
             None,
         )
@@ -451,6 +524,7 @@ where
         self.q_logic.push(E::Fr::zero());
         self.q_fixed_group_add.push(E::Fr::zero());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::zero());
         let var_six = self.add_input(E::Fr::from(6u64));
         let var_one = self.add_input(E::Fr::from(1u64));
         let var_seven = self.add_input(E::Fr::from(7u64));
@@ -480,6 +554,7 @@ where
         self.q_logic.push(E::Fr::zero());
         self.q_fixed_group_add.push(E::Fr::zero());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::zero());
         self.w_l.push(var_min_twenty);
         self.w_r.push(var_six);
         self.w_o.push(var_seven);
@@ -550,6 +625,8 @@ where
             let qfixed = self.q_fixed_group_add[i];
             #[cfg(all(feature = "trace-print", feature = "std"))]
             let qvar = self.q_variable_group_add[i];
+            #[cfg(all(feature = "trace-print", feature = "std"))]
+            let qdouble = self.q_variable_group_double[i];
             let pi = pi_vec[i];
 
             let a = w_l[i];
@@ -576,6 +653,7 @@ where
             - q_logic -> {:?}\n
             - q_fixed_group_add -> {:?}\n
             - q_variable_group_add -> {:?}\n
+            - q_variable_group_double -> {:?}\n
             # Witness polynomials:\n
             - w_l -> {:?}\n
             - w_r -> {:?}\n
@@ -593,6 +671,7 @@ where
                 qlogic,
                 qfixed,
                 qvar,
+                qdouble,
                 a,
                 b,
                 c,
@@ -667,15 +746,12 @@ where
 mod test {
     use super::*;
     use crate::batch_test;
-    use crate::constraint_system::helper::*;
     use crate::prelude::Prover;
     use crate::prelude::Verifier;
+    use crate::setup::PublicParameters;
+    use crate::testing::*;
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
-    use ark_poly::univariate::DensePolynomial;
-    use ark_poly_commit::kzg10::{self, Powers, UniversalParams, KZG10};
-    use ark_poly_commit::sonic_pc::SonicKZG10;
-    use ark_poly_commit::PolynomialCommitment;
     use rand_core::OsRng;
 
     /// Tests that a circuit initially has 3 gates.
@@ -718,8 +794,10 @@ mod test {
                 let choice_a = composer.add_input(E::Fr::from(10u64));
                 let choice_b = composer.add_input(E::Fr::from(20u64));
 
+                let size = composer.circuit_size();
                 let choice =
                     composer.conditional_select(bit_1, choice_a, choice_b);
+                assert_eq!(composer.circuit_size() - size, 2);
                 composer.assert_equal(choice, choice_a);
 
                 let choice =
@@ -737,13 +815,7 @@ mod test {
         E: PairingEngine,
         P: TEModelParameters<BaseField = E::Fr>,
     {
-        let u_params: UniversalParams<E> =
-            KZG10::<E, DensePolynomial<E::Fr>>::setup(
-                2 * 30,
-                false,
-                &mut OsRng,
-            )
-            .unwrap();
+        let pp = PublicParameters::<E>::setup(2 * 30, &mut OsRng).unwrap();
 
         // Create a prover struct
         let mut prover: Prover<E, P> = Prover::new(b"demo");
@@ -752,17 +824,7 @@ mod test {
         dummy_gadget(10, prover.mut_cs());
 
         // Commit Key
-        let (ck, _) = SonicKZG10::<E, DensePolynomial<E::Fr>>::trim(
-            &u_params,
-            2 * 20,
-            0,
-            None,
-        )
-        .unwrap();
-        let powers = Powers {
-            powers_of_g: ck.powers_of_g.into(),
-            powers_of_gamma_g: ck.powers_of_gamma_g.into(),
-        };
+        let powers = pp.commit_key(prover.circuit_size()).unwrap();
 
         // Preprocess circuit
         prover.preprocess(&powers).unwrap();
@@ -787,27 +849,7 @@ mod test {
         dummy_gadget(10, verifier.mut_cs());
 
         // Commit and Verifier Key
-        let (sonic_ck, sonic_vk) =
-            SonicKZG10::<E, DensePolynomial<E::Fr>>::trim(
-                &u_params,
-                2 * 20,
-                0,
-                None,
-            )
-            .unwrap();
-        let powers = Powers {
-            powers_of_g: sonic_ck.powers_of_g.into(),
-            powers_of_gamma_g: sonic_ck.powers_of_gamma_g.into(),
-        };
-
-        let vk = kzg10::VerifierKey {
-            g: sonic_vk.g,
-            gamma_g: sonic_vk.gamma_g,
-            h: sonic_vk.h,
-            beta_h: sonic_vk.beta_h,
-            prepared_h: sonic_vk.prepared_h,
-            prepared_beta_h: sonic_vk.prepared_beta_h,
-        };
+        let (powers, vk) = pp.trim(verifier.circuit_size()).unwrap();
 
         // Preprocess
         verifier.preprocess(&powers).unwrap();
@@ -78,6 +78,7 @@ where
 
         self.q_variable_group_add.push(E::Fr::one());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.extend(&zeros);
 
         self.perm.add_variables_to_map(x_1, y_1, x_2, y_2, self.n);
         self.n += 1;
@@ -93,12 +94,54 @@ where
 
         Point::<E, P>::new(x_3, y_3)
     }
+
+    /// Doubles a curve point using a curve doubling gate, which takes a single
+    /// row instead of the two used by [`Self::point_addition_gate`].
+    pub fn point_doubling_gate(&mut self, point: Point<E, P>) -> Point<E, P> {
+        // The gate checks, for a point `(x_1, y_1)` and its double `(x_3,
+        // y_3)`: x_3 * (a * x_1^2 + y_1^2) = 2 * x_1 * y_1
+        // y_3 * (2 - a * x_1^2 - y_1^2) = y_1^2 - a * x_1^2
+
+        let x_1 = point.x;
+        let y_1 = point.y;
+
+        // Compute the resulting point
+        let p1 =
+            GroupAffine::<P>::new(self.variables[&x_1], self.variables[&y_1]);
+        let p3 = p1 + p1;
+
+        let x_3 = self.add_input(p3.x);
+        let y_3 = self.add_input(p3.y);
+
+        self.w_l.push(x_1);
+        self.w_r.push(y_1);
+        self.w_o.push(x_3);
+        self.w_4.push(y_3);
+
+        self.q_l.push(E::Fr::zero());
+        self.q_r.push(E::Fr::zero());
+        self.q_c.push(E::Fr::zero());
+        self.q_o.push(E::Fr::zero());
+        self.q_m.push(E::Fr::zero());
+        self.q_4.push(E::Fr::zero());
+        self.q_arith.push(E::Fr::zero());
+        self.q_range.push(E::Fr::zero());
+        self.q_logic.push(E::Fr::zero());
+        self.q_fixed_group_add.push(E::Fr::zero());
+        self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::one());
+
+        self.perm.add_variables_to_map(x_1, y_1, x_3, y_3, self.n);
+        self.n += 1;
+
+        Point::<E, P>::new(x_3, y_3)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{batch_test, constraint_system::helper::*};
+    use crate::{batch_test, testing::*};
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
     use ark_ff::Field;
@@ -252,8 +295,63 @@ mod test {
         assert!(res.is_ok());
     }
 
+    fn test_curve_doubling<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+                let generator = GroupAffine::new(x, y);
+                let generator_2 = generator + generator;
+                let point = composer.add_affine(generator);
+
+                let doubled = composer.point_doubling_gate(point);
+                let added = composer.point_addition_gate(point, point);
+                composer.assert_equal_point(doubled, added);
+                composer.assert_equal_public_point(doubled, generator_2);
+
+                let doubled = composer.point_doubling_gate(doubled);
+                composer.assert_equal_public_point(
+                    doubled,
+                    generator_2 + generator_2,
+                );
+
+                let identity = Point::identity(composer);
+                let doubled = composer.point_doubling_gate(identity);
+                composer.assert_equal_point(doubled, identity);
+            },
+            64,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_curve_doubling_wrong_result<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+                let point = composer.add_affine(GroupAffine::new(x, y));
+                let doubled = composer.point_doubling_gate(point);
+
+                let wrong_y = composer.variables[&doubled.y] + E::Fr::one();
+                composer.variables.insert(doubled.y, wrong_y);
+            },
+            64,
+        );
+        assert!(res.is_err());
+    }
+
     batch_test!(
-        [test_curve_addition],
+        [
+            test_curve_addition,
+            test_curve_doubling,
+            test_curve_doubling_wrong_result
+        ],
         []
         => (
             Bls12_381,
@@ -262,7 +360,11 @@ mod test {
     );
 
     batch_test!(
-        [test_curve_addition],
+        [
+            test_curve_addition,
+            test_curve_doubling,
+            test_curve_doubling_wrong_result
+        ],
         [] => (
             Bls12_377,
             ark_ed_on_bls12_377::EdwardsParameters
@@ -9,9 +9,14 @@
 //! Elliptic Curve Gates
 
 pub mod curve_addition;
+pub mod pedersen;
 pub mod scalar_mul;
 
+mod validation;
+
+use crate::circuit::PublicInputType;
 use crate::constraint_system::{variable::Variable, StandardComposer};
+use crate::error::Error;
 use ark_ec::{
     twisted_edwards_extended::GroupAffine, PairingEngine, TEModelParameters,
 };
@@ -47,7 +52,9 @@ where
     ///
     /// This method should only be called when we have a guarantee in some
     /// [`StandardComposer`] that these two are valid coordinates for an
-    /// elliptic curve point in affine form.
+    /// elliptic curve point in affine form, or when they are later checked
+    /// with [`StandardComposer::assert_on_curve`] or
+    /// [`StandardComposer::assert_in_prime_subgroup`].
     pub fn new(x: Variable, y: Variable) -> Self {
         Self {
             x,
@@ -79,7 +86,8 @@ where
     P: TEModelParameters<BaseField = E::Fr>,
 {
     /// Converts an embeded curve point into a constraint system Point
-    /// without constraining the values
+    /// without constraining the values, see
+    /// [`StandardComposer::assert_in_prime_subgroup`] for witnessed points.
     pub fn add_affine(&mut self, affine: GroupAffine<P>) -> Point<E, P> {
         Point::new(self.add_input(affine.x), self.add_input(affine.y))
     }
@@ -125,6 +133,31 @@ where
         );
     }
 
+    /// Constrains a point in the circuit to be equal to the public point
+    /// named `name`, whose value is taken from the witness.
+    ///
+    /// Returns an error, without adding any gate, if a public input with the
+    /// same name was already registered.
+    pub fn public_point(
+        &mut self,
+        name: &str,
+        point: Point<E, P>,
+    ) -> Result<(), Error> {
+        self.check_public_input_name(name)?;
+        let pos = self.n;
+        let public_point = GroupAffine::new(
+            self.variables[&point.x],
+            self.variables[&point.y],
+        );
+        self.assert_equal_public_point(point, public_point);
+        self.register_public_input(
+            name,
+            PublicInputType::Point,
+            vec![pos, pos + 1],
+        );
+        Ok(())
+    }
+
     /// Asserts that a point in the circuit is equal to another point in the
     /// circuit.
     pub fn assert_equal_point(&mut self, lhs: Point<E, P>, rhs: Point<E, P>) {
@@ -213,7 +246,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{batch_test, constraint_system::helper::*};
+    use crate::{batch_test, testing::*};
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
 
@@ -0,0 +1,500 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Pedersen Commitments and Hashes over the Embedded Curve
+
+use crate::constraint_system::ecc::scalar_mul::FixedBaseTable;
+use crate::constraint_system::ecc::Point;
+use crate::constraint_system::{variable::Variable, StandardComposer};
+use alloc::vec::Vec;
+use ark_ec::models::twisted_edwards_extended::{GroupAffine, GroupProjective};
+use ark_ec::models::TEModelParameters;
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::{FpParameters, PrimeField};
+use core::iter;
+use merlin::Transcript;
+use num_traits::{One, Zero};
+
+/// Derives the `index`-th generator of `domain` by hashing to the curve, so
+/// that nobody knows its discrete logarithm with respect to any other
+/// generator.
+pub fn generator<P>(domain: &[u8], index: u64) -> GroupAffine<P>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+{
+    let mut transcript = Transcript::new(b"pedersen_generator");
+    transcript.append_message(b"domain", domain);
+    transcript.append_u64(b"index", index);
+    loop {
+        let mut bytes = [0u8; 64];
+        transcript.challenge_bytes(b"x", &mut bytes);
+        let x = P::BaseField::from_le_bytes_mod_order(&bytes);
+        if let Some(point) = GroupAffine::<P>::get_point_from_x(x, false) {
+            let point = point.mul_by_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+    }
+}
+
+/// Multi-generator Pedersen commitment scheme
+/// `sum_i value_i * G_i + blinding * H`.
+///
+/// The multiples of every generator are computed once, when the scheme is
+/// built, and reused by every commitment.
+#[derive(derivative::Derivative)]
+#[derivative(Clone, Debug)]
+pub struct PedersenCommitment<P>
+where
+    P: TEModelParameters,
+{
+    /// Value Generators `G_i`
+    value_generators: Vec<FixedBaseTable<P>>,
+
+    /// Blinding Generator `H`
+    blinding_generator: FixedBaseTable<P>,
+}
+
+impl<P> PedersenCommitment<P>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+{
+    /// Builds the scheme committing to `num_values` values, with the blinding
+    /// generator and the value generators derived from `domain` at the
+    /// indices `0` and `1..=num_values`.
+    pub fn new(domain: &[u8], num_values: usize) -> Self {
+        Self {
+            value_generators: (1..=num_values as u64)
+                .map(|index| FixedBaseTable::new(generator(domain, index)))
+                .collect(),
+            blinding_generator: FixedBaseTable::new(generator(domain, 0)),
+        }
+    }
+
+    /// Returns the value generators `G_i`.
+    pub fn value_generators(&self) -> Vec<GroupAffine<P>> {
+        self.value_generators
+            .iter()
+            .map(FixedBaseTable::base_point)
+            .collect()
+    }
+
+    /// Returns the blinding generator `H`.
+    pub fn blinding_generator(&self) -> GroupAffine<P> {
+        self.blinding_generator.base_point()
+    }
+
+    /// Commits to `values` with the given `blinding` factor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values does not match the scheme.
+    pub fn commit(
+        &self,
+        values: &[P::ScalarField],
+        blinding: P::ScalarField,
+    ) -> GroupAffine<P> {
+        assert_eq!(
+            values.len(),
+            self.value_generators.len(),
+            "wrong number of committed values"
+        );
+        values
+            .iter()
+            .zip(self.value_generators())
+            .chain(iter::once((&blinding, self.blinding_generator())))
+            .map(|(value, generator)| generator.mul(value.into_repr()))
+            .sum::<GroupProjective<P>>()
+            .into_affine()
+    }
+}
+
+/// Windowed Pedersen hash in the style of Zcash Sapling.
+///
+/// The message is split into chunks of three bits `(s0, s1, s2)` encoding the
+/// digits `(1 - 2 * s2) * (1 + s0 + 2 * s1)`, and the `j`-th chunk of every
+/// segment is multiplied by `2^(4j)` times the generator of that segment.
+/// Segments are short enough for the encoding to be injective. The
+/// multiples of every chunk are computed once, when the hash is built.
+#[derive(derivative::Derivative)]
+#[derivative(Clone, Debug)]
+pub struct PedersenHash<P>
+where
+    P: TEModelParameters,
+{
+    /// Multiples `{1, 2, 3, 4} * 2^(4j) * G_i` of every chunk
+    chunk_multiples: Vec<[GroupAffine<P>; 4]>,
+}
+
+impl<P> PedersenHash<P>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+{
+    /// Builds the hash of messages of at most `max_bits` bits, with the
+    /// generator of each segment derived from `domain`.
+    pub fn new(domain: &[u8], max_bits: usize) -> Self {
+        let chunks_per_segment = Self::chunks_per_segment();
+        let num_chunks = max_bits.div_ceil(3);
+        let mut chunk_multiples = Vec::with_capacity(num_chunks);
+        let mut base = GroupProjective::<P>::zero();
+        for chunk in 0..num_chunks {
+            if chunk % chunks_per_segment == 0 {
+                base =
+                    generator::<P>(domain, (chunk / chunks_per_segment) as u64)
+                        .into_projective();
+            }
+            let double = base.double();
+            let multiples = [base, double, double + base, double.double()];
+            let multiples =
+                GroupProjective::batch_normalization_into_affine(&multiples);
+            chunk_multiples.push([
+                multiples[0],
+                multiples[1],
+                multiples[2],
+                multiples[3],
+            ]);
+            for _ in 0..4 {
+                base.double_in_place();
+            }
+        }
+        Self { chunk_multiples }
+    }
+
+    /// Returns the number of three-bit chunks `c` per segment, chosen so that
+    /// the largest segment value `4 * (2^(4c) - 1) / 15` stays below half
+    /// the order of the prime subgroup.
+    pub fn chunks_per_segment() -> usize {
+        (<P::ScalarField as PrimeField>::Params::MODULUS_BITS as usize - 2) / 4
+    }
+
+    /// Returns the maximum message length in bits.
+    pub fn max_bits(&self) -> usize {
+        3 * self.chunk_multiples.len()
+    }
+
+    /// Hashes `message`, padded with zeros to a multiple of three bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the message is longer than [`PedersenHash::max_bits`].
+    pub fn hash(&self, message: &[bool]) -> GroupAffine<P> {
+        assert!(
+            message.len() <= self.max_bits(),
+            "message longer than the hash capacity"
+        );
+        message
+            .chunks(3)
+            .zip(self.chunk_multiples.iter())
+            .map(|(chunk, multiples)| {
+                let bit = |i| chunk.get(i).copied().unwrap_or(false) as usize;
+                let point = multiples[bit(0) + 2 * bit(1)];
+                if bit(2) == 1 {
+                    -point
+                } else {
+                    point
+                }
+            })
+            .fold(GroupAffine::zero(), |acc, point| acc + point)
+    }
+}
+
+impl<E, P> StandardComposer<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Adds the Pedersen commitment to `values` with the given `blinding`
+    /// factor to the circuit description.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values does not match the scheme.
+    pub fn pedersen_commitment(
+        &mut self,
+        scheme: &PedersenCommitment<P>,
+        values: &[Variable],
+        blinding: Variable,
+    ) -> Point<E, P> {
+        assert_eq!(
+            values.len(),
+            scheme.value_generators.len(),
+            "wrong number of committed values"
+        );
+        let terms: Vec<_> = values
+            .iter()
+            .zip(scheme.value_generators.iter())
+            .chain(iter::once((&blinding, &scheme.blinding_generator)))
+            .map(|(value, table)| (*value, table))
+            .collect();
+        self.fixed_base_table_multi_scalar_mul(&terms)
+    }
+
+    /// Adds the Pedersen hash of the bits of `message` to the circuit
+    /// description, constraining every bit to be boolean.
+    ///
+    /// Each chunk of three bits takes eight rows: three to constrain its bits,
+    /// three to select its multiple and two more to be added to the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the message is longer than [`PedersenHash::max_bits`].
+    pub fn pedersen_hash(
+        &mut self,
+        hash: &PedersenHash<P>,
+        message: &[Variable],
+    ) -> Point<E, P> {
+        assert!(
+            message.len() <= hash.max_bits(),
+            "message longer than the hash capacity"
+        );
+        let mut result = None;
+        for (chunk, [m1, m2, m3, m4]) in
+            message.chunks(3).zip(hash.chunk_multiples.iter())
+        {
+            for bit in chunk {
+                self.boolean_gate(*bit);
+            }
+            let bit = |i| chunk.get(i).copied();
+            let s0 = bit(0).unwrap_or(self.zero_var);
+            let s1 = bit(1).unwrap_or(self.zero_var);
+            let s2 = bit(2).unwrap_or(self.zero_var);
+
+            // Interpolate the multiple `m[s0 + 2 * s1]` from its two bits
+            let x = self.big_arith(
+                m4.x - m3.x - m2.x + m1.x,
+                s0,
+                s1,
+                m2.x - m1.x,
+                m3.x - m1.x,
+                None,
+                m1.x,
+                None,
+            );
+            let y = self.big_arith(
+                m4.y - m3.y - m2.y + m1.y,
+                s0,
+                s1,
+                m2.y - m1.y,
+                m3.y - m1.y,
+                None,
+                m1.y,
+                None,
+            );
+            // Negate the multiple when `s2` is set
+            let x = self.big_arith(
+                -E::Fr::from(2u64),
+                s2,
+                x,
+                E::Fr::zero(),
+                E::Fr::one(),
+                None,
+                E::Fr::zero(),
+                None,
+            );
+            let digit = Point::new(x, y);
+
+            result = Some(match result {
+                None => digit,
+                Some(result) => self.point_addition_gate(result, digit),
+            });
+        }
+        result.unwrap_or_else(|| Point::identity(self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{batch_test, testing::*, util};
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+
+    fn test_generators<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let point = generator::<P>(b"domain", 0);
+        assert!(point.is_on_curve());
+        assert!(point.is_in_correct_subgroup_assuming_on_curve());
+        assert!(!point.is_zero());
+        assert_eq!(point, generator::<P>(b"domain", 0));
+        assert_ne!(point, generator::<P>(b"domain", 1));
+        assert_ne!(point, generator::<P>(b"other domain", 0));
+    }
+
+    fn test_pedersen_commitment<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let scheme = PedersenCommitment::<P>::new(b"commitment", 2);
+        let openings = [
+            ([E::Fr::from(25u64), E::Fr::from(30u64)], E::Fr::from(7u64)),
+            (
+                [E::Fr::zero(), E::Fr::from(u64::MAX)],
+                E::Fr::from(u64::MAX),
+            ),
+        ];
+
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                for (values, blinding) in openings {
+                    let expected_point = scheme.commit(
+                        &values.map(util::to_embedded_curve_scalar::<E, P>),
+                        util::to_embedded_curve_scalar::<E, P>(blinding),
+                    );
+                    let values = values.map(|value| composer.add_input(value));
+                    let blinding = composer.add_input(blinding);
+                    let commitment = composer
+                        .pedersen_commitment(&scheme, &values, blinding);
+                    composer
+                        .assert_equal_public_point(commitment, expected_point);
+                }
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_pedersen_commitment_wrong_opening<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let scheme = PedersenCommitment::<P>::new(b"commitment", 1);
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let expected_point = scheme.commit(
+                    &[util::to_embedded_curve_scalar::<E, P>(E::Fr::from(
+                        25u64,
+                    ))],
+                    util::to_embedded_curve_scalar::<E, P>(E::Fr::from(8u64)),
+                );
+                let value = composer.add_input(E::Fr::from(25u64));
+                let blinding = composer.add_input(E::Fr::from(7u64));
+                let commitment =
+                    composer.pedersen_commitment(&scheme, &[value], blinding);
+                composer.assert_equal_public_point(commitment, expected_point);
+            },
+            1024,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_pedersen_hash_encoding<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let hash = PedersenHash::<P>::new(b"hash", 6);
+        let base = generator::<P>(b"hash", 0);
+        let two = base + base;
+        let three = two + base;
+
+        assert_eq!(hash.max_bits(), 6);
+        assert_eq!(hash.hash(&[]), GroupAffine::zero());
+        assert_eq!(hash.hash(&[true]), two);
+        assert_eq!(hash.hash(&[false, true, true]), -three);
+        // The second chunk is shifted by four bits
+        let expected: GroupAffine<P> = base.mul(16u64).into_affine() + base;
+        assert_eq!(hash.hash(&[false, false, false, false]), expected);
+
+        // The first chunk isn't added to anything
+        let mut composer = StandardComposer::<E, P>::new();
+        let n = composer.circuit_size();
+        composer.pedersen_hash(&hash, &[composer.zero_var(); 6]);
+        assert_eq!(composer.circuit_size(), n + 8 + 6);
+    }
+
+    fn test_pedersen_hash<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        // Long enough to span two segments, and not a multiple of three bits
+        let num_bits = 3 * PedersenHash::<P>::chunks_per_segment() + 14;
+        let hash = PedersenHash::<P>::new(b"hash", num_bits);
+        let message: Vec<bool> =
+            (0..num_bits).map(|i| (i * i + i / 3) % 5 < 2).collect();
+        let expected_point = hash.hash(&message);
+
+        let mut flipped = message.clone();
+        flipped[num_bits - 1] ^= true;
+        assert_ne!(hash.hash(&flipped), expected_point);
+
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let message: Vec<_> = message
+                    .iter()
+                    .map(|bit| composer.add_input(E::Fr::from(*bit as u64)))
+                    .collect();
+                let point = composer.pedersen_hash(&hash, &message);
+                composer.assert_equal_public_point(point, expected_point);
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_pedersen_hash_non_boolean<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let hash = PedersenHash::<P>::new(b"hash", 3);
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let message = [
+                    composer.add_input(E::Fr::from(2u64)),
+                    composer.zero_var(),
+                    composer.zero_var(),
+                ];
+                composer.pedersen_hash(&hash, &message);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_generators,
+            test_pedersen_commitment,
+            test_pedersen_commitment_wrong_opening,
+            test_pedersen_hash_encoding,
+            test_pedersen_hash,
+            test_pedersen_hash_non_boolean
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_generators,
+            test_pedersen_commitment,
+            test_pedersen_commitment_wrong_opening,
+            test_pedersen_hash_encoding,
+            test_pedersen_hash,
+            test_pedersen_hash_non_boolean
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
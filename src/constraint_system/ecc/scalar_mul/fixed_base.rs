@@ -10,6 +10,7 @@
 
 use crate::constraint_system::ecc::Point;
 use crate::constraint_system::{variable::Variable, StandardComposer};
+use alloc::vec::Vec;
 use ark_ec::models::twisted_edwards_extended::{GroupAffine, GroupProjective};
 use ark_ec::models::TEModelParameters;
 use ark_ec::{PairingEngine, ProjectiveCurve};
@@ -35,6 +36,43 @@ where
     ProjectiveCurve::batch_normalization_into_affine(&multiples)
 }
 
+/// Fixed base point together with its precomputed multiples `2^i * base`, so
+/// that they are computed once and reused across scalar multiplications.
+#[derive(derivative::Derivative)]
+#[derivative(Clone, Debug)]
+pub struct FixedBaseTable<P>
+where
+    P: TEModelParameters,
+{
+    /// Base Point
+    base_point: GroupAffine<P>,
+
+    /// Multiples `2^i * base_point` from the most significant bit down
+    point_multiples: Vec<GroupAffine<P>>,
+}
+
+impl<P> FixedBaseTable<P>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+{
+    /// Precomputes the multiples of `base_point`.
+    pub fn new(base_point: GroupAffine<P>) -> Self {
+        let mut point_multiples =
+            compute_wnaf_point_multiples(base_point.into());
+        point_multiples.reverse();
+        Self {
+            base_point,
+            point_multiples,
+        }
+    }
+
+    /// Returns the base point of the table.
+    pub fn base_point(&self) -> GroupAffine<P> {
+        self.base_point
+    }
+}
+
 impl<E, P> StandardComposer<E, P>
 where
     E: PairingEngine,
@@ -52,15 +90,90 @@ where
         &mut self,
         scalar: Variable,
         base_point: GroupAffine<P>,
+    ) -> Point<E, P> {
+        // compute 2^iG
+        let table = FixedBaseTable::new(base_point);
+
+        let acc_x = self.add_input(E::Fr::zero());
+        let acc_y = self.add_input(E::Fr::one());
+        let accumulated_bit = self.add_input(E::Fr::zero());
+
+        // We constrain the point accumulator to start from the Identity
+        // point and the Scalar accumulator to start from zero
+        self.constrain_to_constant(acc_x, E::Fr::zero(), None);
+        self.constrain_to_constant(acc_y, E::Fr::one(), None);
+        self.constrain_to_constant(accumulated_bit, E::Fr::zero(), None);
+
+        self.fixed_base_rounds(
+            Point::new(acc_x, acc_y),
+            accumulated_bit,
+            scalar,
+            &table.point_multiples,
+        )
+    }
+
+    /// Adds the multi-scalar multiplication `sum_i scalar_i * base_i` of
+    /// fixed bases to the circuit description.
+    ///
+    /// Fixed-base rounds never double, so instead of sharing doublings the
+    /// rounds of every term are chained through a single point accumulator:
+    /// no [`StandardComposer::point_addition_gate`] is needed to combine the
+    /// terms and each extra term costs one row on top of its rounds.
+    ///
+    /// Returns the identity for an empty slice.
+    pub fn fixed_base_multi_scalar_mul(
+        &mut self,
+        terms: &[(Variable, GroupAffine<P>)],
+    ) -> Point<E, P> {
+        let tables: Vec<_> = terms
+            .iter()
+            .map(|(_, base_point)| FixedBaseTable::new(*base_point))
+            .collect();
+        let terms: Vec<_> = terms
+            .iter()
+            .zip(tables.iter())
+            .map(|((scalar, _), table)| (*scalar, table))
+            .collect();
+        self.fixed_base_table_multi_scalar_mul(&terms)
+    }
+
+    /// Same as [`StandardComposer::fixed_base_multi_scalar_mul`] but with the
+    /// multiples of every base taken from a precomputed [`FixedBaseTable`].
+    pub fn fixed_base_table_multi_scalar_mul(
+        &mut self,
+        terms: &[(Variable, &FixedBaseTable<P>)],
+    ) -> Point<E, P> {
+        let mut acc = Point::identity(self);
+        for (scalar, table) in terms {
+            acc = self.fixed_base_rounds(
+                acc,
+                self.zero_var,
+                *scalar,
+                &table.point_multiples,
+            );
+        }
+        acc
+    }
+
+    /// Adds `scalar * base` to the point accumulator `acc`, where
+    /// `point_multiples` holds the multiples `2^i * base` from the most
+    /// significant bit down, and `accumulated_bit` is a variable constrained
+    /// to zero.
+    fn fixed_base_rounds(
+        &mut self,
+        acc: Point<E, P>,
+        accumulated_bit: Variable,
+        scalar: Variable,
+        point_multiples: &[GroupAffine<P>],
     ) -> Point<E, P> {
         let num_bits =
             <P::BaseField as PrimeField>::Params::MODULUS_BITS as usize;
-        // compute 2^iG
-        let mut point_multiples =
-            compute_wnaf_point_multiples(base_point.into());
-        point_multiples.reverse();
 
         let scalar_value = self.variables.get(&scalar).unwrap();
+        let acc_value = GroupAffine::<P>::new(
+            self.variables[&acc.x],
+            self.variables[&acc.y],
+        );
 
         // Convert scalar to wnaf_2(k)
         let wnaf_entries =
@@ -72,14 +185,14 @@ where
         let mut scalar_acc = Vec::with_capacity(num_bits);
         scalar_acc.push(E::Fr::zero());
         let mut point_acc = Vec::with_capacity(num_bits);
-        point_acc.push(GroupAffine::<P>::zero());
+        point_acc.push(acc_value);
 
         // Auxillary point to help with checks on the backend
         let mut xy_alphas = Vec::with_capacity(num_bits);
 
         let n_trailing_zeros = num_bits - wnaf_entries.len();
         scalar_acc.extend(vec![E::Fr::zero(); n_trailing_zeros]);
-        point_acc.extend(vec![GroupAffine::<P>::zero(); n_trailing_zeros]);
+        point_acc.extend(vec![acc_value; n_trailing_zeros]);
         xy_alphas.extend(vec![E::Fr::zero(); n_trailing_zeros]);
 
         // Load values into accumulators based on wnaf entries
@@ -107,22 +220,16 @@ where
         }
 
         for i in 0..num_bits {
-            let acc_x = self.add_input(point_acc[i].x);
-            let acc_y = self.add_input(point_acc[i].y);
-
-            let accumulated_bit = self.add_input(scalar_acc[i]);
-
-            // We constrain the point accumulator to start from the Identity
-            // point and the Scalar accumulator to start from zero
-            if i == 0 {
-                self.constrain_to_constant(acc_x, E::Fr::zero(), None);
-                self.constrain_to_constant(acc_y, E::Fr::one(), None);
-                self.constrain_to_constant(
-                    accumulated_bit,
-                    E::Fr::zero(),
-                    None,
-                );
-            }
+            // The first round starts from the given accumulators
+            let (acc_x, acc_y, accumulated_bit) = if i == 0 {
+                (acc.x, acc.y, accumulated_bit)
+            } else {
+                (
+                    self.add_input(point_acc[i].x),
+                    self.add_input(point_acc[i].y),
+                    self.add_input(scalar_acc[i]),
+                )
+            };
 
             let x_beta = point_multiples[i].x;
             let y_beta = point_multiples[i].y;
@@ -175,11 +282,11 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{batch_test, constraint_system::helper::*, util};
+    use crate::{batch_test, testing::*, util};
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
-    use ark_ec::{group::Group, AffineCurve};
-    use ark_ff::PrimeField;
+    use ark_ec::{group::Group, msm::VariableBaseMSM, AffineCurve};
+    use ark_ff::{Field, PrimeField};
 
     fn test_ecc_constraint<E, P>()
     where
@@ -452,6 +559,88 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    fn test_fixed_base_multi_scalar_mul<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = GroupAffine::<P>::new(x, y);
+        let bases = [generator, generator.double(), -generator];
+        let scalars = [
+            E::Fr::from(u64::MAX).pow([3]),
+            E::Fr::zero(),
+            E::Fr::from(0xdead_beef_u64).pow([5]),
+        ];
+        let expected_point: GroupAffine<P> = VariableBaseMSM::multi_scalar_mul(
+            &bases,
+            &scalars
+                .iter()
+                .map(|scalar| {
+                    util::to_embedded_curve_scalar::<E, P>(*scalar).into_repr()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .into();
+
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let terms: Vec<_> = scalars
+                    .iter()
+                    .zip(bases.iter())
+                    .map(|(scalar, base)| (composer.add_input(*scalar), *base))
+                    .collect();
+                let point = composer.fixed_base_multi_scalar_mul(&terms);
+                composer.assert_equal_public_point(point, expected_point);
+
+                let empty = composer.fixed_base_multi_scalar_mul(&[]);
+                composer.assert_equal_public_point(empty, GroupAffine::zero());
+            },
+            1024,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_fixed_base_multi_scalar_mul_rows<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = GroupAffine::<P>::new(x, y);
+        let terms = [
+            (E::Fr::from(25u64), generator),
+            (E::Fr::from(30u64), generator.double()),
+        ];
+
+        let mut separate = StandardComposer::<E, P>::new();
+        let mut chained = StandardComposer::<E, P>::new();
+        let separate_point = {
+            let a = separate.add_input(terms[0].0);
+            let b = separate.add_input(terms[1].0);
+            let a = separate.fixed_base_scalar_mul(a, terms[0].1);
+            let b = separate.fixed_base_scalar_mul(b, terms[1].1);
+            separate.point_addition_gate(a, b)
+        };
+        let chained_point = {
+            let terms: Vec<_> = terms
+                .iter()
+                .map(|(scalar, base)| (chained.add_input(*scalar), *base))
+                .collect();
+            chained.fixed_base_multi_scalar_mul(&terms)
+        };
+
+        assert_eq!(
+            separate.variables[&separate_point.x],
+            chained.variables[&chained_point.x]
+        );
+        assert_eq!(
+            separate.variables[&separate_point.y],
+            chained.variables[&chained_point.y]
+        );
+        assert!(chained.circuit_size() < separate.circuit_size());
+    }
+
     // Bls12-381 tests
     batch_test!(
         [
@@ -460,7 +649,9 @@ mod tests {
             test_ecc_constraint_should_fail,
             test_point_addition,
             test_pedersen_hash,
-            test_pedersen_balance
+            test_pedersen_balance,
+            test_fixed_base_multi_scalar_mul,
+            test_fixed_base_multi_scalar_mul_rows
         ],
         [] => (
             Bls12_381,
@@ -476,7 +667,9 @@ mod tests {
             test_ecc_constraint_should_fail,
             test_point_addition,
             test_pedersen_hash,
-            test_pedersen_balance
+            test_pedersen_balance,
+            test_fixed_base_multi_scalar_mul,
+            test_fixed_base_multi_scalar_mul_rows
         ],
         [] => (
             Bls12_377,
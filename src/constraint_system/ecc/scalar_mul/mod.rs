@@ -10,3 +10,5 @@
 
 mod fixed_base;
 mod variable_base;
+
+pub use fixed_base::FixedBaseTable;
@@ -10,6 +10,7 @@
 
 use crate::constraint_system::ecc::Point;
 use crate::constraint_system::{variable::Variable, StandardComposer};
+use alloc::vec::Vec;
 use ark_ec::models::TEModelParameters;
 use ark_ec::PairingEngine;
 use ark_ff::{BigInteger, Field, FpParameters, PrimeField};
@@ -48,7 +49,7 @@ where
         let mut result = identity;
 
         for bit in scalar_bits_var.into_iter().rev() {
-            result = self.point_addition_gate(result, result);
+            result = self.point_doubling_gate(result);
             let point_to_add = self.conditional_select_identity(bit, point);
             result = self.point_addition_gate(result, point_to_add);
         }
@@ -56,6 +57,249 @@ where
         result
     }
 
+    /// Adds a variable-base scalar multiplication to the circuit description,
+    /// processing the scalar in signed windows of two bits.
+    ///
+    /// The odd multiples `P` and `3P` are precomputed in the circuit and each
+    /// window selects one of `{-3P, -P, P, 3P}` with four arithmetic gates,
+    /// followed by two [`StandardComposer::point_doubling_gate`]s and one
+    /// [`StandardComposer::point_addition_gate`]. This takes eight rows per
+    /// pair of scalar bits against the five rows per bit of
+    /// [`StandardComposer::variable_base_scalar_mul`].
+    ///
+    /// # Note
+    ///
+    /// There is no endomorphism-accelerated (GLV) variant: none of the
+    /// embedded curves supported by the crate has an efficiently computable
+    /// endomorphism.
+    pub fn windowed_variable_base_scalar_mul(
+        &mut self,
+        curve_var: Variable,
+        point: Point<E, P>,
+    ) -> Point<E, P> {
+        self.multi_scalar_mul(&[(curve_var, point)])
+    }
+
+    /// Adds the multi-scalar multiplication `sum_i scalar_i * point_i` to the
+    /// circuit description.
+    ///
+    /// Every term is processed in the signed windows of
+    /// [`StandardComposer::windowed_variable_base_scalar_mul`], but the two
+    /// doublings of each window are shared by all the terms (Straus' trick),
+    /// so every extra term costs six rows per pair of scalar bits.
+    ///
+    /// Returns the identity for an empty slice.
+    pub fn multi_scalar_mul(
+        &mut self,
+        terms: &[(Variable, Point<E, P>)],
+    ) -> Point<E, P> {
+        if terms.is_empty() {
+            return Point::identity(self);
+        }
+
+        let terms: Vec<_> = terms
+            .iter()
+            .map(|(curve_var, point)| {
+                let raw_scalar = *self
+                    .variables
+                    .get(curve_var)
+                    .expect("Variable in existance without referenced scalar");
+                let (parity, digit_bits) =
+                    self.signed_digit_decomposition(*curve_var, raw_scalar);
+                let table = self.signed_digit_table(*point);
+                (parity, digit_bits, table)
+            })
+            .collect();
+
+        let num_windows = terms[0].1.len() / 2;
+        let mut result: Option<Point<E, P>> = None;
+        for window in (0..num_windows).rev() {
+            if let Some(acc) = result {
+                let acc = self.point_doubling_gate(acc);
+                result = Some(self.point_doubling_gate(acc));
+            }
+            for (_, digit_bits, table) in &terms {
+                let digit = self.signed_digit_lookup(
+                    table,
+                    digit_bits[2 * window],
+                    digit_bits[2 * window + 1],
+                );
+                result = Some(match result {
+                    None => digit,
+                    Some(acc) => self.point_addition_gate(acc, digit),
+                });
+            }
+        }
+        let mut result = result.expect("the scalars have at least one window");
+
+        // The digits encode `scalar + 1 - parity`, so subtract `P` when the
+        // scalar is even.
+        for (parity, _, (point, _, _)) in &terms {
+            let x = self.big_arith(
+                E::Fr::one(),
+                *parity,
+                point.x,
+                E::Fr::zero(),
+                -E::Fr::one(),
+                None,
+                E::Fr::zero(),
+                None,
+            );
+            let y = self.big_arith(
+                -E::Fr::one(),
+                *parity,
+                point.y,
+                E::Fr::one(),
+                E::Fr::one(),
+                None,
+                E::Fr::zero(),
+                None,
+            );
+            result = self.point_addition_gate(result, Point::new(x, y));
+        }
+        result
+    }
+
+    /// Precomputes `3P` and returns `P` together with the coordinate
+    /// differences `3P - P` used to select between the two.
+    fn signed_digit_table(
+        &mut self,
+        point: Point<E, P>,
+    ) -> (Point<E, P>, Variable, Variable) {
+        let point_2 = self.point_doubling_gate(point);
+        let point_3 = self.point_addition_gate(point_2, point);
+        let dx = self.add(
+            (E::Fr::one(), point_3.x),
+            (-E::Fr::one(), point.x),
+            E::Fr::zero(),
+            None,
+        );
+        let dy = self.add(
+            (E::Fr::one(), point_3.y),
+            (-E::Fr::one(), point.y),
+            E::Fr::zero(),
+            None,
+        );
+        (point, dx, dy)
+    }
+
+    /// Selects the multiple `(2 * (low + 2 * high) - 3) * P` out of the
+    /// table built by `signed_digit_table`.
+    fn signed_digit_lookup(
+        &mut self,
+        (point, dx, dy): &(Point<E, P>, Variable, Variable),
+        low: Variable,
+        high: Variable,
+    ) -> Point<E, P> {
+        // The digit `2 * (low + 2 * high) - 3` is `3` or `-3` when both
+        // bits are equal, and negative when `high` is zero.
+        let is_three = self.big_arith(
+            E::Fr::from(2u64),
+            low,
+            high,
+            -E::Fr::one(),
+            -E::Fr::one(),
+            None,
+            E::Fr::one(),
+            None,
+        );
+        let x = self.big_mul(
+            E::Fr::one(),
+            is_three,
+            *dx,
+            Some((E::Fr::one(), point.x)),
+            E::Fr::zero(),
+            None,
+        );
+        let y = self.big_mul(
+            E::Fr::one(),
+            is_three,
+            *dy,
+            Some((E::Fr::one(), point.y)),
+            E::Fr::zero(),
+            None,
+        );
+        let x = self.big_arith(
+            E::Fr::from(2u64),
+            high,
+            x,
+            E::Fr::zero(),
+            -E::Fr::one(),
+            None,
+            E::Fr::zero(),
+            None,
+        );
+        Point::new(x, y)
+    }
+
+    /// Decomposes `witness_scalar` into its parity bit and the bits `u_j` of
+    /// `u = (scalar + 1 - parity + 4^m - 1) / 2`, so that the windows
+    /// `u_{2i} + 2 * u_{2i + 1}` give the signed odd digits
+    /// `2 * (u_{2i} + 2 * u_{2i + 1}) - 3` of `scalar + 1 - parity` in base
+    /// `4`.
+    fn signed_digit_decomposition(
+        &mut self,
+        witness_var: Variable,
+        witness_scalar: E::Fr,
+    ) -> (Variable, Vec<Variable>) {
+        let num_bits = <P::BaseField as PrimeField>::Params::MODULUS_BITS;
+        let num_windows = (num_bits as usize).div_ceil(2);
+        let scalar_bits = witness_scalar.into_repr().to_bits_le();
+
+        let parity = self.add_input(E::Fr::from(scalar_bits[0] as u64));
+
+        // `u` is the scalar shifted right by one with its top bit set.
+        let digit_bits: Vec<Variable> = (0..2 * num_windows)
+            .map(|j| {
+                let bit = j + 1 == 2 * num_windows || scalar_bits[j + 1];
+                self.add_input(E::Fr::from(bit as u64))
+            })
+            .collect();
+        self.constrain_signed_digits(witness_var, parity, &digit_bits);
+
+        (parity, digit_bits)
+    }
+
+    /// Constrains `parity` and `digit_bits` to be the decomposition of
+    /// [`StandardComposer::signed_digit_decomposition`] of `witness_var`.
+    ///
+    /// The top bit of `u` is fixed to one, so `2 * u + parity - 4^m` is the
+    /// integer whose bits are `parity` followed by the lower bits of `u`.
+    /// Those bits are then compared against the modulus, as otherwise
+    /// `scalar + p` (or `scalar + 2p`) would also be accepted.
+    fn constrain_signed_digits(
+        &mut self,
+        witness_var: Variable,
+        parity: Variable,
+        digit_bits: &[Variable],
+    ) {
+        self.boolean_gate(parity);
+        let (top_bit, low_bits) = digit_bits
+            .split_last()
+            .expect("the scalar has at least one window");
+        for bit in low_bits {
+            self.boolean_gate(*bit);
+        }
+        self.constrain_to_constant(*top_bit, E::Fr::one(), None);
+
+        let value_bits: Vec<_> = core::iter::once(parity)
+            .chain(low_bits.iter().copied())
+            .collect();
+        self.assert_below_modulus(&value_bits);
+
+        // Ensure that `2 * u + parity - 4^m` is the scalar
+        let two = E::Fr::from(2u64);
+        let mut terms: Vec<_> = digit_bits
+            .iter()
+            .enumerate()
+            .map(|(power, bit)| (two.pow([power as u64 + 1]), *bit))
+            .collect();
+        terms.push((E::Fr::one(), parity));
+        let accumulator_var = self
+            .linear_combination(&terms, -two.pow([digit_bits.len() as u64]));
+        self.assert_equal(accumulator_var, witness_var);
+    }
+
     fn scalar_decomposition(
         &mut self,
         witness_var: Variable,
@@ -76,23 +320,12 @@ where
             .to_vec();
 
         // Now ensure that the bits correctly accumulate to the witness given
-        let mut accumulator_var = self.zero_var;
-        let mut accumulator_scalar = E::Fr::zero();
-
+        let mut terms = Vec::with_capacity(scalar_bits_var.len());
         for (power, bit) in scalar_bits_var.iter().enumerate() {
             self.boolean_gate(*bit);
-
-            let two_pow = E::Fr::from(2u64).pow([power as u64, 0, 0, 0]);
-
-            let q_l_a = (two_pow, *bit);
-            let q_r_b = (E::Fr::one(), accumulator_var);
-            let q_c = E::Fr::zero();
-
-            accumulator_var = self.add(q_l_a, q_r_b, q_c, None);
-
-            accumulator_scalar +=
-                two_pow * E::Fr::from(scalar_bits_iter[power] as u64);
+            terms.push((E::Fr::from(2u64).pow([power as u64, 0, 0, 0]), *bit));
         }
+        let accumulator_var = self.linear_combination(&terms, E::Fr::zero());
         self.assert_equal(accumulator_var, witness_var);
 
         scalar_bits_var
@@ -102,10 +335,14 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{batch_test, constraint_system::helper::*, util};
+    use crate::{batch_test, testing::*, util};
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
-    use ark_ec::{twisted_edwards_extended::GroupAffine, AffineCurve};
+    use ark_ec::{
+        msm::VariableBaseMSM, twisted_edwards_extended::GroupAffine,
+        AffineCurve,
+    };
+    use ark_ff::Field;
 
     fn test_var_base_scalar_mul<E, P>()
     where
@@ -145,9 +382,265 @@ mod test {
         assert!(res.is_ok());
     }
 
+    fn test_windowed_var_base_scalar_mul<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+                let generator = GroupAffine::new(x, y);
+                let point = composer.add_affine(generator);
+
+                for scalar in [
+                    E::Fr::zero(),
+                    E::Fr::one(),
+                    E::Fr::from(2u64),
+                    E::Fr::from(u64::MAX).pow([3]),
+                    E::Fr::from(0xdead_beef_u64).pow([5]),
+                ] {
+                    let expected_point: GroupAffine<P> = AffineCurve::mul(
+                        &generator,
+                        util::to_embedded_curve_scalar::<E, P>(scalar),
+                    )
+                    .into();
+
+                    let secret_scalar = composer.add_input(scalar);
+                    let point_scalar = composer
+                        .windowed_variable_base_scalar_mul(
+                            secret_scalar,
+                            point,
+                        );
+                    composer.assert_equal_public_point(
+                        point_scalar,
+                        expected_point,
+                    );
+                }
+            },
+            8192,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_windowed_var_base_scalar_mul_rows<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let scalar = E::Fr::from(0xdead_beef_u64).pow([5]);
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = GroupAffine::new(x, y);
+
+        let mut plain = StandardComposer::<E, P>::new();
+        let mut windowed = StandardComposer::<E, P>::new();
+        let plain_point = {
+            let scalar = plain.add_input(scalar);
+            let point = plain.add_affine(generator);
+            plain.variable_base_scalar_mul(scalar, point)
+        };
+        let windowed_point = {
+            let scalar = windowed.add_input(scalar);
+            let point = windowed.add_affine(generator);
+            windowed.windowed_variable_base_scalar_mul(scalar, point)
+        };
+
+        assert_eq!(
+            plain.variables[&plain_point.x],
+            windowed.variables[&windowed_point.x]
+        );
+        assert_eq!(
+            plain.variables[&plain_point.y],
+            windowed.variables[&windowed_point.y]
+        );
+        assert!(windowed.circuit_size() < plain.circuit_size());
+
+        // Doubling takes one row and addition two
+        let n = plain.circuit_size();
+        plain.point_doubling_gate(plain_point);
+        assert_eq!(plain.circuit_size(), n + 1);
+        plain.point_addition_gate(plain_point, plain_point);
+        assert_eq!(plain.circuit_size(), n + 3);
+    }
+
+    fn test_signed_digits_top_bit<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let num_bits = <P::BaseField as PrimeField>::Params::MODULUS_BITS;
+        let num_digit_bits = 2 * (num_bits as usize).div_ceil(2);
+        let scalar = E::Fr::from(0xdead_beef_u64).pow([5]);
+
+        // With the top bit of `u` cleared, the remaining bits can encode
+        // `scalar + 4^m` reduced modulo the field, which is another
+        // representative of the scalar.
+        assert_unsatisfiable(
+            |composer: &mut StandardComposer<E, P>| {
+                let forged =
+                    scalar + E::Fr::from(2u64).pow([num_digit_bits as u64]);
+                let forged_bits = forged.into_repr().to_bits_le();
+                let witness_var = composer.add_input(scalar);
+                let parity =
+                    composer.add_input(E::Fr::from(forged_bits[0] as u64));
+                let digit_bits: Vec<_> = (0..num_digit_bits)
+                    .map(|j| {
+                        let bit = j + 1 < num_digit_bits && forged_bits[j + 1];
+                        composer.add_input(E::Fr::from(bit as u64))
+                    })
+                    .collect();
+                composer.constrain_signed_digits(
+                    witness_var,
+                    parity,
+                    &digit_bits,
+                );
+            },
+            1024,
+        );
+    }
+
+    fn test_signed_digits_non_canonical<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let num_bits = <P::BaseField as PrimeField>::Params::MODULUS_BITS;
+        let num_digit_bits = 2 * (num_bits as usize).div_ceil(2);
+        let scalar = E::Fr::from(0xdead_beef_u64).pow([5]);
+
+        // `scalar + p` fits below `4^m` and is congruent to the scalar, so
+        // only the comparison against the modulus rules it out.
+        assert_unsatisfiable(
+            |composer: &mut StandardComposer<E, P>| {
+                let mut forged = scalar.into_repr();
+                forged.add_nocarry(&<E::Fr as PrimeField>::Params::MODULUS);
+                let forged_bits = forged.to_bits_le();
+                let witness_var = composer.add_input(scalar);
+                let parity =
+                    composer.add_input(E::Fr::from(forged_bits[0] as u64));
+                let digit_bits: Vec<_> = (0..num_digit_bits)
+                    .map(|j| {
+                        let bit = j + 1 == num_digit_bits || forged_bits[j + 1];
+                        composer.add_input(E::Fr::from(bit as u64))
+                    })
+                    .collect();
+                composer.constrain_signed_digits(
+                    witness_var,
+                    parity,
+                    &digit_bits,
+                );
+            },
+            2048,
+        );
+    }
+
+    fn test_multi_scalar_mul<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = GroupAffine::<P>::new(x, y);
+        let bases = [generator, generator + generator, -generator];
+        let scalars = [
+            E::Fr::from(u64::MAX).pow([3]),
+            E::Fr::zero(),
+            E::Fr::from(0xdead_beef_u64).pow([5]),
+        ];
+        let expected_point: GroupAffine<P> = VariableBaseMSM::multi_scalar_mul(
+            &bases,
+            &scalars
+                .iter()
+                .map(|scalar| {
+                    util::to_embedded_curve_scalar::<E, P>(*scalar).into_repr()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .into();
+
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let terms: Vec<_> = scalars
+                    .iter()
+                    .zip(bases.iter())
+                    .map(|(scalar, base)| {
+                        (
+                            composer.add_input(*scalar),
+                            composer.add_affine(*base),
+                        )
+                    })
+                    .collect();
+                let point = composer.multi_scalar_mul(&terms);
+                composer.assert_equal_public_point(point, expected_point);
+
+                let empty = composer.multi_scalar_mul(&[]);
+                composer.assert_equal_public_point(empty, GroupAffine::zero());
+            },
+            8192,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_multi_scalar_mul_rows<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = GroupAffine::<P>::new(x, y);
+        let scalars = [
+            E::Fr::from(3u64),
+            E::Fr::from(u64::MAX),
+            E::Fr::from(0xdead_beef_u64).pow([5]),
+        ];
+
+        let mut separate = StandardComposer::<E, P>::new();
+        let mut straus = StandardComposer::<E, P>::new();
+        let separate_point = {
+            let point = separate.add_affine(generator);
+            let mut result = None;
+            for scalar in scalars {
+                let scalar = separate.add_input(scalar);
+                let term =
+                    separate.windowed_variable_base_scalar_mul(scalar, point);
+                result = Some(match result {
+                    None => term,
+                    Some(result) => separate.point_addition_gate(result, term),
+                });
+            }
+            result.unwrap()
+        };
+        let straus_point = {
+            let point = straus.add_affine(generator);
+            let terms: Vec<_> = scalars
+                .iter()
+                .map(|scalar| (straus.add_input(*scalar), point))
+                .collect();
+            straus.multi_scalar_mul(&terms)
+        };
+
+        assert_eq!(
+            separate.variables[&separate_point.x],
+            straus.variables[&straus_point.x]
+        );
+        assert_eq!(
+            separate.variables[&separate_point.y],
+            straus.variables[&straus_point.y]
+        );
+        assert!(straus.circuit_size() < separate.circuit_size());
+    }
+
     // Tests for Bls12_381
     batch_test!(
-        [test_var_base_scalar_mul],
+        [
+            test_var_base_scalar_mul,
+            test_windowed_var_base_scalar_mul,
+            test_windowed_var_base_scalar_mul_rows,
+            test_signed_digits_top_bit,
+            test_signed_digits_non_canonical,
+            test_multi_scalar_mul,
+            test_multi_scalar_mul_rows
+        ],
         [] => (
             Bls12_381,
             ark_ed_on_bls12_381::EdwardsParameters
@@ -156,7 +649,15 @@ mod test {
 
     // Tests for Bls12_377
     batch_test!(
-        [test_var_base_scalar_mul],
+        [
+            test_var_base_scalar_mul,
+            test_windowed_var_base_scalar_mul,
+            test_windowed_var_base_scalar_mul_rows,
+            test_signed_digits_top_bit,
+            test_signed_digits_non_canonical,
+            test_multi_scalar_mul,
+            test_multi_scalar_mul_rows
+        ],
         [] => (
             Bls12_377,
             ark_ed_on_bls12_377::EdwardsParameters
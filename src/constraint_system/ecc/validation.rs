@@ -0,0 +1,448 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Point Validation Gates
+
+use crate::constraint_system::ecc::Point;
+use crate::constraint_system::{variable::Variable, StandardComposer};
+use alloc::vec::Vec;
+use ark_ec::models::twisted_edwards_extended::GroupAffine;
+use ark_ec::models::TEModelParameters;
+use ark_ec::{AffineCurve, PairingEngine};
+use ark_ff::{
+    BigInteger, BitIteratorBE, Field, FpParameters, PrimeField, SquareRootField,
+};
+use num_traits::{One, Zero};
+
+impl<E, P> StandardComposer<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Adds to the circuit description the constraint that `point` satisfies
+    /// the curve equation `a * x^2 + y^2 = 1 + d * x^2 * y^2`.
+    pub fn assert_on_curve(&mut self, point: Point<E, P>) {
+        let x_sq =
+            self.mul(E::Fr::one(), point.x, point.x, E::Fr::zero(), None);
+        let y_sq =
+            self.mul(E::Fr::one(), point.y, point.y, E::Fr::zero(), None);
+        self.poly_gate(
+            x_sq,
+            y_sq,
+            self.zero_var,
+            -P::COEFF_D,
+            P::COEFF_A,
+            E::Fr::one(),
+            E::Fr::zero(),
+            -E::Fr::one(),
+            None,
+        );
+    }
+
+    /// Adds to the circuit description the constraint that `point` lies in
+    /// the prime order subgroup, which implies that it is on the curve.
+    ///
+    /// The point is checked to be the cofactor multiple of a witnessed point
+    /// on the curve, which only takes a few doublings since multiplying by
+    /// the cofactor maps the whole curve onto the prime order subgroup.
+    pub fn assert_in_prime_subgroup(&mut self, point: Point<E, P>) {
+        let value = GroupAffine::<P>::new(
+            self.variables[&point.x],
+            self.variables[&point.y],
+        );
+        let cleared = value.mul(P::COFACTOR_INV.into_repr());
+        let cleared = self.add_affine(cleared.into());
+        self.assert_on_curve(cleared);
+
+        let mut bits = BitIteratorBE::without_leading_zeros(P::COFACTOR);
+        bits.next();
+        let mut multiple = cleared;
+        for bit in bits {
+            multiple = self.point_doubling_gate(multiple);
+            if bit {
+                multiple = self.point_addition_gate(multiple, cleared);
+            }
+        }
+        self.assert_equal_point(multiple, point);
+    }
+
+    /// Adds to the circuit description the constraint that `point` is not the
+    /// identity, by witnessing `w_x` and `w_y` such that
+    /// `x * w_x + (y - 1) * w_y = 1`.
+    pub fn assert_not_identity(&mut self, point: Point<E, P>) {
+        let x = self.variables[&point.x];
+        let y = self.variables[&point.y];
+        let (w_x, w_y) = match x.inverse() {
+            Some(x_inv) => (x_inv, E::Fr::zero()),
+            None => (
+                E::Fr::zero(),
+                (y - E::Fr::one()).inverse().unwrap_or_default(),
+            ),
+        };
+        let w_x = self.add_input(w_x);
+        let w_y = self.add_input(w_y);
+
+        let x_w_x = self.mul(E::Fr::one(), point.x, w_x, E::Fr::zero(), None);
+        self.poly_gate(
+            point.y,
+            w_y,
+            x_w_x,
+            E::Fr::one(),
+            E::Fr::zero(),
+            -E::Fr::one(),
+            E::Fr::one(),
+            -E::Fr::one(),
+            None,
+        );
+    }
+
+    /// Adds to the circuit description the decompression of the point with
+    /// the given `y` coordinate whose `x` coordinate has the least
+    /// significant bit `sign`.
+    ///
+    /// The point is constrained to be on the curve but not to be in the prime
+    /// order subgroup, see [`StandardComposer::assert_in_prime_subgroup`].
+    pub fn decompress_point(
+        &mut self,
+        y: Variable,
+        sign: Variable,
+    ) -> Point<E, P> {
+        let y_value = self.variables[&y];
+        let sign_value = !self.variables[&sign].is_zero();
+
+        // x^2 = (1 - y^2) / (a - d * y^2), where the denominator never
+        // vanishes since `a` is a square and `d` is not.
+        let y_sq = y_value.square();
+        let x_value = ((E::Fr::one() - y_sq)
+            * (P::COEFF_A - P::COEFF_D * y_sq)
+                .inverse()
+                .unwrap_or_default())
+        .sqrt()
+        .map(|x| {
+            if x.into_repr().is_odd() == sign_value {
+                x
+            } else {
+                -x
+            }
+        })
+        .unwrap_or_default();
+
+        let x = self.add_input(x_value);
+        let point = Point::new(x, y);
+        self.assert_on_curve(point);
+
+        self.boolean_gate(sign);
+        let x_bits = self.canonical_decomposition(x);
+        self.assert_equal(x_bits[0], sign);
+
+        point
+    }
+
+    /// Decomposes `var` into the little-endian bits of its canonical
+    /// representative, constraining them to encode a value below the
+    /// modulus.
+    fn canonical_decomposition(&mut self, var: Variable) -> Vec<Variable> {
+        let num_bits = <E::Fr as PrimeField>::Params::MODULUS_BITS as usize;
+        let value_bits = self.variables[&var].into_repr().to_bits_le();
+        let bits: Vec<Variable> = value_bits[..num_bits]
+            .iter()
+            .map(|bit| {
+                let bit = self.add_input(E::Fr::from(*bit as u64));
+                self.boolean_gate(bit);
+                bit
+            })
+            .collect();
+
+        let two = E::Fr::from(2u64);
+        let terms: Vec<_> = bits
+            .iter()
+            .enumerate()
+            .map(|(power, bit)| (two.pow([power as u64]), *bit))
+            .collect();
+        let accumulator = self.linear_combination(&terms, E::Fr::zero());
+        self.assert_equal(accumulator, var);
+
+        self.assert_below_modulus(&bits);
+
+        bits
+    }
+
+    /// Constrains the little-endian boolean `bits` to encode an integer below
+    /// the modulus of the scalar field. Bits past the modulus width must be
+    /// zero.
+    pub(crate) fn assert_below_modulus(&mut self, bits: &[Variable]) {
+        let num_bits = <E::Fr as PrimeField>::Params::MODULUS_BITS as usize;
+        let (bits, high_bits) = bits.split_at(bits.len().min(num_bits));
+        for bit in high_bits {
+            self.constrain_to_constant(*bit, E::Fr::zero(), None);
+        }
+        // Fewer bits than the modulus width can't reach the modulus.
+        if bits.len() < num_bits {
+            return;
+        }
+
+        // Compare against `modulus - 1` from the most significant bit down,
+        // tracking whether all the bits so far are equal. While they are, a
+        // bit must not exceed the matching bit of the bound. The bits facing
+        // a run of zeros in the bound are summed first: being boolean, they
+        // are all zero exactly when their sum is.
+        let bound_bits = (-E::Fr::one()).into_repr().to_bits_le();
+        let pairs: Vec<_> =
+            bits.iter().zip(&bound_bits[..num_bits]).rev().collect();
+        let mut equal: Option<Variable> = None;
+        for run in pairs.chunk_by(|(_, a), (_, b)| a == b) {
+            if *run[0].1 {
+                for (bit, _) in run {
+                    equal = Some(match equal {
+                        None => **bit,
+                        Some(prefix) => self.mul(
+                            E::Fr::one(),
+                            prefix,
+                            **bit,
+                            E::Fr::zero(),
+                            None,
+                        ),
+                    });
+                }
+                continue;
+            }
+
+            let terms: Vec<_> =
+                run.iter().map(|(bit, _)| (E::Fr::one(), **bit)).collect();
+            let sum = self.linear_combination(&terms, E::Fr::zero());
+            match equal {
+                None => self.constrain_to_constant(sum, E::Fr::zero(), None),
+                Some(prefix) => {
+                    self.poly_gate(
+                        prefix,
+                        sum,
+                        self.zero_var,
+                        E::Fr::one(),
+                        E::Fr::zero(),
+                        E::Fr::zero(),
+                        E::Fr::zero(),
+                        E::Fr::zero(),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{batch_test, testing::*};
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+
+    /// Returns the point of order two `(0, -1)`.
+    fn order_two_point<P>() -> GroupAffine<P>
+    where
+        P: TEModelParameters,
+    {
+        GroupAffine::new(P::BaseField::zero(), -P::BaseField::one())
+    }
+
+    fn generator<P>() -> GroupAffine<P>
+    where
+        P: TEModelParameters,
+    {
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        GroupAffine::new(x, y)
+    }
+
+    fn test_valid_points<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let generator = generator::<P>();
+                for value in [generator, generator + generator, -generator] {
+                    let point = composer.add_affine(value);
+                    composer.assert_on_curve(point);
+                    composer.assert_in_prime_subgroup(point);
+                    composer.assert_not_identity(point);
+                }
+                // Small order points are on the curve and not the identity
+                let point = composer.add_affine(order_two_point());
+                composer.assert_on_curve(point);
+                composer.assert_not_identity(point);
+            },
+            64,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_point_not_on_curve<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let point = composer
+                    .add_affine(GroupAffine::new(E::Fr::one(), E::Fr::one()));
+                composer.assert_on_curve(point);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_identity<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let point = composer.add_affine(GroupAffine::zero());
+                composer.assert_not_identity(point);
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_small_order_points<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let order_two = order_two_point::<P>();
+        // The points of order four are `(+-1 / sqrt(a), 0)`
+        let order_four = P::COEFF_A
+            .inverse()
+            .and_then(|a_inv| a_inv.sqrt())
+            .map(|x| GroupAffine::<P>::new(x, E::Fr::zero()));
+        assert!(order_two.is_on_curve());
+        assert!(!order_two.is_in_correct_subgroup_assuming_on_curve());
+
+        let small_order_points = [
+            Some(order_two),
+            order_four,
+            Some(generator::<P>() + order_two),
+        ];
+        for value in small_order_points.into_iter().flatten() {
+            let res = gadget_tester(
+                |composer: &mut StandardComposer<E, P>| {
+                    let point = composer.add_affine(value);
+                    composer.assert_in_prime_subgroup(point);
+                },
+                32,
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    fn test_decompress_point<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let generator = generator::<P>();
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                for value in [generator, -generator] {
+                    let y = composer.add_input(value.y);
+                    let sign = composer.add_input(E::Fr::from(
+                        value.x.into_repr().is_odd() as u64,
+                    ));
+                    let point = composer.decompress_point(y, sign);
+                    composer.assert_equal_public_point(point, value);
+                }
+            },
+            2048,
+        );
+        assert!(res.is_ok());
+    }
+
+    fn test_decompress_wrong_sign<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let generator = generator::<P>();
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let y = composer.add_input(generator.y);
+                let sign = composer.add_input(E::Fr::from(
+                    !generator.x.into_repr().is_odd() as u64,
+                ));
+                let point = composer.decompress_point(y, sign);
+                composer.assert_equal_public_point(point, generator);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_decompress_invalid_y<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        // Find a `y` coordinate with no point on the curve
+        let y_value = (2u64..)
+            .map(E::Fr::from)
+            .find(|y| {
+                ((E::Fr::one() - y.square())
+                    * (P::COEFF_A - P::COEFF_D * y.square()).inverse().unwrap())
+                .sqrt()
+                .is_none()
+            })
+            .unwrap();
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let y = composer.add_input(y_value);
+                let sign = composer.zero_var();
+                composer.decompress_point(y, sign);
+            },
+            2048,
+        );
+        assert!(res.is_err());
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_valid_points,
+            test_point_not_on_curve,
+            test_identity,
+            test_small_order_points,
+            test_decompress_point,
+            test_decompress_wrong_sign,
+            test_decompress_invalid_y
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_valid_points,
+            test_point_not_on_curve,
+            test_identity,
+            test_small_order_points,
+            test_decompress_point,
+            test_decompress_wrong_sign,
+            test_decompress_invalid_y
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
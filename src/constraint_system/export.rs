@@ -0,0 +1,479 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Circuit Export
+//!
+//! Dumps the gate table of a [`StandardComposer`] as CSV or JSON, and its
+//! copy constraints as a Graphviz DOT graph, to look into a circuit whose
+//! proofs fail.
+
+use crate::constraint_system::stats::write_json_string;
+use crate::constraint_system::{StandardComposer, Variable, WireData};
+use alloc::string::String;
+use alloc::vec::Vec;
+use ark_ec::{PairingEngine, TEModelParameters};
+use ark_ff::{BigInteger, PrimeField};
+use core::fmt::Write;
+use core::ops::Range;
+
+/// Names of the selectors, in the order of the exported columns.
+const SELECTORS: [&str; 12] = [
+    "q_m",
+    "q_l",
+    "q_r",
+    "q_o",
+    "q_4",
+    "q_c",
+    "q_arith",
+    "q_range",
+    "q_logic",
+    "q_fixed_group_add",
+    "q_variable_group_add",
+    "q_variable_group_double",
+];
+
+/// Names of the wires, in the order of the exported columns.
+const WIRES: [&str; 4] = ["w_l", "w_r", "w_o", "w_4"];
+
+/// Gates to export.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GateFilter {
+    /// Every gate of the circuit.
+    All,
+    /// Gates within the range.
+    Gates(Range<usize>),
+    /// Gates added inside of the namespace with this full path, see
+    /// [`StandardComposer::namespace`].
+    Namespace(String),
+}
+
+impl<E, P> StandardComposer<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Returns the gates selected by `filter` as CSV, one row per gate.
+    ///
+    /// Each row holds the gate index, its innermost namespace, the selectors,
+    /// the wire variables followed by their values and the public input.
+    /// Field elements are written in decimal, with small negative values as
+    /// `-n` and large values in hexadecimal.
+    pub fn gates_to_csv(&self, filter: &GateFilter) -> String {
+        let mut out = String::from("gate,namespace");
+        for name in SELECTORS.iter().chain(&WIRES) {
+            write!(out, ",{}", name).expect("writing to a String can't fail");
+        }
+        for name in &WIRES {
+            write!(out, ",{}_value", name)
+                .expect("writing to a String can't fail");
+        }
+        out.push_str(",public_input\n");
+
+        for gate in self.filter_gates(filter) {
+            write!(out, "{},", gate).expect("writing to a String can't fail");
+            if let Some(namespace) = self.gate_namespace(gate) {
+                write_csv_string(&mut out, namespace);
+            }
+            for selector in self.selectors(gate) {
+                out.push(',');
+                write_field(&mut out, selector);
+            }
+            for var in self.wires(gate) {
+                write!(out, ",v{}", var.0)
+                    .expect("writing to a String can't fail");
+            }
+            for var in self.wires(gate) {
+                out.push(',');
+                write_field(&mut out, self.variables[&var]);
+            }
+            out.push(',');
+            if let Some(pi) = self.public_inputs_sparse_store.get(&gate) {
+                write_field(&mut out, *pi);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Returns the gates selected by `filter` as a JSON array with an object
+    /// per gate, holding the same data as
+    /// [`gates_to_csv`](Self::gates_to_csv).
+    pub fn gates_to_json(&self, filter: &GateFilter) -> String {
+        let mut out = String::from("[");
+        for (i, gate) in self.filter_gates(filter).into_iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            write!(out, "{{\"gate\":{},\"namespace\":", gate)
+                .expect("writing to a String can't fail");
+            match self.gate_namespace(gate) {
+                Some(namespace) => write_json_string(&mut out, namespace),
+                None => out.push_str("null"),
+            }
+
+            out.push_str(",\"selectors\":{");
+            for (j, (name, selector)) in
+                SELECTORS.iter().zip(self.selectors(gate)).enumerate()
+            {
+                if j > 0 {
+                    out.push(',');
+                }
+                write!(out, "\"{}\":\"", name)
+                    .expect("writing to a String can't fail");
+                write_field(&mut out, selector);
+                out.push('"');
+            }
+
+            out.push_str("},\"wires\":{");
+            for (j, (name, var)) in
+                WIRES.iter().zip(self.wires(gate)).enumerate()
+            {
+                if j > 0 {
+                    out.push(',');
+                }
+                write!(
+                    out,
+                    "\"{}\":{{\"variable\":{},\"value\":\"",
+                    name, var.0
+                )
+                .expect("writing to a String can't fail");
+                write_field(&mut out, self.variables[&var]);
+                out.push_str("\"}");
+            }
+
+            out.push_str("},\"public_input\":");
+            match self.public_inputs_sparse_store.get(&gate) {
+                Some(pi) => {
+                    out.push('"');
+                    write_field(&mut out, *pi);
+                    out.push('"');
+                }
+                None => out.push_str("null"),
+            }
+            out.push('}');
+        }
+        out.push(']');
+        out
+    }
+
+    /// Returns the copy constraints between the gates selected by `filter`
+    /// as a Graphviz DOT graph.
+    ///
+    /// Every gate is a node with a port per wire, and the wires holding the
+    /// same variable are linked in a chain labelled with the variable, as in
+    /// the cycles of the permutation argument.
+    pub fn copy_constraints_to_dot(&self, filter: &GateFilter) -> String {
+        let gates = self.filter_gates(filter);
+        let mut out =
+            String::from("graph circuit {\n    node [shape=record];\n");
+        for gate in &gates {
+            let mut label = format!("gate {}", gate);
+            if let Some(namespace) = self.gate_namespace(*gate) {
+                write!(label, "\\n{}", namespace)
+                    .expect("writing to a String can't fail");
+            }
+            write!(out, "    g{} [label=\"{{", gate)
+                .expect("writing to a String can't fail");
+            write_dot_label(&mut out, &label);
+            out.push_str("|{");
+            for (i, (name, var)) in
+                WIRES.iter().zip(self.wires(*gate)).enumerate()
+            {
+                if i > 0 {
+                    out.push('|');
+                }
+                write!(out, "<{}> {}: v{}", name, name, var.0)
+                    .expect("writing to a String can't fail");
+            }
+            out.push_str("}}\"];\n");
+        }
+
+        let mut variables: Vec<_> = self.perm.variable_map.iter().collect();
+        variables.sort_by_key(|(var, _)| var.0);
+        for (var, wires) in variables {
+            let wires: Vec<_> = wires
+                .iter()
+                .map(|wire| wire_port(*wire))
+                .filter(|(gate, _)| gates.binary_search(gate).is_ok())
+                .collect();
+            for pair in wires.windows(2) {
+                let ((from, from_wire), (to, to_wire)) = (pair[0], pair[1]);
+                writeln!(
+                    out,
+                    "    g{}:{} -- g{}:{} [label=\"v{}\"];",
+                    from, from_wire, to, to_wire, var.0
+                )
+                .expect("writing to a String can't fail");
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Returns the sorted gates selected by `filter`.
+    fn filter_gates(&self, filter: &GateFilter) -> Vec<usize> {
+        match filter {
+            GateFilter::All => (0..self.n).collect(),
+            GateFilter::Gates(gates) => {
+                (gates.start.min(self.n)..gates.end.min(self.n)).collect()
+            }
+            GateFilter::Namespace(name) => {
+                let mut gates: Vec<usize> = self
+                    .namespaces
+                    .iter()
+                    .filter(|(path, _)| path == name)
+                    .flat_map(|(_, gates)| gates.clone())
+                    .collect();
+                gates.sort_unstable();
+                gates.dedup();
+                gates
+            }
+        }
+    }
+
+    /// Returns the path of the innermost namespace holding `gate`, preferring
+    /// the deepest path among namespaces over the same gates.
+    fn gate_namespace(&self, gate: usize) -> Option<&str> {
+        self.namespaces
+            .iter()
+            .filter(|(_, gates)| gates.contains(&gate))
+            .max_by_key(|(path, gates)| {
+                (gates.start, core::cmp::Reverse(gates.end), path.len())
+            })
+            .map(|(path, _)| path.as_str())
+    }
+
+    /// Returns the selectors of `gate`, in the order of [`SELECTORS`].
+    fn selectors(&self, gate: usize) -> [E::Fr; 12] {
+        [
+            self.q_m[gate],
+            self.q_l[gate],
+            self.q_r[gate],
+            self.q_o[gate],
+            self.q_4[gate],
+            self.q_c[gate],
+            self.q_arith[gate],
+            self.q_range[gate],
+            self.q_logic[gate],
+            self.q_fixed_group_add[gate],
+            self.q_variable_group_add[gate],
+            self.q_variable_group_double[gate],
+        ]
+    }
+
+    /// Returns the wires of `gate`, in the order of [`WIRES`].
+    fn wires(&self, gate: usize) -> [Variable; 4] {
+        [
+            self.w_l[gate],
+            self.w_r[gate],
+            self.w_o[gate],
+            self.w_4[gate],
+        ]
+    }
+}
+
+/// Returns the gate and the name of `wire`.
+fn wire_port(wire: WireData) -> (usize, &'static str) {
+    match wire {
+        WireData::Left(gate) => (gate, WIRES[0]),
+        WireData::Right(gate) => (gate, WIRES[1]),
+        WireData::Output(gate) => (gate, WIRES[2]),
+        WireData::Fourth(gate) => (gate, WIRES[3]),
+    }
+}
+
+/// Writes `value` in decimal if it or its negation fits in a `u64`, and in
+/// hexadecimal otherwise.
+fn write_field<F>(out: &mut String, value: F)
+where
+    F: PrimeField,
+{
+    let small = |value: F| {
+        let repr = value.into_repr();
+        let limbs = repr.as_ref();
+        limbs[1..].iter().all(|limb| *limb == 0).then_some(limbs[0])
+    };
+    let res = match (small(value), small(-value)) {
+        (Some(n), _) => write!(out, "{}", n),
+        (None, Some(n)) => write!(out, "-{}", n),
+        (None, None) => {
+            out.push_str("0x");
+            let bytes = value.into_repr().to_bytes_be();
+            let first = bytes.iter().position(|b| *b != 0).unwrap_or(0);
+            bytes[first..]
+                .iter()
+                .try_for_each(|byte| write!(out, "{:02x}", byte))
+        }
+    };
+    res.expect("writing to a String can't fail");
+}
+
+/// Writes `s` as a CSV field, quoted if needed.
+fn write_csv_string(out: &mut String, s: &str) {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        out.push('"');
+        out.push_str(&s.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(s);
+    }
+}
+
+/// Writes `s` within a quoted DOT record label.
+fn write_dot_label(out: &mut String, s: &str) {
+    for c in s.chars() {
+        if "\"{}|<>".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use num_traits::{One, Zero};
+
+    /// Builds `(a + b) * b` with its inputs in a namespace and the product as
+    /// a public input.
+    fn circuit<E, P>() -> (StandardComposer<E, P>, [Variable; 3])
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut composer = StandardComposer::new();
+        let (a, b) = composer.namespace("inputs", |composer| {
+            let a = composer.add_input(E::Fr::from(3u64));
+            let b = composer.add_input(E::Fr::from(5u64));
+            composer.namespace("bits", |composer| composer.boolean_gate(a));
+            (a, b)
+        });
+        let sum = composer.add(
+            (E::Fr::one(), a),
+            (E::Fr::one(), b),
+            E::Fr::zero(),
+            None,
+        );
+        let product = composer.mul(E::Fr::one(), sum, b, E::Fr::zero(), None);
+        composer.constrain_to_constant(
+            product,
+            E::Fr::zero(),
+            Some(-E::Fr::from(40u64)),
+        );
+        (composer, [a, b, sum])
+    }
+
+    fn test_gates_to_csv<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let (composer, [a, b, sum]) = circuit::<E, P>();
+        let csv = composer.gates_to_csv(&GateFilter::All);
+        let rows: Vec<_> = csv.lines().collect();
+        assert_eq!(rows.len(), composer.circuit_size() + 1);
+        assert!(rows[0].starts_with("gate,namespace,q_m,q_l,"));
+        assert!(rows[0].ends_with(",w_4_value,public_input"));
+        assert_eq!(rows[1].split(',').count(), 23);
+        assert_eq!(
+            rows[4],
+            format!(
+                "3,inputs/bits,1,0,0,-1,0,0,1,0,0,0,0,0,v{a},v{a},v{a},v{z},\
+                 3,3,3,0,",
+                a = a.0,
+                z = composer.zero_var.0
+            )
+        );
+
+        let csv = composer.gates_to_csv(&GateFilter::Gates(4..100));
+        let rows: Vec<_> = csv.lines().skip(1).collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].starts_with("4,,0,1,1,-1,"));
+        assert!(rows[0].contains(&format!(",v{},v{},v{},", a.0, b.0, sum.0)));
+        assert!(rows[2].ends_with(",-40"));
+    }
+
+    fn test_gates_to_json<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let (composer, [a, ..]) = circuit::<E, P>();
+        let json =
+            composer.gates_to_json(&GateFilter::Namespace("inputs".into()));
+        assert!(json.starts_with("[{\"gate\":3,\"namespace\":\"inputs/bits\","));
+        assert!(json.contains(&format!(
+            "\"wires\":{{\"w_l\":{{\"variable\":{},\"value\":\"3\"}}",
+            a.0
+        )));
+        assert!(json.ends_with("\"public_input\":null}]"));
+        assert_eq!(json.matches("\"gate\"").count(), 1);
+        assert_eq!(
+            composer.gates_to_json(&GateFilter::Namespace("missing".into())),
+            "[]"
+        );
+
+        let json = composer.gates_to_json(&GateFilter::Gates(6..7));
+        assert!(json.contains("\"namespace\":null"));
+        assert!(json.ends_with("\"public_input\":\"-40\"}]"));
+    }
+
+    fn test_copy_constraints_to_dot<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let (composer, [a, b, sum]) = circuit::<E, P>();
+        let dot = composer.copy_constraints_to_dot(&GateFilter::Gates(3..6));
+        assert!(dot.starts_with("graph circuit {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(
+            dot.contains("    g3 [label=\"{gate 3\\ninputs/bits|{<w_l> w_l: v")
+        );
+        assert_eq!(dot.matches(" [label=\"{gate").count(), 3);
+        assert!(dot
+            .contains(&format!("    g3:w_o -- g4:w_l [label=\"v{}\"];", a.0)));
+        assert!(dot
+            .contains(&format!("    g4:w_r -- g5:w_r [label=\"v{}\"];", b.0)));
+        assert!(dot.contains(&format!(
+            "    g4:w_o -- g5:w_l [label=\"v{}\"];",
+            sum.0
+        )));
+        // Gates outside of the range are left out
+        assert!(!dot.contains("g6"));
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_gates_to_csv,
+            test_gates_to_json,
+            test_copy_constraints_to_dot
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_gates_to_csv,
+            test_gates_to_json,
+            test_copy_constraints_to_dot
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -0,0 +1,453 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Operator-overloaded Field Variables
+//!
+//! A [`FieldVar`] wraps a linear combination of [`Variable`]s together with a
+//! handle to the [`StandardComposer`] it lives in. Additions, subtractions and
+//! scalings are accumulated lazily and only turn into gates once the value is
+//! needed by a multiplication or explicitly materialized, so linear terms get
+//! folded into the width-4 arithmetic gates instead of costing a row each.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::vec::Vec;
+use ark_ec::{PairingEngine, TEModelParameters};
+use core::cell::RefCell;
+use core::ops::{Add, Mul, Neg, Sub};
+use num_traits::{One, Zero};
+
+/// Field element inside of a circuit, represented as the lazily accumulated
+/// linear combination `sum(coeff_i * var_i) + constant`.
+///
+/// Build them through [`StandardComposer::with_field_vars`] and combine them
+/// using the `+`, `-`, `*` operators and the constant helpers. Gates are
+/// only added to the circuit description when a product is taken or when the
+/// combination is materialized with [`FieldVar::to_variable`].
+#[derive(derivative::Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Composer the linear combination belongs to.
+    cs: &'a RefCell<StandardComposer<E, P>>,
+
+    /// Linear terms of the combination.
+    terms: Vec<(E::Fr, Variable)>,
+
+    /// Constant term of the combination.
+    constant: E::Fr,
+}
+
+impl<'a, E, P> FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Wraps an already allocated [`Variable`] without adding any gate.
+    pub fn from_variable(
+        cs: &'a RefCell<StandardComposer<E, P>>,
+        var: Variable,
+    ) -> Self {
+        Self {
+            cs,
+            terms: vec![(E::Fr::one(), var)],
+            constant: E::Fr::zero(),
+        }
+    }
+
+    /// Allocates a new private witness with the given `value`.
+    pub fn new_witness(
+        cs: &'a RefCell<StandardComposer<E, P>>,
+        value: E::Fr,
+    ) -> Self {
+        let var = cs.borrow_mut().add_input(value);
+        Self::from_variable(cs, var)
+    }
+
+    /// Builds a constant which is part of the circuit description. No gate is
+    /// added until the constant gets materialized on its own.
+    pub fn constant(
+        cs: &'a RefCell<StandardComposer<E, P>>,
+        value: E::Fr,
+    ) -> Self {
+        Self {
+            cs,
+            terms: Vec::new(),
+            constant: value,
+        }
+    }
+
+    /// Returns `true` if the combination has no variable terms.
+    pub fn is_constant(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns the witness value of the combination.
+    pub fn value(&self) -> E::Fr {
+        let cs = self.cs.borrow();
+        self.terms.iter().fold(self.constant, |acc, (coeff, var)| {
+            acc + *coeff * cs.variables[var]
+        })
+    }
+
+    /// Adds a constant to `self`.
+    pub fn add_constant(mut self, constant: E::Fr) -> Self {
+        self.constant += constant;
+        self
+    }
+
+    /// Subtracts a constant from `self`.
+    pub fn sub_constant(self, constant: E::Fr) -> Self {
+        self.add_constant(-constant)
+    }
+
+    /// Scales `self` by a constant.
+    pub fn mul_constant(mut self, constant: E::Fr) -> Self {
+        if constant.is_zero() {
+            self.terms.clear();
+        } else {
+            self.terms
+                .iter_mut()
+                .for_each(|(coeff, _)| *coeff *= constant);
+        }
+        self.constant *= constant;
+        self
+    }
+
+    /// Materializes the linear combination into a single [`Variable`]
+    /// through [`StandardComposer::linear_combination`].
+    ///
+    /// Combinations that already are a plain variable are returned as they
+    /// are without adding any gate.
+    pub fn to_variable(&self) -> Variable {
+        self.cs
+            .borrow_mut()
+            .linear_combination(&self.terms, self.constant)
+    }
+
+    /// Adds the constraint `self == other` to the circuit description.
+    pub fn enforce_equal(&self, other: &Self) {
+        let diff = self.clone() - other.clone();
+        let mut cs = self.cs.borrow_mut();
+        let zero = cs.zero_var();
+        match diff.terms.as_slice() {
+            [] => cs.constrain_to_constant(zero, diff.constant, None),
+            terms => {
+                let last = cs.reduce_linear_terms(terms, diff.constant, 3);
+                let (q_l, a) = last[0];
+                let (q_r, b) =
+                    last.get(1).copied().unwrap_or((E::Fr::zero(), zero));
+                let (q_4, d) =
+                    last.get(2).copied().unwrap_or((E::Fr::zero(), zero));
+                let q_c = if last.len() == terms.len() {
+                    diff.constant
+                } else {
+                    E::Fr::zero()
+                };
+                cs.big_add_gate(
+                    a,
+                    b,
+                    zero,
+                    Some(d),
+                    q_l,
+                    q_r,
+                    E::Fr::zero(),
+                    q_4,
+                    q_c,
+                    None,
+                );
+            }
+        }
+    }
+
+    /// Reduces `self` to the form `coeff * var + constant` adding as few
+    /// gates as possible.
+    fn to_single_term(&self) -> Option<(E::Fr, Variable, E::Fr)> {
+        match self.terms.as_slice() {
+            [] => None,
+            [(coeff, var)] => Some((*coeff, *var, self.constant)),
+            _ => Some((E::Fr::one(), self.to_variable(), E::Fr::zero())),
+        }
+    }
+
+    /// Adds the scaled term `coeff * var` to the combination, merging it with
+    /// an existing term for the same variable.
+    fn push_term(&mut self, coeff: E::Fr, var: Variable) {
+        match self.terms.iter().position(|(_, v)| *v == var) {
+            Some(i) => {
+                self.terms[i].0 += coeff;
+                if self.terms[i].0.is_zero() {
+                    self.terms.remove(i);
+                }
+            }
+            None if coeff.is_zero() => {}
+            None => self.terms.push((coeff, var)),
+        }
+    }
+}
+
+impl<E, P> StandardComposer<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Runs `f` with a shared handle to this composer so that [`FieldVar`]s
+    /// can be built and combined with the arithmetic operators.
+    ///
+    /// The [`FieldVar`]s cannot outlive the closure, so anything that is
+    /// needed afterwards should be returned as a [`Variable`] through
+    /// [`FieldVar::to_variable`].
+    pub fn with_field_vars<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&RefCell<StandardComposer<E, P>>) -> R,
+    {
+        let cs = RefCell::new(core::mem::take(self));
+        let res = f(&cs);
+        *self = cs.into_inner();
+        res
+    }
+}
+
+impl<'a, E, P> Add for FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        rhs.terms
+            .into_iter()
+            .for_each(|(coeff, var)| self.push_term(coeff, var));
+        self.constant += rhs.constant;
+        self
+    }
+}
+
+impl<'a, 'b, E, P> Add<&'b FieldVar<'a, E, P>> for FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: &'b FieldVar<'a, E, P>) -> Self {
+        self + rhs.clone()
+    }
+}
+
+impl<'a, E, P> Neg for FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.mul_constant(-E::Fr::one())
+    }
+}
+
+impl<'a, E, P> Sub for FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<'a, 'b, E, P> Sub<&'b FieldVar<'a, E, P>> for FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: &'b FieldVar<'a, E, P>) -> Self {
+        self - rhs.clone()
+    }
+}
+
+impl<'a, E, P> Mul for FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    type Output = Self;
+
+    /// Multiplies two combinations.
+    ///
+    /// Constant operands only scale the other side. Otherwise both sides are
+    /// reduced to `coeff * var + constant` and the whole product
+    /// `(c_1 * x + k_1) * (c_2 * y + k_2)` is computed with a single
+    /// `big_arith` gate.
+    fn mul(self, rhs: Self) -> Self {
+        let cs = self.cs;
+        match (self.to_single_term(), rhs.to_single_term()) {
+            (None, _) => rhs.mul_constant(self.constant),
+            (_, None) => self.mul_constant(rhs.constant),
+            (Some((c_1, x, k_1)), Some((c_2, y, k_2))) => {
+                let out = cs.borrow_mut().big_arith(
+                    c_1 * c_2,
+                    x,
+                    y,
+                    c_1 * k_2,
+                    c_2 * k_1,
+                    None,
+                    k_1 * k_2,
+                    None,
+                );
+                FieldVar::from_variable(cs, out)
+            }
+        }
+    }
+}
+
+impl<'a, 'b, E, P> Mul<&'b FieldVar<'a, E, P>> for FieldVar<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: &'b FieldVar<'a, E, P>) -> Self {
+        self * rhs.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::testing::*;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+
+    fn test_field_var_arithmetic<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                composer.with_field_vars(|cs| {
+                    // (a + 2b - c + 3) * (d - 1) + a * a == 995
+                    let a = FieldVar::new_witness(cs, E::Fr::from(4u64));
+                    let b = FieldVar::new_witness(cs, E::Fr::from(5u64));
+                    let c = FieldVar::new_witness(cs, E::Fr::from(6u64));
+                    let d = FieldVar::new_witness(cs, E::Fr::from(90u64));
+
+                    let lhs = a.clone() + b.mul_constant(E::Fr::from(2u64)) - c
+                        + FieldVar::constant(cs, E::Fr::from(3u64));
+                    let rhs = d.sub_constant(E::Fr::one());
+                    let res = lhs * rhs + a.clone() * a;
+
+                    assert_eq!(res.value(), E::Fr::from(995u64));
+                    res.enforce_equal(&FieldVar::constant(
+                        cs,
+                        E::Fr::from(995u64),
+                    ));
+                });
+            },
+            64,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_field_var_wrong_equality<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                composer.with_field_vars(|cs| {
+                    let a = FieldVar::new_witness(cs, E::Fr::from(4u64));
+                    let b = FieldVar::new_witness(cs, E::Fr::from(5u64));
+                    (a * b).enforce_equal(&FieldVar::constant(
+                        cs,
+                        E::Fr::from(21u64),
+                    ));
+                });
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    fn test_field_var_folds_linear_terms<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut composer = StandardComposer::<E, P>::new();
+        let vars = (1..=7u64)
+            .map(|i| composer.add_input(E::Fr::from(i)))
+            .collect::<Vec<_>>();
+
+        // Naive approach: one `add` per term.
+        let start = composer.circuit_size();
+        let mut acc = vars[0];
+        for var in &vars[1..] {
+            acc = composer.add(
+                (E::Fr::one(), acc),
+                (E::Fr::one(), *var),
+                E::Fr::zero(),
+                None,
+            );
+        }
+        let naive_rows = composer.circuit_size() - start;
+
+        // Lazy approach: the sum is only materialized once.
+        let start = composer.circuit_size();
+        let sum = composer.with_field_vars(|cs| {
+            vars.iter()
+                .map(|var| FieldVar::from_variable(cs, *var))
+                .reduce(|acc, var| acc + var)
+                .unwrap()
+                .to_variable()
+        });
+        let lazy_rows = composer.circuit_size() - start;
+
+        assert_eq!(naive_rows, 6);
+        assert_eq!(lazy_rows, 3);
+        assert_eq!(composer.variables[&sum], composer.variables[&acc]);
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [
+            test_field_var_arithmetic,
+            test_field_var_wrong_equality,
+            test_field_var_folds_linear_terms
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [
+            test_field_var_arithmetic,
+            test_field_var_wrong_equality,
+            test_field_var_folds_linear_terms
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
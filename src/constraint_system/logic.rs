@@ -12,6 +12,8 @@
 //! `AND` gate.
 
 use crate::constraint_system::{StandardComposer, Variable, WireData};
+use crate::error::Error;
+use alloc::vec::Vec;
 use ark_ec::{PairingEngine, TEModelParameters};
 use ark_ff::{BigInteger, PrimeField};
 use num_traits::{One, Zero};
@@ -33,19 +35,20 @@ where
     /// - is_xor_gate = 0 -> Performs AND between the first `num_bits` for `a`
     ///   and `b`.
     ///
-    /// # Panics
-    /// This function will panic if the num_bits specified is not even, ie.
-    /// `num_bits % 2 != 0`.
+    /// Fails with [`Error::OddNumberOfBits`] if the num_bits specified is not
+    /// even, ie. `num_bits % 2 != 0`.
     fn logic_gate(
         &mut self,
         a: Variable,
         b: Variable,
         num_bits: usize,
         is_xor_gate: bool,
-    ) -> Variable {
+    ) -> Result<Variable, Error> {
         // Since we work on base4, we need to guarantee that we have an even
         // number of bits representing the greatest input.
-        assert_eq!(num_bits & 1, 0);
+        if num_bits & 1 != 0 {
+            return Err(Error::OddNumberOfBits { num_bits });
+        }
         // We will have exactly `num_bits / 2` quads (quaternary digits)
         // representing both numbers.
         let num_quads = num_bits >> 1;
@@ -250,6 +253,7 @@ where
             self.q_range.push(E::Fr::zero());
             self.q_fixed_group_add.push(E::Fr::zero());
             self.q_variable_group_add.push(E::Fr::zero());
+            self.q_variable_group_double.push(E::Fr::zero());
             match is_xor_gate {
                 true => {
                     self.q_c.push(-E::Fr::one());
@@ -271,6 +275,7 @@ where
         self.q_range.push(E::Fr::zero());
         self.q_fixed_group_add.push(E::Fr::zero());
         self.q_variable_group_add.push(E::Fr::zero());
+        self.q_variable_group_double.push(E::Fr::zero());
 
         self.q_c.push(E::Fr::zero());
         self.q_logic.push(E::Fr::zero());
@@ -311,21 +316,20 @@ where
         // we can safely return the resulting variable of the gate computation
         // which is stored on the last program memory row and in the column that
         // `w_4` is holding.
-        self.w_4[self.w_4.len() - 1]
+        Ok(self.w_4[self.w_4.len() - 1])
     }
 
     /// Adds a logical XOR gate that performs the XOR between two values for the
     /// specified first `num_bits` returning a [`Variable`] holding the result.
     ///
-    /// # Panics
-    ///
-    /// If the `num_bits` specified in the fn params is odd.
+    /// Fails with [`Error::OddNumberOfBits`] if the `num_bits` specified in the
+    /// fn params is odd.
     pub fn xor_gate(
         &mut self,
         a: Variable,
         b: Variable,
         num_bits: usize,
-    ) -> Variable {
+    ) -> Result<Variable, Error> {
         self.logic_gate(a, b, num_bits, true)
     }
 
@@ -333,15 +337,14 @@ where
     /// for the specified first `num_bits` returning a [`Variable`] holding the
     /// result.
     ///
-    /// # Panics
-    ///
-    /// If the `num_bits` specified in the fn params is odd.
+    /// Fails with [`Error::OddNumberOfBits`] if the `num_bits` specified in the
+    /// fn params is odd.
     pub fn and_gate(
         &mut self,
         a: Variable,
         b: Variable,
         num_bits: usize,
-    ) -> Variable {
+    ) -> Result<Variable, Error> {
         self.logic_gate(a, b, num_bits, false)
     }
 }
@@ -349,7 +352,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::constraint_system::helper::*;
+    use crate::testing::*;
     use crate::{batch_test, constraint_system::StandardComposer};
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
@@ -364,7 +367,8 @@ mod test {
             |composer: &mut StandardComposer<E, P>| {
                 let witness_a = composer.add_input(E::Fr::from(500u64));
                 let witness_b = composer.add_input(E::Fr::from(357u64));
-                let xor_res = composer.xor_gate(witness_a, witness_b, 10);
+                let xor_res =
+                    composer.xor_gate(witness_a, witness_b, 10).unwrap();
                 // Check that the XOR result is indeed what we are expecting.
                 composer.constrain_to_constant(
                     xor_res,
@@ -381,7 +385,8 @@ mod test {
             |composer: &mut StandardComposer<E, P>| {
                 let witness_a = composer.add_input(E::Fr::from(469u64));
                 let witness_b = composer.add_input(E::Fr::from(321u64));
-                let xor_res = composer.and_gate(witness_a, witness_b, 10);
+                let xor_res =
+                    composer.and_gate(witness_a, witness_b, 10).unwrap();
                 // Check that the AND result is indeed what we are expecting.
                 composer.constrain_to_constant(
                     xor_res,
@@ -399,7 +404,8 @@ mod test {
             |composer: &mut StandardComposer<E, P>| {
                 let witness_a = composer.add_input(E::Fr::from(139u64));
                 let witness_b = composer.add_input(E::Fr::from(33u64));
-                let xor_res = composer.xor_gate(witness_a, witness_b, 10);
+                let xor_res =
+                    composer.xor_gate(witness_a, witness_b, 10).unwrap();
                 // Check that the XOR result is indeed what we are expecting.
                 composer.constrain_to_constant(
                     xor_res,
@@ -416,7 +422,8 @@ mod test {
             |composer: &mut StandardComposer<E, P>| {
                 let witness_a = composer.add_input(E::Fr::from(256u64));
                 let witness_b = composer.add_input(E::Fr::from(235u64));
-                let xor_res = composer.xor_gate(witness_a, witness_b, 2);
+                let xor_res =
+                    composer.xor_gate(witness_a, witness_b, 2).unwrap();
                 // Check that the XOR result is indeed what we are expecting.
                 composer.constrain_to_constant(
                     xor_res,
@@ -435,26 +442,23 @@ mod test {
         P: TEModelParameters<BaseField = E::Fr>,
     {
         // Should fail since the bit-num is odd.
-        let _ = gadget_tester(
-            |composer: &mut StandardComposer<E, P>| {
-                let witness_a = composer.add_input(E::Fr::from(500u64));
-                let witness_b = composer.add_input(E::Fr::from(499u64));
-                let xor_res = composer.xor_gate(witness_a, witness_b, 9);
-                // Check that the XOR result is indeed what we are expecting.
-                composer.constrain_to_constant(
-                    xor_res,
-                    E::Fr::from(7u64),
-                    None,
-                );
-            },
-            200,
-        );
+        let mut composer = StandardComposer::<E, P>::new();
+        let witness_a = composer.add_input(E::Fr::from(500u64));
+        let witness_b = composer.add_input(E::Fr::from(499u64));
+        assert!(matches!(
+            composer.xor_gate(witness_a, witness_b, 9),
+            Err(Error::OddNumberOfBits { num_bits: 9 })
+        ));
+        assert!(matches!(
+            composer.and_gate(witness_a, witness_b, 9),
+            Err(Error::OddNumberOfBits { num_bits: 9 })
+        ));
     }
 
     // Test for Bls12_381
     batch_test!(
-        [test_logic_xor_and_constraint],
-        [test_logical_gate_odd_bit_num]
+        [test_logic_xor_and_constraint, test_logical_gate_odd_bit_num],
+        []
         => (
             Bls12_381,
             ark_ed_on_bls12_381::EdwardsParameters
@@ -463,8 +467,8 @@ mod test {
 
     // Test for Bls12_377
     batch_test!(
-        [test_logic_xor_and_constraint],
-        [test_logical_gate_odd_bit_num]
+        [test_logic_xor_and_constraint, test_logical_gate_odd_bit_num],
+        []
         => (
             Bls12_377,
             ark_ed_on_bls12_377::EdwardsParameters
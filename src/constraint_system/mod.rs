@@ -10,18 +10,26 @@
 //! [`StandardComposer`], as well as the circuit tools and abstractions, used by
 //! the Composer to generate, build, preprocess circuits.
 
+mod analysis;
 mod arithmetic;
 mod boolean;
+mod export;
+mod field_var;
 mod logic;
+mod optimizer;
 mod range;
+mod stats;
 
 pub(crate) mod composer;
-pub(crate) mod helper;
 pub(crate) mod variable;
 
 pub mod ecc;
 
 pub(crate) use variable::WireData;
 
+pub use analysis::Diagnostic;
 pub use composer::StandardComposer;
+pub use export::GateFilter;
+pub use field_var::FieldVar;
+pub use stats::{CircuitStats, GateCounts, NamespaceStats};
 pub use variable::Variable;
@@ -0,0 +1,843 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Circuit Optimization Pass
+//!
+//! Gadgets append rows to the [`StandardComposer`] exactly as they request
+//! them, which leaves behind duplicated constants, chains of linear gates
+//! that would fit in a single width-4 row and gates whose outputs are never
+//! used. The pass implemented here rewrites the circuit description before
+//! it gets preprocessed.
+//!
+//! Only arithmetic rows are touched. Rows carrying a custom gate (range,
+//! logic, fixed and variable base group additions), the rows right after
+//! them (which custom gates read through their `_next` wires), the reserved
+//! rows added on construction and rows carrying public inputs are always
+//! kept in place.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use ark_ec::{PairingEngine, TEModelParameters};
+use hashbrown::HashMap;
+use num_traits::{One, Zero};
+
+/// Linear combination `terms + constant` described by an arithmetic gate.
+type LinearGate<F> = (Vec<(F, Variable)>, F);
+
+/// Number of rows added by [`StandardComposer::new`] which are never touched
+/// by the optimizer: the `zero_var` constraint and the two dummy gates.
+const RESERVED_GATES: usize = 3;
+
+impl<E, P> StandardComposer<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Runs the optimization pass over the circuit description, returning the
+    /// number of gates that got removed.
+    ///
+    /// The pass merges constants that got allocated more than once, folds
+    /// linear gates into the rows consuming their outputs and drops gates
+    /// whose outputs are unused and otherwise unconstrained, repeating until
+    /// no further change applies. The positions of the public inputs are
+    /// remapped accordingly, so [`StandardComposer::pi_positions`] must be
+    /// read after calling it.
+    ///
+    /// # Note
+    /// The pass only depends on the circuit description and not on the
+    /// witness values, but it must be called at the same point of the gadget
+    /// for both the [`Prover`](crate::proof_system::Prover) and the
+    /// [`Verifier`](crate::proof_system::Verifier) and always before
+    /// preprocessing.
+    pub fn optimize(&mut self) -> usize {
+        let size = self.n;
+        let mut removed = vec![false; self.n];
+        loop {
+            let mut changed = self.merge_duplicate_constants(&mut removed);
+            changed |= self.fold_linear_gates(&mut removed);
+            changed |= self.drop_unused_gates(&mut removed);
+            if !changed {
+                break;
+            }
+        }
+        self.remove_gates(&removed);
+        size - self.n
+    }
+
+    /// Replaces every [`Variable`] constrained to the same constant by a
+    /// single representative, removing the redundant constant gates.
+    fn merge_duplicate_constants(&mut self, removed: &mut [bool]) -> bool {
+        let mut constants = BTreeMap::new();
+        constants.insert(E::Fr::zero(), self.zero_var);
+        // Gate at which each representative got pinned to its constant.
+        let mut pinned_at = HashMap::new();
+        let mut substitutions = HashMap::new();
+        let mut changed = false;
+
+        for (i, gate_removed) in removed.iter_mut().enumerate() {
+            if *gate_removed || !self.is_arithmetic_gate(i) {
+                continue;
+            }
+            let (coeff, var, q_c) = match self.linear_terms(i) {
+                Some((terms, q_c)) if terms.len() == 1 => {
+                    (terms[0].0, terms[0].1, q_c)
+                }
+                _ => continue,
+            };
+            if substitutions.contains_key(&var) {
+                continue;
+            }
+            let value = -q_c / coeff;
+            match constants.get(&value).copied() {
+                Some(repr) if repr == var => {
+                    if pinned_at.get(&var) != Some(&i) {
+                        *gate_removed = true;
+                        changed = true;
+                    }
+                }
+                Some(_) | None if pinned_at.contains_key(&var) => {}
+                Some(repr) => {
+                    substitutions.insert(var, repr);
+                    *gate_removed = true;
+                    changed = true;
+                }
+                None => {
+                    constants.insert(value, var);
+                    pinned_at.insert(var, i);
+                }
+            }
+        }
+
+        if !substitutions.is_empty() {
+            for i in (0..self.n).filter(|i| !removed[*i]) {
+                for wire in [
+                    &mut self.w_l[i],
+                    &mut self.w_r[i],
+                    &mut self.w_o[i],
+                    &mut self.w_4[i],
+                ] {
+                    if let Some(repr) = substitutions.get(wire) {
+                        *wire = *repr;
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    /// Substitutes the output of a linear gate into the only other linear
+    /// gate using it, whenever the result still fits in a single row.
+    fn fold_linear_gates(&mut self, removed: &mut [bool]) -> bool {
+        let mut positions = self.variable_positions(removed);
+        let mut changed = false;
+
+        for i in 0..self.n {
+            if removed[i] || !self.is_arithmetic_gate(i) {
+                continue;
+            }
+            let (terms_i, q_c_i) = match self.linear_terms(i) {
+                Some(linear) => linear,
+                None => continue,
+            };
+            for (coeff, var) in terms_i.iter().copied() {
+                // `var` must only be wired into gates `i` and `j`.
+                let rows = match positions.get(&var) {
+                    Some(rows) => rows,
+                    None => continue,
+                };
+                let j = match rows.iter().find(|r| **r != i) {
+                    Some(j) => *j,
+                    None => continue,
+                };
+                if rows.iter().any(|r| *r != i && *r != j) {
+                    continue;
+                }
+                if removed[j] || !self.is_arithmetic_gate(j) {
+                    continue;
+                }
+                let (terms_j, q_c_j) = match self.linear_terms(j) {
+                    Some(linear) => linear,
+                    None => continue,
+                };
+
+                // Row `i` fixes `var = -(rest_i + q_c_i) / coeff`.
+                let scale = terms_j
+                    .iter()
+                    .find(|(_, v)| *v == var)
+                    .map(|(q, _)| -*q / coeff)
+                    .unwrap_or_else(E::Fr::zero);
+                let mut merged: Vec<(E::Fr, Variable)> = Vec::new();
+                for (q, v) in
+                    terms_j.iter().copied().filter(|(_, v)| *v != var).chain(
+                        terms_i
+                            .iter()
+                            .filter(|(_, v)| *v != var)
+                            .map(|(q, v)| (*q * scale, *v)),
+                    )
+                {
+                    match merged.iter_mut().find(|(_, m)| *m == v) {
+                        Some(term) => term.0 += q,
+                        None => merged.push((q, v)),
+                    }
+                }
+                merged.retain(|(q, _)| !q.is_zero());
+                if merged.len() > 4 {
+                    continue;
+                }
+
+                for row in [i, j] {
+                    for wire in self.gate_wires(row) {
+                        if let Some(rows) = positions.get_mut(&wire) {
+                            if let Some(k) = rows.iter().position(|r| *r == row)
+                            {
+                                rows.swap_remove(k);
+                            }
+                        }
+                    }
+                }
+                self.set_linear_gate(j, &merged, q_c_j + q_c_i * scale);
+                for wire in self.gate_wires(j) {
+                    if wire != self.zero_var {
+                        positions.entry(wire).or_default().push(j);
+                    }
+                }
+                removed[i] = true;
+                changed = true;
+                break;
+            }
+        }
+        changed
+    }
+
+    /// Removes the arithmetic gates that can always be satisfied by choosing
+    /// the value of a [`Variable`] not used anywhere else, as well as the
+    /// gates that are trivially satisfied.
+    fn drop_unused_gates(&mut self, removed: &mut [bool]) -> bool {
+        let mut positions = self.variable_positions(removed);
+        let mut changed = false;
+
+        for i in 0..self.n {
+            if removed[i] || !self.is_free_gate(i) {
+                continue;
+            }
+            let droppable = if self.q_arith[i].is_zero() {
+                true
+            } else {
+                let only_here = |var: &Variable| match positions.get(var) {
+                    Some(rows) => rows.iter().all(|r| *r == i),
+                    None => false,
+                };
+                match self.linear_terms(i) {
+                    Some((terms, q_c)) => {
+                        (terms.is_empty() && q_c.is_zero())
+                            || terms.iter().any(|(_, v)| only_here(v))
+                    }
+                    None => {
+                        (!self.q_o[i].is_zero()
+                            && only_here(&self.w_o[i])
+                            && positions[&self.w_o[i]].len() == 1)
+                            || (!self.q_4[i].is_zero()
+                                && only_here(&self.w_4[i])
+                                && positions[&self.w_4[i]].len() == 1)
+                    }
+                }
+            };
+            if droppable {
+                for wire in self.gate_wires(i) {
+                    if let Some(rows) = positions.get_mut(&wire) {
+                        rows.retain(|r| *r != i);
+                    }
+                }
+                removed[i] = true;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Compacts the circuit description dropping the `removed` gates and
+    /// remapping the public input positions, the namespaces, the named public
+    /// inputs and the permutation.
+    fn remove_gates(&mut self, removed: &[bool]) {
+        fn retain<T: Copy>(values: &mut Vec<T>, removed: &[bool]) {
+            let mut i = 0;
+            values.retain(|_| {
+                i += 1;
+                !removed[i - 1]
+            });
+        }
+
+        if !removed.iter().any(|r| *r) {
+            return;
+        }
+
+        let mut new_index = vec![0; self.n + 1];
+        let mut next = 0;
+        for i in 0..self.n {
+            new_index[i] = next;
+            if !removed[i] {
+                next += 1;
+            }
+        }
+        new_index[self.n] = next;
+        self.public_inputs_sparse_store = self
+            .public_inputs_sparse_store
+            .iter()
+            .map(|(pos, value)| (new_index[*pos], *value))
+            .collect();
+        for (_, gates) in self.namespaces.iter_mut() {
+            *gates = new_index[gates.start]..new_index[gates.end];
+        }
+        for pi in self.named_public_inputs.iter_mut() {
+            for pos in pi.positions.iter_mut() {
+                *pos = new_index[*pos];
+            }
+        }
+
+        retain(&mut self.q_m, removed);
+        retain(&mut self.q_l, removed);
+        retain(&mut self.q_r, removed);
+        retain(&mut self.q_o, removed);
+        retain(&mut self.q_4, removed);
+        retain(&mut self.q_c, removed);
+        retain(&mut self.q_arith, removed);
+        retain(&mut self.q_range, removed);
+        retain(&mut self.q_logic, removed);
+        retain(&mut self.q_fixed_group_add, removed);
+        retain(&mut self.q_variable_group_add, removed);
+        retain(&mut self.q_variable_group_double, removed);
+        retain(&mut self.w_l, removed);
+        retain(&mut self.w_r, removed);
+        retain(&mut self.w_o, removed);
+        retain(&mut self.w_4, removed);
+        self.n = next;
+
+        self.perm
+            .variable_map
+            .values_mut()
+            .for_each(|wires| wires.clear());
+        for i in 0..self.n {
+            self.perm.add_variables_to_map(
+                self.w_l[i],
+                self.w_r[i],
+                self.w_o[i],
+                self.w_4[i],
+                i,
+            );
+        }
+    }
+
+    /// Returns `true` if the gate at `index` carries a custom gate selector.
+    fn has_custom_selector(&self, index: usize) -> bool {
+        !(self.q_range[index].is_zero()
+            && self.q_logic[index].is_zero()
+            && self.q_fixed_group_add[index].is_zero()
+            && self.q_variable_group_add[index].is_zero()
+            && self.q_variable_group_double[index].is_zero())
+    }
+
+    /// Returns `true` if the gate at `index` can be rewritten or removed by
+    /// the optimizer.
+    fn is_free_gate(&self, index: usize) -> bool {
+        index >= RESERVED_GATES
+            && !self.has_custom_selector(index)
+            && !self.has_custom_selector(index - 1)
+            && !self.public_inputs_sparse_store.contains_key(&index)
+    }
+
+    /// Returns `true` if the gate at `index` is a free arithmetic gate.
+    fn is_arithmetic_gate(&self, index: usize) -> bool {
+        self.is_free_gate(index) && self.q_arith[index].is_one()
+    }
+
+    /// Returns the terms of the gate at `index` as a linear combination plus
+    /// its constant, or `None` if the gate has a multiplication term.
+    ///
+    /// Repeated [`Variable`]s are merged and terms with a zero coefficient or
+    /// over the `zero_var` are skipped.
+    fn linear_terms(&self, index: usize) -> Option<LinearGate<E::Fr>> {
+        if !self.q_m[index].is_zero() {
+            return None;
+        }
+        let mut terms: Vec<(E::Fr, Variable)> = Vec::with_capacity(4);
+        for (q, var) in [
+            (self.q_l[index], self.w_l[index]),
+            (self.q_r[index], self.w_r[index]),
+            (self.q_o[index], self.w_o[index]),
+            (self.q_4[index], self.w_4[index]),
+        ] {
+            if var == self.zero_var {
+                continue;
+            }
+            match terms.iter_mut().find(|(_, v)| *v == var) {
+                Some(term) => term.0 += q,
+                None => terms.push((q, var)),
+            }
+        }
+        terms.retain(|(q, _)| !q.is_zero());
+        Some((terms, self.q_c[index]))
+    }
+
+    /// Overwrites the gate at `index` with the linear combination
+    /// `terms + q_c = 0`.
+    fn set_linear_gate(
+        &mut self,
+        index: usize,
+        terms: &[(E::Fr, Variable)],
+        q_c: E::Fr,
+    ) {
+        let zero = (E::Fr::zero(), self.zero_var);
+        let term = |i: usize| terms.get(i).copied().unwrap_or(zero);
+        (self.q_l[index], self.w_l[index]) = term(0);
+        (self.q_r[index], self.w_r[index]) = term(1);
+        (self.q_o[index], self.w_o[index]) = term(2);
+        (self.q_4[index], self.w_4[index]) = term(3);
+        self.q_m[index] = E::Fr::zero();
+        self.q_c[index] = q_c;
+    }
+
+    /// Returns the [`Variable`]s wired into the gate at `index`.
+    fn gate_wires(&self, index: usize) -> [Variable; 4] {
+        [
+            self.w_l[index],
+            self.w_r[index],
+            self.w_o[index],
+            self.w_4[index],
+        ]
+    }
+
+    /// Maps every [`Variable`] but the `zero_var` to the gates it is wired
+    /// into, repeating the gate once per wire.
+    fn variable_positions(
+        &self,
+        removed: &[bool],
+    ) -> HashMap<Variable, Vec<usize>> {
+        let mut positions: HashMap<Variable, Vec<usize>> = HashMap::new();
+        for i in (0..self.n).filter(|i| !removed[*i]) {
+            for wire in self.gate_wires(i) {
+                if wire != self.zero_var {
+                    positions.entry(wire).or_default().push(i);
+                }
+            }
+        }
+        positions
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::circuit::{verify_proof, Circuit, FeIntoPubInput};
+    use crate::error::Error;
+    use crate::setup::PublicParameters;
+    use crate::testing::*;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::twisted_edwards_extended::GroupAffine;
+    use ark_ff::Field;
+    use proptest::prelude::*;
+    use rand_core::OsRng;
+
+    fn test_merge_duplicate_constants<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let a = composer.add_input(E::Fr::from(7u64));
+                let five_1 =
+                    composer.add_witness_to_circuit_description(5u64.into());
+                let five_2 =
+                    composer.add_witness_to_circuit_description(5u64.into());
+                let zero =
+                    composer.add_witness_to_circuit_description(E::Fr::zero());
+                let prod = composer.mul(
+                    E::Fr::one(),
+                    five_1,
+                    five_2,
+                    E::Fr::zero(),
+                    None,
+                );
+                let prod =
+                    composer.mul(E::Fr::one(), prod, a, E::Fr::zero(), None);
+                let prod =
+                    composer.mul(E::Fr::one(), prod, zero, E::Fr::zero(), None);
+                composer.assert_equal(prod, composer.zero_var());
+
+                // The second `5`, the `0` and the final product (which is
+                // pinned to zero by the last gate) all get merged away.
+                let size = composer.circuit_size();
+                assert_eq!(composer.optimize(), 3);
+                assert_eq!(composer.circuit_size(), size - 3);
+                assert!((0..composer.circuit_size()).all(|i| {
+                    !composer.gate_wires(i).contains(&five_2)
+                        && !composer.gate_wires(i).contains(&zero)
+                }));
+            },
+            32,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_fold_linear_gates<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let a = composer.add_input(E::Fr::from(3u64));
+                let b = composer.add_input(E::Fr::from(4u64));
+                let c = composer.add_input(E::Fr::from(5u64));
+                let ab = composer.mul(E::Fr::one(), a, b, E::Fr::zero(), None);
+                let bc = composer.mul(E::Fr::one(), b, c, E::Fr::zero(), None);
+
+                // 12 + 2 * 20 + 3 + 1 = 56, computed through a chain of adds.
+                let sum = composer.add(
+                    (E::Fr::one(), ab),
+                    (E::Fr::from(2u64), bc),
+                    E::Fr::zero(),
+                    None,
+                );
+                let sum = composer.add(
+                    (E::Fr::one(), sum),
+                    (E::Fr::one(), a),
+                    E::Fr::one(),
+                    None,
+                );
+                composer.constrain_to_constant(sum, E::Fr::from(56u64), None);
+
+                // The two additions fold into the constant constraint.
+                assert_eq!(composer.optimize(), 2);
+                assert_eq!(composer.circuit_size(), 6);
+            },
+            32,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_drop_unused_gates<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let a = composer.add_input(E::Fr::from(3u64));
+                let b = composer.add_input(E::Fr::from(4u64));
+                composer.constrain_to_constant(a, E::Fr::from(3u64), None);
+                composer.constrain_to_constant(b, E::Fr::from(4u64), None);
+                let ab = composer.mul(E::Fr::one(), a, b, E::Fr::zero(), None);
+                composer.add(
+                    (E::Fr::one(), ab),
+                    (E::Fr::one(), a),
+                    E::Fr::zero(),
+                    None,
+                );
+
+                // Once the unused outputs are gone, `a` and `b` are unused too.
+                assert_eq!(composer.optimize(), 4);
+                assert_eq!(composer.circuit_size(), RESERVED_GATES);
+            },
+            32,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_optimize_keeps_custom_gates<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let a = composer.add_input(E::Fr::from(500u64));
+                let b = composer.add_input(E::Fr::from(357u64));
+                let one =
+                    composer.add_witness_to_circuit_description(E::Fr::one());
+                composer.range_gate(a, 10).unwrap();
+                let xor = composer.xor_gate(a, b, 10).unwrap();
+                composer.constrain_to_constant(
+                    xor,
+                    E::Fr::from(500u64 ^ 357),
+                    None,
+                );
+
+                let scalar = composer.add(
+                    (E::Fr::one(), a),
+                    (E::Fr::one(), one),
+                    E::Fr::zero(),
+                    None,
+                );
+                let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+                let generator = GroupAffine::new(x, y);
+                let point = composer.fixed_base_scalar_mul(scalar, generator);
+                let double = composer.point_addition_gate(point, point);
+                let one =
+                    composer.add_witness_to_circuit_description(E::Fr::one());
+                let scaled = composer.mul(
+                    E::Fr::one(),
+                    *double.x(),
+                    one,
+                    E::Fr::zero(),
+                    None,
+                );
+                composer.assert_equal(scaled, *double.x());
+
+                assert!(composer.optimize() > 0);
+            },
+            2048,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_optimize_unsatisfied_circuit<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let a = composer.add_input(E::Fr::from(3u64));
+                let b = composer.add_input(E::Fr::from(4u64));
+                composer.constrain_to_constant(a, E::Fr::from(3u64), None);
+                composer.constrain_to_constant(b, E::Fr::from(4u64), None);
+                let c = composer.add(
+                    (E::Fr::one(), a),
+                    (E::Fr::one(), b),
+                    E::Fr::zero(),
+                    None,
+                );
+                composer.constrain_to_constant(c, E::Fr::from(8u64), None);
+                composer.optimize();
+            },
+            32,
+        );
+        assert!(res.is_err());
+    }
+
+    /// Circuit proving knowledge of `a, b` such that `a + b + 2 = c` and
+    /// `a * b = d`, with `c` and `d` being public inputs placed after gates
+    /// that the optimizer removes.
+    #[derive(derivative::Derivative)]
+    #[derivative(Default(bound = ""))]
+    struct PublicInputCircuit<E, P>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        a: E::Fr,
+        b: E::Fr,
+        c: E::Fr,
+        d: E::Fr,
+        __: core::marker::PhantomData<P>,
+    }
+
+    impl<E, P> Circuit<E, P> for PublicInputCircuit<E, P>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        const CIRCUIT_ID: [u8; 32] = [0xaa; 32];
+
+        fn gadget(
+            &mut self,
+            composer: &mut StandardComposer<E, P>,
+        ) -> Result<(), Error> {
+            let a = composer.add_input(self.a);
+            let b = composer.add_input(self.b);
+            let two_1 =
+                composer.add_witness_to_circuit_description(2u64.into());
+            let two_2 =
+                composer.add_witness_to_circuit_description(2u64.into());
+            let a_two = composer.add(
+                (E::Fr::one(), a),
+                (E::Fr::one(), two_1),
+                E::Fr::zero(),
+                None,
+            );
+            let sum = composer.add(
+                (E::Fr::one(), a_two),
+                (E::Fr::one(), b),
+                E::Fr::zero(),
+                None,
+            );
+            composer.constrain_to_constant(sum, E::Fr::zero(), Some(-self.c));
+            let prod = composer.mul(E::Fr::one(), a, b, E::Fr::zero(), None);
+            let prod =
+                composer.mul(E::Fr::one(), prod, two_2, E::Fr::zero(), None);
+            composer.constrain_to_constant(
+                prod,
+                E::Fr::zero(),
+                Some(-self.d.double()),
+            );
+            composer.optimize();
+            Ok(())
+        }
+
+        fn padded_circuit_size(&self) -> usize {
+            1 << 4
+        }
+    }
+
+    fn test_optimize_preserves_pi_positions<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let pp = PublicParameters::<E>::setup(1 << 5, &mut OsRng).unwrap();
+
+        let (pk, vd) =
+            PublicInputCircuit::<E, P>::default().compile(&pp).unwrap();
+        // Without the optimizer the public inputs would sit at 7 and 10.
+        assert_eq!(vd.pi_pos, vec![5, 8]);
+
+        let mut circuit = PublicInputCircuit::<E, P> {
+            a: E::Fr::from(3u64),
+            b: E::Fr::from(4u64),
+            c: E::Fr::from(9u64),
+            d: E::Fr::from(12u64),
+            __: core::marker::PhantomData,
+        };
+        let proof = circuit.gen_proof(&pp, pk, b"Optimizer").unwrap();
+
+        let public_inputs =
+            [E::Fr::from(9u64).into_pi(), E::Fr::from(24u64).into_pi()];
+        assert!(verify_proof(
+            &pp,
+            vd.key.clone(),
+            &proof,
+            &public_inputs,
+            &vd.pi_pos,
+            b"Optimizer",
+        )
+        .is_ok());
+
+        let wrong_inputs =
+            [E::Fr::from(10u64).into_pi(), E::Fr::from(24u64).into_pi()];
+        assert!(verify_proof(
+            &pp,
+            vd.key,
+            &proof,
+            &wrong_inputs,
+            &vd.pi_pos,
+            b"Optimizer",
+        )
+        .is_err());
+    }
+
+    /// Builds a random arithmetic circuit out of `ops`, checking that the
+    /// proof still verifies once the circuit is optimized.
+    fn check_random_circuit<E, P>(ops: &[(u8, usize, usize, u64)])
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let mut vars = vec![
+                    composer.add_input(E::Fr::from(3u64)),
+                    composer.add_input(E::Fr::from(5u64)),
+                ];
+                for (kind, i, j, k) in ops.iter().copied() {
+                    let a = vars[i % vars.len()];
+                    let b = vars[j % vars.len()];
+                    let k = E::Fr::from(k);
+                    let var = match kind % 5 {
+                        0 => composer.add_witness_to_circuit_description(k),
+                        1 => composer.add((E::Fr::one(), a), (k, b), k, None),
+                        2 => composer.mul(k, a, b, E::Fr::zero(), None),
+                        3 => composer.big_add(
+                            (k, a),
+                            (E::Fr::one(), b),
+                            Some((E::Fr::one(), a)),
+                            E::Fr::zero(),
+                            None,
+                        ),
+                        _ => {
+                            let value = composer.variables[&a];
+                            composer.constrain_to_constant(
+                                a,
+                                E::Fr::zero(),
+                                Some(-value),
+                            );
+                            continue;
+                        }
+                    };
+                    vars.push(var);
+                }
+                let last = *vars.last().unwrap();
+                let value = composer.variables[&last];
+                composer.constrain_to_constant(last, value, None);
+
+                let size = composer.circuit_size();
+                let pi = composer.public_inputs_sparse_store.len();
+                composer.optimize();
+                assert!(composer.circuit_size() <= size);
+                assert_eq!(composer.public_inputs_sparse_store.len(), pi);
+            },
+            256,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        #[test]
+        #[allow(non_snake_case)]
+        fn test_random_circuit_on_Bls12_381(
+            ops in prop::collection::vec((0u8..5, 0usize..64, 0usize..64, 0u64..4), 1..24)
+        ) {
+            check_random_circuit::<Bls12_381, ark_ed_on_bls12_381::EdwardsParameters>(&ops);
+        }
+
+        #[test]
+        #[allow(non_snake_case)]
+        fn test_random_circuit_on_Bls12_377(
+            ops in prop::collection::vec((0u8..5, 0usize..64, 0usize..64, 0u64..4), 1..24)
+        ) {
+            check_random_circuit::<Bls12_377, ark_ed_on_bls12_377::EdwardsParameters>(&ops);
+        }
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [
+            test_merge_duplicate_constants,
+            test_fold_linear_gates,
+            test_drop_unused_gates,
+            test_optimize_keeps_custom_gates,
+            test_optimize_unsatisfied_circuit,
+            test_optimize_preserves_pi_positions
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [
+            test_merge_duplicate_constants,
+            test_fold_linear_gates,
+            test_drop_unused_gates,
+            test_optimize_keeps_custom_gates,
+            test_optimize_unsatisfied_circuit,
+            test_optimize_preserves_pi_positions
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -9,6 +9,8 @@
 //! Range Gate
 
 use crate::constraint_system::{StandardComposer, Variable, WireData};
+use crate::error::Error;
+use alloc::vec::Vec;
 use ark_ec::{PairingEngine, TEModelParameters};
 use ark_ff::{BigInteger, PrimeField};
 use num_traits::{One, Zero};
@@ -24,10 +26,13 @@ where
     /// This function adds `num_bits/4` gates to the circuit description in
     /// order to add the range constraint.
     ///
-    ///# Panics
-    /// This function will panic if the num_bits specified is not even, ie.
-    /// `num_bits % 2 != 0`.
-    pub fn range_gate(&mut self, witness: Variable, num_bits: usize) {
+    /// Fails with [`Error::OddNumberOfBits`] if the num_bits specified is not
+    /// even, ie. `num_bits % 2 != 0`.
+    pub fn range_gate(
+        &mut self,
+        witness: Variable,
+        num_bits: usize,
+    ) -> Result<(), Error> {
         // Adds `variable` into the appropriate witness position
         // based on the accumulator number a_i
         let add_wire = |composer: &mut StandardComposer<E, P>,
@@ -63,7 +68,9 @@ where
         //
         // Number of bits should be even, this means that user must pad the
         // number of bits external.
-        assert!(num_bits % 2 == 0);
+        if num_bits % 2 != 0 {
+            return Err(Error::OddNumberOfBits { num_bits });
+        }
 
         // Convert witness to bit representation and reverse
         let bits = self.variables[&witness].into_repr().to_bits_le();
@@ -175,6 +182,7 @@ where
         self.q_4.extend(zeros.iter());
         self.q_fixed_group_add.extend(zeros.iter());
         self.q_variable_group_add.extend(zeros.iter());
+        self.q_variable_group_double.extend(zeros.iter());
         self.q_range.extend(ones.iter());
         self.q_logic.extend(zeros.iter());
         self.n += used_gates;
@@ -194,13 +202,14 @@ where
         let last_accumulator = accumulators.len() - 1;
         self.assert_equal(accumulators[last_accumulator], witness);
         accumulators[last_accumulator] = witness;
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{batch_test, constraint_system::helper::*};
+    use crate::{batch_test, testing::*};
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
 
@@ -214,7 +223,7 @@ mod test {
             |composer: &mut StandardComposer<E, P>| {
                 let witness = composer
                     .add_input(E::Fr::from((u32::max_value() as u64) + 1));
-                composer.range_gate(witness, 32);
+                composer.range_gate(witness, 32).unwrap();
             },
             200,
         );
@@ -224,7 +233,7 @@ mod test {
         let res = gadget_tester(
             |composer: &mut StandardComposer<E, P>| {
                 let witness = composer.add_input(E::Fr::from(u64::max_value()));
-                composer.range_gate(witness, 32);
+                composer.range_gate(witness, 32).unwrap();
             },
             200,
         );
@@ -234,7 +243,7 @@ mod test {
         let res = gadget_tester(
             |composer: &mut StandardComposer<E, P>| {
                 let witness = composer.add_input(E::Fr::from(2u64.pow(34) - 1));
-                composer.range_gate(witness, 34);
+                composer.range_gate(witness, 34).unwrap();
             },
             200,
         );
@@ -247,20 +256,18 @@ mod test {
         P: TEModelParameters<BaseField = E::Fr>,
     {
         // Should fail as the number we we need a even number of bits
-        let _ok = gadget_tester(
-            |composer: &mut StandardComposer<E, P>| {
-                let witness =
-                    composer.add_input(E::Fr::from(u32::max_value() as u64));
-                composer.range_gate(witness, 33);
-            },
-            200,
-        );
+        let mut composer = StandardComposer::<E, P>::new();
+        let witness = composer.add_input(E::Fr::from(u32::max_value() as u64));
+        assert!(matches!(
+            composer.range_gate(witness, 33),
+            Err(Error::OddNumberOfBits { num_bits: 33 })
+        ));
     }
 
     // Test on Bls12-381
     batch_test!(
-        [test_range_constraint],
-        [test_odd_bit_range]
+        [test_range_constraint, test_odd_bit_range],
+        []
         => (
             Bls12_381,
             ark_ed_on_bls12_381::EdwardsParameters
@@ -269,8 +276,8 @@ mod test {
 
     // Test on Bls12-377
     batch_test!(
-        [test_range_constraint],
-        [test_odd_bit_range]
+        [test_range_constraint, test_odd_bit_range],
+        []
         => (
             Bls12_377,
             ark_ed_on_bls12_377::EdwardsParameters
@@ -0,0 +1,378 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Namespaces and Circuit Statistics
+//!
+//! Gadgets can be wrapped in named regions through
+//! [`StandardComposer::namespace`] so that [`StandardComposer::stats`] can
+//! report where the gates of a circuit go.
+
+use crate::constraint_system::StandardComposer;
+use alloc::string::String;
+use alloc::vec::Vec;
+use ark_ec::{PairingEngine, TEModelParameters};
+use core::cmp::Reverse;
+use core::fmt::Write;
+use core::ops::Range;
+use num_traits::Zero;
+
+/// Number of gates of a circuit region, broken down by the selector they
+/// enable.
+///
+/// Gates without any selector enabled only hold the `_next` wires of the
+/// custom gate before them and are accounted to it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GateCounts {
+    /// Arithmetic gates.
+    pub arithmetic: usize,
+    /// Range gates.
+    pub range: usize,
+    /// Logic gates.
+    pub logic: usize,
+    /// Fixed-base scalar multiplication gates.
+    pub fixed_base: usize,
+    /// Variable-base curve addition gates.
+    pub variable_base: usize,
+}
+
+impl GateCounts {
+    /// Returns the total number of gates.
+    pub fn total(&self) -> usize {
+        self.arithmetic
+            + self.range
+            + self.logic
+            + self.fixed_base
+            + self.variable_base
+    }
+
+    /// Counts the gates of `kinds` by the selector they enable.
+    fn from_kinds(kinds: &[GateKind]) -> Self {
+        let mut counts = Self::default();
+        for kind in kinds {
+            match kind {
+                GateKind::Arithmetic => counts.arithmetic += 1,
+                GateKind::Range => counts.range += 1,
+                GateKind::Logic => counts.logic += 1,
+                GateKind::FixedBase => counts.fixed_base += 1,
+                GateKind::VariableBase => counts.variable_base += 1,
+            }
+        }
+        counts
+    }
+
+    /// Adds the counts of `other` to `self`.
+    fn accumulate(&mut self, other: &Self) {
+        self.arithmetic += other.arithmetic;
+        self.range += other.range;
+        self.logic += other.logic;
+        self.fixed_base += other.fixed_base;
+        self.variable_base += other.variable_base;
+    }
+
+    /// Writes `self` as a JSON object.
+    fn write_json(&self, out: &mut String) {
+        write!(
+            out,
+            "{{\"arithmetic\":{},\"range\":{},\"logic\":{},\"fixed_base\":{},\
+             \"variable_base\":{},\"total\":{}}}",
+            self.arithmetic,
+            self.range,
+            self.logic,
+            self.fixed_base,
+            self.variable_base,
+            self.total()
+        )
+        .expect("writing to a String can't fail");
+    }
+}
+
+/// Gates added inside of a namespace.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NamespaceStats {
+    /// Full `/`-separated path of the namespace.
+    pub name: String,
+    /// Gates added inside of the namespace, including the ones of the
+    /// namespaces nested in it.
+    pub gates: GateCounts,
+}
+
+/// Report of the size of a circuit, as returned by
+/// [`StandardComposer::stats`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircuitStats {
+    /// Gates of the whole circuit.
+    pub gates: GateCounts,
+    /// Gates per namespace, in the order they were first opened.
+    pub namespaces: Vec<NamespaceStats>,
+    /// Number of [`Variable`](crate::constraint_system::Variable)s allocated.
+    pub variables: usize,
+    /// Number of copy constraints enforced by the permutation argument.
+    pub copy_constraints: usize,
+    /// Number of public inputs.
+    pub public_inputs: usize,
+}
+
+impl CircuitStats {
+    /// Serializes the report as a JSON object, suitable for tracking the
+    /// circuit size across changes.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"gates\":");
+        self.gates.write_json(&mut out);
+        write!(
+            out,
+            ",\"variables\":{},\"copy_constraints\":{},\"public_inputs\":{},\
+             \"namespaces\":[",
+            self.variables, self.copy_constraints, self.public_inputs
+        )
+        .expect("writing to a String can't fail");
+        for (i, namespace) in self.namespaces.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"name\":");
+            write_json_string(&mut out, &namespace.name);
+            out.push_str(",\"gates\":");
+            namespace.gates.write_json(&mut out);
+            out.push('}');
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+impl<E, P> StandardComposer<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Runs `f` inside of the namespace `name`, so that the gates it adds
+    /// get reported under it by [`StandardComposer::stats`].
+    ///
+    /// Namespaces can be nested, in which case they are reported by their
+    /// full path, as in `merkle_path/hash`. Opening the same path several
+    /// times accumulates its gates.
+    pub fn namespace<F, R>(&mut self, name: &str, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        self.namespace_stack.push(name.into());
+        let path = self.namespace_stack.join("/");
+        let start = self.n;
+        let res = f(self);
+        self.namespaces.push((path, start..self.n));
+        self.namespace_stack.pop();
+        res
+    }
+
+    /// Returns a report with the gates per namespace and selector type,
+    /// together with the variable, copy constraint and public input counts.
+    pub fn stats(&self) -> CircuitStats {
+        let kinds = self.gate_kinds();
+        let mut namespaces: Vec<NamespaceStats> = Vec::new();
+        // Namespaces are recorded when closed, so sort them by when they
+        // were opened to list parents before their children.
+        let mut regions: Vec<&(String, Range<usize>)> =
+            self.namespaces.iter().collect();
+        regions.sort_by_key(|(_, gates)| (gates.start, Reverse(gates.end)));
+        for (name, gates) in regions {
+            let counts = GateCounts::from_kinds(&kinds[gates.clone()]);
+            match namespaces.iter_mut().find(|ns| ns.name == *name) {
+                Some(ns) => ns.gates.accumulate(&counts),
+                None => namespaces.push(NamespaceStats {
+                    name: name.clone(),
+                    gates: counts,
+                }),
+            }
+        }
+
+        CircuitStats {
+            gates: GateCounts::from_kinds(&kinds),
+            namespaces,
+            variables: self.perm.variable_map.len(),
+            copy_constraints: self
+                .perm
+                .variable_map
+                .values()
+                .map(|wires| wires.len().saturating_sub(1))
+                .sum(),
+            public_inputs: self.public_inputs_sparse_store.len(),
+        }
+    }
+
+    /// Returns the kind of every gate in a single pass, accounting the gates
+    /// without any selector enabled to the gate before them.
+    fn gate_kinds(&self) -> Vec<GateKind> {
+        let mut kind = GateKind::Arithmetic;
+        (0..self.n)
+            .map(|i| {
+                if !self.q_arith[i].is_zero() {
+                    kind = GateKind::Arithmetic;
+                } else if !self.q_range[i].is_zero() {
+                    kind = GateKind::Range;
+                } else if !self.q_logic[i].is_zero() {
+                    kind = GateKind::Logic;
+                } else if !self.q_fixed_group_add[i].is_zero() {
+                    kind = GateKind::FixedBase;
+                } else if !self.q_variable_group_add[i].is_zero()
+                    || !self.q_variable_group_double[i].is_zero()
+                {
+                    kind = GateKind::VariableBase;
+                }
+                kind
+            })
+            .collect()
+    }
+}
+
+/// Writes `s` as a JSON string.
+pub(super) fn write_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    s.chars().for_each(|c| match c {
+        '"' => out.push_str("\\\""),
+        '\\' => out.push_str("\\\\"),
+        c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)
+            .expect("writing to a String can't fail"),
+        c => out.push(c),
+    });
+    out.push('"');
+}
+
+/// Gate types reported by [`GateCounts`].
+#[derive(Clone, Copy)]
+enum GateKind {
+    Arithmetic,
+    Range,
+    Logic,
+    FixedBase,
+    VariableBase,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::testing::*;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::twisted_edwards_extended::GroupAffine;
+    use num_traits::One;
+
+    fn test_namespace_stats<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let res = gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                let a = composer.add_input(E::Fr::from(5u64));
+                let b = composer.add_input(E::Fr::from(6u64));
+                composer.namespace("checks", |composer| {
+                    composer.range_gate(a, 8).unwrap();
+                    composer.namespace("xor", |composer| {
+                        composer.xor_gate(a, b, 4).unwrap();
+                    });
+                    composer.mul(E::Fr::one(), a, b, E::Fr::zero(), None);
+                });
+                composer.namespace("ecc", |composer| {
+                    let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+                    let generator = GroupAffine::new(x, y);
+                    let point = composer.fixed_base_scalar_mul(a, generator);
+                    composer.point_addition_gate(point, point)
+                });
+                composer.constrain_to_constant(
+                    b,
+                    E::Fr::zero(),
+                    Some(-E::Fr::from(6u64)),
+                );
+
+                let stats = composer.stats();
+                assert_eq!(stats.gates.total(), composer.circuit_size());
+                assert_eq!(stats.public_inputs, 1);
+                assert_eq!(stats.variables, composer.variables.len());
+                assert!(stats.copy_constraints > 0);
+
+                let names: Vec<_> = stats
+                    .namespaces
+                    .iter()
+                    .map(|ns| ns.name.as_str())
+                    .collect();
+                assert_eq!(names, ["checks", "checks/xor", "ecc"]);
+
+                // 8 bits: 1 range gate plus the gate holding its last quad and
+                // the final `assert_equal`.
+                let checks = stats.namespaces[0].gates;
+                assert_eq!(checks.range, 2);
+                assert_eq!(checks.logic, 3);
+                assert_eq!(checks.arithmetic, 2);
+                assert_eq!(stats.namespaces[1].gates.logic, 3);
+                assert_eq!(stats.namespaces[1].gates.total(), 3);
+
+                let ecc = stats.namespaces[2].gates;
+                assert_eq!(ecc.variable_base, 2);
+                assert!(ecc.fixed_base > 0);
+                assert_eq!(ecc.range + ecc.logic, 0);
+            },
+            2048,
+        );
+        assert!(res.is_ok(), "{:?}", res.err().unwrap());
+    }
+
+    fn test_repeated_namespace<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut composer = StandardComposer::<E, P>::new();
+        let mut acc = composer.add_input(E::Fr::one());
+        for _ in 0..3 {
+            acc = composer.namespace("double", |composer| {
+                composer.add(
+                    (E::Fr::one(), acc),
+                    (E::Fr::one(), acc),
+                    E::Fr::zero(),
+                    None,
+                )
+            });
+        }
+        composer.namespace("empty", |_| {});
+
+        let stats = composer.stats();
+        assert_eq!(stats.namespaces.len(), 2);
+        assert_eq!(stats.namespaces[0].gates.arithmetic, 3);
+        assert_eq!(stats.namespaces[1].gates.total(), 0);
+        assert_eq!(
+            stats.to_json(),
+            "{\"gates\":{\"arithmetic\":6,\"range\":0,\"logic\":0,\
+             \"fixed_base\":0,\"variable_base\":0,\"total\":6},\
+             \"variables\":9,\"copy_constraints\":15,\"public_inputs\":0,\
+             \"namespaces\":[{\"name\":\"double\",\"gates\":{\
+             \"arithmetic\":3,\"range\":0,\"logic\":0,\"fixed_base\":0,\
+             \"variable_base\":0,\"total\":3}},{\"name\":\"empty\",\
+             \"gates\":{\"arithmetic\":0,\"range\":0,\"logic\":0,\
+             \"fixed_base\":0,\"variable_base\":0,\"total\":0}}]}"
+        );
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [test_namespace_stats, test_repeated_namespace],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [test_namespace_stats, test_repeated_namespace],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -8,6 +8,9 @@
 
 //! A collection of all possible errors encountered in PLONK.
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
 /// Defines all possible errors that can be encountered in PLONK.
 #[derive(Debug)]
 pub enum Error {
@@ -30,11 +33,70 @@ pub enum Error {
     /// This error occurs when we want to verify a Proof but the pi_constructor
     /// attribute is uninitialized.
     UninitializedPIGenerator,
+    /// This error occurs when we want to verify a Proof but the verifier key
+    /// is uninitialized.
+    UninitializedVerifierKey,
+    /// This error occurs when the permutation challenges `beta` and `gamma`
+    /// drawn from the transcript are equal.
+    EqualChallenges,
+    /// This error occurs when a public input is placed at a gate outside of
+    /// the circuit.
+    PublicInputOutOfRange {
+        /// Gate position of the public input.
+        position: usize,
+        /// Size of the circuit.
+        circuit_size: usize,
+    },
     /// PublicInput serialization error
     InvalidPublicInputBytes,
+    /// This error occurs when a gate is given an odd number of bits to
+    /// decompose into quads.
+    OddNumberOfBits {
+        /// Number of bits requested.
+        num_bits: usize,
+    },
+    /// This error occurs when no circuit is registered under a name.
+    UnknownCircuit {
+        /// Name of the circuit.
+        name: String,
+    },
     /// This error occurs when the Prover structure already contains a
     /// preprocessed circuit inside, but you call preprocess again.
     CircuitAlreadyPreprocessed,
+    /// This error occurs when stored keys were derived for a different
+    /// circuit, setup or library version than the requested ones.
+    StaleKey {
+        /// Header field that does not match.
+        field: &'static str,
+    },
+    /// This error occurs when the named public inputs supplied for
+    /// verification do not match the ones registered by the circuit.
+    PublicInputMismatch {
+        /// Public inputs registered by the circuit but not supplied.
+        missing: Vec<String>,
+        /// Supplied public inputs unknown to the circuit.
+        extra: Vec<String>,
+        /// Public inputs supplied with a different type than registered.
+        wrong_type: Vec<String>,
+    },
+    /// This error occurs when a circuit registers two public inputs with the
+    /// same name.
+    DuplicatePublicInputName {
+        /// Name of the public input.
+        name: String,
+    },
+    /// This error occurs when an R1CS circuit fails to generate its
+    /// constraints.
+    SynthesisError {
+        /// Description of the synthesis error
+        error: String,
+    },
+    /// This error occurs when a Circom `.r1cs` or `.wtns` file is malformed
+    /// or doesn't match the circuit.
+    InvalidCircomFile {
+        /// What is wrong with the file.
+        reason: &'static str,
+    },
 
     // Preprocessing errors
     /// This error occurs when an error triggers during the preprocessing
@@ -47,6 +109,12 @@ pub enum Error {
         error: ark_poly_commit::error::Error,
     },
 
+    /// Serialization errors
+    SerializationError {
+        /// Serialization errors
+        error: ark_serialize::SerializationError,
+    },
+
     // KZG10 errors
     // XXX: Are these errors still used?
     /// This error occurs when the user tries to create PublicParameters
@@ -67,6 +135,12 @@ pub enum Error {
     /// This error occurs when the pairing check fails at being equal to the
     /// Identity point.
     PairingCheckFailure,
+    /// This error occurs when a contribution to a setup ceremony does not
+    /// prove knowledge of its secret or does not update the previous powers.
+    InvalidContribution {
+        /// Position of the contribution in the ceremony.
+        index: usize,
+    },
 
     /// This error occurs when there are not enough bytes to read out of a
     /// slice during deserialization.
@@ -80,13 +154,32 @@ pub enum Error {
 
 impl From<ark_poly_commit::error::Error> for Error {
     fn from(error: ark_poly_commit::error::Error) -> Self {
-        Self::PCError { error }
+        match error {
+            ark_poly_commit::error::Error::TooManyCoefficients { .. } => {
+                Self::PolynomialDegreeTooLarge
+            }
+            error => Self::PCError { error },
+        }
     }
 }
 
-#[cfg(feature = "std")]
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl From<ark_serialize::SerializationError> for Error {
+    fn from(error: ark_serialize::SerializationError) -> Self {
+        Self::SerializationError { error }
+    }
+}
+
+#[cfg(feature = "r1cs")]
+impl From<ark_relations::r1cs::SynthesisError> for Error {
+    fn from(error: ark_relations::r1cs::SynthesisError) -> Self {
+        Self::SynthesisError {
+            error: alloc::format!("{}", error),
+        }
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidEvalDomainSize {
                 log_size_of_group,
@@ -106,6 +199,26 @@ impl std::fmt::Display for Error {
             Self::UninitializedPIGenerator => {
                 write!(f, "PI generator uninitialized")
             }
+            Self::UninitializedVerifierKey => {
+                write!(f, "verifier key uninitialized")
+            }
+            Self::EqualChallenges => {
+                write!(f, "permutation challenges must be different")
+            }
+            Self::OddNumberOfBits { num_bits } => {
+                write!(f, "number of bits must be even, got {}", num_bits)
+            }
+            Self::PublicInputOutOfRange {
+                position,
+                circuit_size,
+            } => write!(
+                f,
+                "public input position {} is outside of a circuit of size {}",
+                position, circuit_size
+            ),
+            Self::UnknownCircuit { name } => {
+                write!(f, "no circuit is registered under {:?}", name)
+            }
             Self::InvalidPublicInputBytes => {
                 write!(f, "invalid public input bytes")
             }
@@ -115,9 +228,34 @@ impl std::fmt::Display for Error {
             Self::PCError { error } => {
                 write!(f, "{:?}", error)
             }
+            Self::SerializationError { error } => {
+                write!(f, "{:?}", error)
+            }
             Self::CircuitAlreadyPreprocessed => {
                 write!(f, "circuit has already been preprocessed")
             }
+            Self::StaleKey { field } => {
+                write!(f, "stored key is stale: {} does not match", field)
+            }
+            Self::PublicInputMismatch {
+                missing,
+                extra,
+                wrong_type,
+            } => write!(
+                f,
+                "public input mismatch: missing {:?}, extra {:?}, \
+                wrong type {:?}",
+                missing, extra, wrong_type
+            ),
+            Self::DuplicatePublicInputName { name } => {
+                write!(f, "public input `{}` registered twice", name)
+            }
+            Self::SynthesisError { error } => {
+                write!(f, "R1CS synthesis failed: {}", error)
+            }
+            Self::InvalidCircomFile { reason } => {
+                write!(f, "invalid Circom file: {}", reason)
+            }
             Self::DegreeIsZero => {
                 write!(f, "cannot create PublicParameters with max degree 0")
             }
@@ -136,6 +274,9 @@ impl std::fmt::Display for Error {
                 write!(f, "cannot commit to polynomial of zero degree")
             }
             Self::PairingCheckFailure => write!(f, "pairing check failed"),
+            Self::InvalidContribution { index } => {
+                write!(f, "contribution {} of the ceremony is invalid", index)
+            }
             Self::NotEnoughBytes => write!(f, "not enough bytes left to read"),
             Self::PointMalformed => write!(f, "point bytes malformed"),
             Self::ScalarMalformed => write!(f, "scalar bytes malformed"),
@@ -0,0 +1,325 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! On-disk storage of preprocessed circuit keys.
+//!
+//! A [`KeyStore`] saves the keys of a circuit together with its
+//! [`CIRCUIT_ID`](crate::circuit::Circuit::CIRCUIT_ID), a hash of the setup
+//! parameters they were derived from and the version of this library. Keys for
+//! which any of them differs are refused as stale.
+
+use crate::circuit::VerifierData;
+use crate::error::Error;
+use crate::proof_system::ProverKey;
+use crate::setup::PublicParameters;
+use ark_ec::{PairingEngine, TEModelParameters};
+use ark_serialize::*;
+use merlin::Transcript;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::PathBuf;
+
+/// Prefix of every key file.
+const MAGIC: &[u8; 8] = b"plonkkey";
+
+/// Version of this library, stored with every key.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Directory of preprocessed circuit keys, one file per circuit.
+#[derive(Clone, Debug)]
+pub struct KeyStore {
+    /// Directory holding the key files
+    root: PathBuf,
+}
+
+impl KeyStore {
+    /// Creates a `KeyStore` keeping its keys in the `root` directory, which is
+    /// created on the first save.
+    pub fn new<T>(root: T) -> Self
+    where
+        T: Into<PathBuf>,
+    {
+        Self { root: root.into() }
+    }
+
+    /// Returns the path of the file holding the keys of `circuit_id`.
+    pub fn path(&self, circuit_id: [u8; 32]) -> PathBuf {
+        let mut name = circuit_id
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        name.push_str(".key");
+        self.root.join(name)
+    }
+
+    /// Saves the keys of `circuit_id`, derived from `pp`, replacing any
+    /// previously saved ones.
+    pub fn save<E, P>(
+        &self,
+        circuit_id: [u8; 32],
+        pp: &PublicParameters<E>,
+        prover_key: &ProverKey<E::Fr, P>,
+        verifier_data: &VerifierData<E, P>,
+    ) -> Result<(), Error>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        self.save_with_version(
+            VERSION,
+            circuit_id,
+            pp,
+            prover_key,
+            verifier_data,
+        )
+    }
+
+    /// Saves the keys of `circuit_id` as derived by the library `version`.
+    fn save_with_version<E, P>(
+        &self,
+        version: &str,
+        circuit_id: [u8; 32],
+        pp: &PublicParameters<E>,
+        prover_key: &ProverKey<E::Fr, P>,
+        verifier_data: &VerifierData<E, P>,
+    ) -> Result<(), Error>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let setup_hash = setup_hash(pp, prover_key.n)
+            .ok_or(Error::TruncatedDegreeTooLarge)?;
+
+        fs::create_dir_all(&self.root).map_err(SerializationError::from)?;
+        let path = self.path(circuit_id);
+        let tmp_path = path.with_extension("tmp");
+        {
+            let file =
+                File::create(&tmp_path).map_err(SerializationError::from)?;
+            let mut writer = BufWriter::new(file);
+            writer.write_all(MAGIC).map_err(SerializationError::from)?;
+            version.to_owned().serialize(&mut writer)?;
+            writer
+                .write_all(&circuit_id)
+                .map_err(SerializationError::from)?;
+            prover_key.n.serialize(&mut writer)?;
+            writer
+                .write_all(&setup_hash)
+                .map_err(SerializationError::from)?;
+            prover_key.serialize(&mut writer)?;
+            verifier_data.serialize(&mut writer)?;
+            writer.flush().map_err(SerializationError::from)?;
+        }
+
+        // Replace the previous keys only once the new ones are complete.
+        fs::rename(&tmp_path, &path).map_err(SerializationError::from)?;
+        Ok(())
+    }
+
+    /// Loads the keys of `circuit_id`, returning `None` if none were saved.
+    ///
+    /// Fails with [`Error::StaleKey`] if the saved keys were derived by
+    /// another version of this library or from other setup parameters.
+    #[allow(clippy::type_complexity)] // NOTE: This is an ok type for a key pair.
+    pub fn load<E, P>(
+        &self,
+        circuit_id: [u8; 32],
+        pp: &PublicParameters<E>,
+    ) -> Result<Option<(ProverKey<E::Fr, P>, VerifierData<E, P>)>, Error>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let file = match File::open(self.path(circuit_id)) {
+            Ok(file) => file,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok(None)
+            }
+            Err(error) => return Err(SerializationError::from(error).into()),
+        };
+        let mut reader = BufReader::new(file);
+
+        let mut magic = [0u8; 8];
+        reader
+            .read_exact(&mut magic)
+            .map_err(SerializationError::from)?;
+        if &magic != MAGIC {
+            return Err(SerializationError::InvalidData.into());
+        }
+        if String::deserialize(&mut reader)? != VERSION {
+            return Err(Error::StaleKey {
+                field: "library version",
+            });
+        }
+        let mut stored_circuit_id = [0u8; 32];
+        reader
+            .read_exact(&mut stored_circuit_id)
+            .map_err(SerializationError::from)?;
+        if stored_circuit_id != circuit_id {
+            return Err(Error::StaleKey {
+                field: "circuit id",
+            });
+        }
+        let n = usize::deserialize(&mut reader)?;
+        let mut stored_setup_hash = [0u8; 32];
+        reader
+            .read_exact(&mut stored_setup_hash)
+            .map_err(SerializationError::from)?;
+        if setup_hash(pp, n) != Some(stored_setup_hash) {
+            return Err(Error::StaleKey {
+                field: "setup parameters",
+            });
+        }
+
+        let prover_key = ProverKey::deserialize(&mut reader)?;
+        let verifier_data = VerifierData::deserialize(&mut reader)?;
+        Ok(Some((prover_key, verifier_data)))
+    }
+}
+
+/// Hashes the part of `pp` that the keys of a circuit of size `n` are derived
+/// from, or returns `None` if `pp` is too small for the circuit.
+fn setup_hash<E>(pp: &PublicParameters<E>, n: usize) -> Option<[u8; 32]>
+where
+    E: PairingEngine,
+{
+    let powers_of_g = pp.commit_key(n).ok()?.powers_of_g;
+    let mut transcript = Transcript::new(b"keystore");
+    let mut writer = TranscriptWriter(&mut transcript);
+    powers_of_g.serialize(&mut writer).ok()?;
+    pp.params().h.serialize(&mut writer).ok()?;
+    pp.params().beta_h.serialize(&mut writer).ok()?;
+
+    let mut hash = [0u8; 32];
+    transcript.challenge_bytes(b"setup_hash", &mut hash);
+    Some(hash)
+}
+
+/// Appends everything written to it to a transcript, so that large setups are
+/// hashed without being serialized into memory first.
+struct TranscriptWriter<'a>(&'a mut Transcript);
+
+impl io::Write for TranscriptWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.append_message(b"setup", buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::circuit::Circuit;
+    use crate::constraint_system::StandardComposer;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use core::marker::PhantomData;
+    use rand_core::OsRng;
+
+    /// Circuit adding its two inputs.
+    #[derive(derivative::Derivative)]
+    #[derivative(Default(bound = ""))]
+    struct AddCircuit<E, P>(PhantomData<(E, P)>);
+
+    impl<E, P> Circuit<E, P> for AddCircuit<E, P>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        const CIRCUIT_ID: [u8; 32] = [0x0a; 32];
+
+        fn gadget(
+            &mut self,
+            composer: &mut StandardComposer<E, P>,
+        ) -> Result<(), Error> {
+            let a = composer.add_input(E::Fr::from(2u64));
+            let b = composer.add_input(E::Fr::from(3u64));
+            let c = composer.add_input(E::Fr::from(5u64));
+            let sum = composer.add(
+                (E::Fr::from(1u64), a),
+                (E::Fr::from(1u64), b),
+                E::Fr::from(0u64),
+                None,
+            );
+            composer.assert_equal(sum, c);
+            Ok(())
+        }
+
+        fn padded_circuit_size(&self) -> usize {
+            1 << 3
+        }
+    }
+
+    fn test_keystore<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let pp = PublicParameters::<E>::setup(1 << 4, &mut OsRng).unwrap();
+        let dir = tempdir::TempDir::new("keystore").unwrap();
+        let keystore = KeyStore::new(dir.path().join("keys"));
+        let circuit_id = AddCircuit::<E, P>::CIRCUIT_ID;
+        let mut circuit = AddCircuit::<E, P>::default();
+
+        // Keys are compiled and saved on the first call, then loaded
+        assert!(keystore.load::<E, P>(circuit_id, &pp).unwrap().is_none());
+        let keys = circuit.compile_with_keystore(&pp, &keystore).unwrap();
+        assert_eq!(keys, circuit.compile(&pp).unwrap());
+        assert_eq!(keystore.load(circuit_id, &pp).unwrap(), Some(keys.clone()));
+        assert!(keystore.load::<E, P>([0; 32], &pp).unwrap().is_none());
+
+        // Keys derived from other setup parameters are stale
+        let other_params =
+            PublicParameters::<E>::setup(1 << 4, &mut OsRng).unwrap();
+        assert!(matches!(
+            keystore.load::<E, P>(circuit_id, &other_params),
+            Err(Error::StaleKey {
+                field: "setup parameters"
+            })
+        ));
+
+        // Keys derived by another version of the library are stale
+        keystore
+            .save_with_version("0.0.0", circuit_id, &pp, &keys.0, &keys.1)
+            .unwrap();
+        assert!(matches!(
+            keystore.load::<E, P>(circuit_id, &pp),
+            Err(Error::StaleKey {
+                field: "library version"
+            })
+        ));
+
+        // Stale keys are recompiled and replaced
+        let recompiled = circuit.compile_with_keystore(&pp, &keystore).unwrap();
+        assert_eq!(recompiled, keys);
+        assert_eq!(keystore.load(circuit_id, &pp).unwrap(), Some(keys));
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [test_keystore],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [test_keystore],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -24,6 +24,7 @@
 //! <https://github.com/dusk-network/plonk/tree/v0.1.0/examples>
 
 // Bitshift/Bitwise ops are allowed to gain performance.
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::suspicious_arithmetic_impl)]
 // Some structs do not have AddAssign or MulAssign impl.
 #![allow(clippy::suspicious_op_assign_impl)]
@@ -37,6 +38,7 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(missing_docs)]
 
+#[macro_use]
 extern crate alloc;
 
 mod permutation;
@@ -46,11 +48,17 @@ mod util;
 pub mod circuit;
 pub mod constraint_system;
 pub mod error;
+#[cfg(feature = "std")]
+pub mod keystore;
 pub mod prelude;
 pub mod proof_system;
-
-#[cfg(test)]
-mod test;
+#[cfg(feature = "r1cs")]
+pub mod r1cs;
+pub mod setup;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
 
 #[doc = include_str!("../docs/notes-intro.md")]
 pub mod notes {
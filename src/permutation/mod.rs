@@ -11,6 +11,7 @@
 pub(crate) mod constants;
 
 use crate::constraint_system::{Variable, WireData};
+use alloc::vec::Vec;
 use ark_ff::PrimeField;
 use ark_poly::domain::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_poly::{univariate::DensePolynomial, UVPolynomial};
@@ -1213,7 +1214,7 @@ mod test {
         let gamma = F::rand(&mut OsRng);
         assert_ne!(gamma, beta);
 
-        //1. Compute the permutation polynomial using both methods
+        // 1. Compute the permutation polynomial using both methods
         //
         let (
             left_sigma_poly,
@@ -1274,7 +1275,7 @@ mod test {
         }
         assert_eq!(a_0 * b_0.inverse().unwrap(), F::one());
 
-        //3. Now we perform the two checks that need to be done on the
+        // 3. Now we perform the two checks that need to be done on the
         // permutation polynomial (z)
         let z_poly =
             DensePolynomial::<F>::from_coefficients_vec(domain.ifft(&z_vec));
@@ -13,8 +13,9 @@
 
 pub use crate::{
     circuit::{self, Circuit, PublicInputValue, VerifierData},
-    constraint_system::{ecc::Point, StandardComposer, Variable},
+    constraint_system::{ecc::Point, FieldVar, StandardComposer, Variable},
     error::Error,
     proof_system::{Proof, VerifierKey},
     proof_system::{Prover, ProverKey, Verifier},
+    setup::PublicParameters,
 };
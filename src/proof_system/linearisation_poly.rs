@@ -6,37 +6,28 @@
 //
 // Copyright (c) ZK-INFRA. All rights reserved.
 
-use crate::proof_system::ecc::{CurveAddition, FixedBaseScalarMul};
+use crate::error::Error;
+use crate::proof_system::ecc::{
+    CurveAddition, CurveDoubling, FixedBaseScalarMul,
+};
 use crate::proof_system::logic::Logic;
+use crate::proof_system::low_memory::MemoryMeter;
+use crate::proof_system::permutation;
 use crate::proof_system::range::Range;
 use crate::proof_system::widget::GateConstraint;
-use crate::proof_system::GateValues;
-use crate::proof_system::ProverKey;
-use crate::util::EvaluationDomainExt;
+use crate::proof_system::{GateValues, PolynomialId, PolynomialStore};
+use crate::util::{self, EvaluationDomainExt};
 use ark_ec::TEModelParameters;
 use ark_ff::Field;
 use ark_ff::PrimeField;
 use ark_poly::{
-    univariate::DensePolynomial, GeneralEvaluationDomain, Polynomial,
+    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
+    Polynomial,
 };
 use ark_serialize::{
     CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write,
 };
-
-/// Polynomial Evaluations
-///
-/// This `struct` keeps track of polynomial evaluations at points `z` and/or `z
-/// * w` where `w` is a root of unit.
-pub struct Evaluations<F>
-where
-    F: PrimeField,
-{
-    /// Proof-relevant Evaluations
-    pub proof: ProofEvaluations<F>,
-
-    /// Evaluation of the linearisation sigma polynomial at `z`.
-    pub quot_eval: F,
-}
+use num_traits::Zero;
 
 /// Subset of all of the evaluations. These evaluations
 /// are added to the [`Proof`](super::Proof).
@@ -92,6 +83,10 @@ where
     pub out_sigma_eval: F,
 
     /// Evaluation of the linearisation sigma polynomial at `z`.
+    ///
+    /// Left as zero and not serialized in
+    /// [`ProofMode::Compact`](super::ProofMode::Compact) proofs, where the
+    /// verifier derives it.
     pub linearisation_polynomial_eval: F,
 
     /// Evaluation of the permutation polynomial at `z * omega` where `omega`
@@ -100,9 +95,10 @@ where
 }
 
 /// Compute the linearisation polynomial.
-pub fn compute<F, P>(
+pub fn compute<F, P, S>(
     domain: &GeneralEvaluationDomain<F>,
-    prover_key: &ProverKey<F, P>,
+    store: &S,
+    meter: &MemoryMeter,
     alpha: &F,
     beta: &F,
     gamma: &F,
@@ -115,38 +111,69 @@ pub fn compute<F, P>(
     w_r_poly: &DensePolynomial<F>,
     w_o_poly: &DensePolynomial<F>,
     w_4_poly: &DensePolynomial<F>,
-    t_x_poly: &DensePolynomial<F>,
     z_poly: &DensePolynomial<F>,
-) -> (DensePolynomial<F>, Evaluations<F>)
+) -> Result<(DensePolynomial<F>, ProofEvaluations<F>), Error>
 where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
+    S: PolynomialStore<F>,
 {
-    let quot_eval = t_x_poly.evaluate(z_challenge);
-    let a_eval = w_l_poly.evaluate(z_challenge);
-    let b_eval = w_r_poly.evaluate(z_challenge);
-    let c_eval = w_o_poly.evaluate(z_challenge);
-    let d_eval = w_4_poly.evaluate(z_challenge);
-    let left_sigma_eval =
-        prover_key.permutation.left_sigma.0.evaluate(z_challenge);
-    let right_sigma_eval =
-        prover_key.permutation.right_sigma.0.evaluate(z_challenge);
-    let out_sigma_eval =
-        prover_key.permutation.out_sigma.0.evaluate(z_challenge);
-    let q_arith_eval = prover_key.arithmetic.q_arith.0.evaluate(z_challenge);
-    let q_c_eval = prover_key.arithmetic.q_c.0.evaluate(z_challenge);
-    let q_l_eval = prover_key.arithmetic.q_l.0.evaluate(z_challenge);
-    let q_r_eval = prover_key.arithmetic.q_r.0.evaluate(z_challenge);
+    let evaluate = |id| -> Result<F, Error> {
+        let polynomial = store.polynomial(id)?;
+        let _allocation = meter.track_loaded(&polynomial);
+        Ok(polynomial.evaluate(z_challenge))
+    };
 
     let omega = domain.group_gen();
     let shifted_z_challenge = *z_challenge * omega;
 
-    let a_next_eval = w_l_poly.evaluate(&shifted_z_challenge);
-    let b_next_eval = w_r_poly.evaluate(&shifted_z_challenge);
-    let d_next_eval = w_4_poly.evaluate(&shifted_z_challenge);
-    let permutation_eval = z_poly.evaluate(&shifted_z_challenge);
-
-    let gate_constraints = compute_gate_constraint_satisfiability(
+    // The witness evaluations are independent of each other.
+    let (
+        ((a_eval, b_eval), (c_eval, d_eval)),
+        ((a_next_eval, b_next_eval), (d_next_eval, permutation_eval)),
+    ) = util::join(
+        || {
+            util::join(
+                || {
+                    util::join(
+                        || w_l_poly.evaluate(z_challenge),
+                        || w_r_poly.evaluate(z_challenge),
+                    )
+                },
+                || {
+                    util::join(
+                        || w_o_poly.evaluate(z_challenge),
+                        || w_4_poly.evaluate(z_challenge),
+                    )
+                },
+            )
+        },
+        || {
+            util::join(
+                || {
+                    util::join(
+                        || w_l_poly.evaluate(&shifted_z_challenge),
+                        || w_r_poly.evaluate(&shifted_z_challenge),
+                    )
+                },
+                || {
+                    util::join(
+                        || w_4_poly.evaluate(&shifted_z_challenge),
+                        || z_poly.evaluate(&shifted_z_challenge),
+                    )
+                },
+            )
+        },
+    );
+    let left_sigma_eval = evaluate(PolynomialId::LeftSigma)?;
+    let right_sigma_eval = evaluate(PolynomialId::RightSigma)?;
+    let out_sigma_eval = evaluate(PolynomialId::OutSigma)?;
+    let q_arith_eval = evaluate(PolynomialId::QArith)?;
+    let q_c_eval = evaluate(PolynomialId::QC)?;
+    let q_l_eval = evaluate(PolynomialId::QL)?;
+    let q_r_eval = evaluate(PolynomialId::QR)?;
+
+    let gate_constraints = compute_gate_constraint_satisfiability::<F, P>(
         range_separation_challenge,
         logic_separation_challenge,
         fixed_base_separation_challenge,
@@ -162,49 +189,62 @@ where
         q_c_eval,
         q_l_eval,
         q_r_eval,
-        prover_key,
     );
 
-    let permutation = prover_key.permutation.compute_linearisation(
+    // The selector polynomials are loaded one at a time and accumulated.
+    let mut linearisation_polynomial = DensePolynomial::zero();
+    let _linearisation = meter.track::<F>(domain.size());
+    for (id, scalar) in gate_constraints {
+        let selector = store.polynomial(id)?;
+        let _selector = meter.track_loaded(&selector);
+        let _term = meter.track::<F>(domain.size());
+        linearisation_polynomial =
+            &linearisation_polynomial + &(selector.as_ref() * scalar);
+    }
+
+    let fourth_sigma = store.polynomial(PolynomialId::FourthSigma)?;
+    let _fourth_sigma = meter.track_loaded(&fourth_sigma);
+    let _permutation = meter.track::<F>(domain.size());
+    let permutation = permutation::ProverKey::compute_linearisation(
+        domain,
         *z_challenge,
         (*alpha, *beta, *gamma),
         (a_eval, b_eval, c_eval, d_eval),
         (left_sigma_eval, right_sigma_eval, out_sigma_eval),
         permutation_eval,
         z_poly,
+        &fourth_sigma,
     );
 
-    let linearisation_polynomial = gate_constraints + permutation;
+    let linearisation_polynomial = linearisation_polynomial + permutation;
     let linearisation_polynomial_eval =
         linearisation_polynomial.evaluate(z_challenge);
 
-    (
+    Ok((
         linearisation_polynomial,
-        Evaluations {
-            proof: ProofEvaluations {
-                a_eval,
-                b_eval,
-                c_eval,
-                d_eval,
-                a_next_eval,
-                b_next_eval,
-                d_next_eval,
-                q_arith_eval,
-                q_c_eval,
-                q_l_eval,
-                q_r_eval,
-                left_sigma_eval,
-                right_sigma_eval,
-                out_sigma_eval,
-                linearisation_polynomial_eval,
-                permutation_eval,
-            },
-            quot_eval,
+        ProofEvaluations {
+            a_eval,
+            b_eval,
+            c_eval,
+            d_eval,
+            a_next_eval,
+            b_next_eval,
+            d_next_eval,
+            q_arith_eval,
+            q_c_eval,
+            q_l_eval,
+            q_r_eval,
+            left_sigma_eval,
+            right_sigma_eval,
+            out_sigma_eval,
+            linearisation_polynomial_eval,
+            permutation_eval,
         },
-    )
+    ))
 }
 
-/// Computes the gate constraint satisfiability portion of the linearisation
+/// Computes the scalars by which each selector polynomial is multiplied in
+/// the gate constraint satisfiability portion of the linearisation
 /// polynomial.
 fn compute_gate_constraint_satisfiability<F, P>(
     range_separation_challenge: &F,
@@ -222,8 +262,7 @@ fn compute_gate_constraint_satisfiability<F, P>(
     q_c_eval: F,
     q_l_eval: F,
     q_r_eval: F,
-    prover_key: &ProverKey<F, P>,
-) -> DensePolynomial<F>
+) -> [(PolynomialId, F); 11]
 where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
@@ -241,37 +280,41 @@ where
         constant_selector: q_c_eval,
     };
 
-    let arithmetic = prover_key.arithmetic.compute_linearisation(
-        a_eval,
-        b_eval,
-        c_eval,
-        d_eval,
-        q_arith_eval,
-    );
-
-    let range = Range::linearisation_term(
-        &prover_key.range_selector.0,
-        *range_separation_challenge,
-        values,
-    );
-
-    let logic = Logic::linearisation_term(
-        &prover_key.logic_selector.0,
-        *logic_separation_challenge,
-        values,
-    );
-
-    let fixed_base_scalar_mul = FixedBaseScalarMul::<_, P>::linearisation_term(
-        &prover_key.fixed_group_add_selector.0,
-        *fixed_base_separation_challenge,
-        values,
-    );
-
-    let curve_addition = CurveAddition::<_, P>::linearisation_term(
-        &prover_key.variable_group_add_selector.0,
-        *var_base_separation_challenge,
-        values,
-    );
-
-    arithmetic + range + logic + fixed_base_scalar_mul + curve_addition
+    [
+        (PolynomialId::QM, a_eval * b_eval * q_arith_eval),
+        (PolynomialId::QL, a_eval * q_arith_eval),
+        (PolynomialId::QR, b_eval * q_arith_eval),
+        (PolynomialId::QO, c_eval * q_arith_eval),
+        (PolynomialId::Q4, d_eval * q_arith_eval),
+        (PolynomialId::QC, q_arith_eval),
+        (
+            PolynomialId::QRange,
+            Range::constraints(*range_separation_challenge, values),
+        ),
+        (
+            PolynomialId::QLogic,
+            Logic::constraints(*logic_separation_challenge, values),
+        ),
+        (
+            PolynomialId::QFixedGroupAdd,
+            FixedBaseScalarMul::<_, P>::constraints(
+                *fixed_base_separation_challenge,
+                values,
+            ),
+        ),
+        (
+            PolynomialId::QVariableGroupAdd,
+            CurveAddition::<_, P>::constraints(
+                *var_base_separation_challenge,
+                values,
+            ),
+        ),
+        (
+            PolynomialId::QVariableGroupDouble,
+            CurveDoubling::<_, P>::constraints(
+                *var_base_separation_challenge,
+                values,
+            ),
+        ),
+    ]
 }
@@ -0,0 +1,545 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Memory-conscious proving.
+//!
+//! The [`ProverKey`] stores every selector and permutation polynomial both in
+//! coefficient form and as evaluations over the `4n` coset, which dominates
+//! the memory needed to prove large circuits. The low-memory prover
+//! ([`Prover::prove_low_memory`](super::Prover::prove_low_memory)) only needs
+//! the coefficients, provided by any [`PolynomialStore`]. It recomputes the
+//! coset evaluations one `n`-sized coset at a time while computing the
+//! quotient polynomial, and produces exactly the same proofs as the in-memory
+//! prover.
+
+use crate::error::Error;
+use crate::proof_system::{ProofMode, ProverKey};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use ark_ec::TEModelParameters;
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_serialize::*;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Identifies one of the preprocessed polynomials of a circuit.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PolynomialId {
+    /// Multiplication Selector
+    QM,
+    /// Left Wire Selector
+    QL,
+    /// Right Wire Selector
+    QR,
+    /// Output Wire Selector
+    QO,
+    /// Fourth Wire Selector
+    Q4,
+    /// Constant Selector
+    QC,
+    /// Arithmetic Selector
+    QArith,
+    /// Range Gate Selector
+    QRange,
+    /// Logic Gate Selector
+    QLogic,
+    /// Fixed Group Addition Selector
+    QFixedGroupAdd,
+    /// Variable Group Addition Selector
+    QVariableGroupAdd,
+    /// Variable Group Doubling Selector
+    QVariableGroupDouble,
+    /// Left Permutation
+    LeftSigma,
+    /// Right Permutation
+    RightSigma,
+    /// Output Permutation
+    OutSigma,
+    /// Fourth Permutation
+    FourthSigma,
+}
+
+impl PolynomialId {
+    /// Every preprocessed polynomial, in storage order.
+    pub const ALL: [PolynomialId; 16] = [
+        PolynomialId::QM,
+        PolynomialId::QL,
+        PolynomialId::QR,
+        PolynomialId::QO,
+        PolynomialId::Q4,
+        PolynomialId::QC,
+        PolynomialId::QArith,
+        PolynomialId::QRange,
+        PolynomialId::QLogic,
+        PolynomialId::QFixedGroupAdd,
+        PolynomialId::QVariableGroupAdd,
+        PolynomialId::QVariableGroupDouble,
+        PolynomialId::LeftSigma,
+        PolynomialId::RightSigma,
+        PolynomialId::OutSigma,
+        PolynomialId::FourthSigma,
+    ];
+}
+
+/// Source of the preprocessed polynomials of a circuit, in coefficient form.
+pub trait PolynomialStore<F>: Sync
+where
+    F: PrimeField,
+{
+    /// Size of the evaluation domain of the circuit.
+    fn domain_size(&self) -> usize;
+
+    /// [`ProofMode`] of the proofs created with this key.
+    fn mode(&self) -> ProofMode;
+
+    /// Returns the coefficients of the polynomial `id`.
+    fn polynomial(
+        &self,
+        id: PolynomialId,
+    ) -> Result<Cow<'_, DensePolynomial<F>>, Error>;
+
+    /// Number of bytes of the store kept in memory.
+    fn resident_bytes(&self) -> usize;
+}
+
+impl<F, P> PolynomialStore<F> for ProverKey<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    fn domain_size(&self) -> usize {
+        self.n
+    }
+
+    fn mode(&self) -> ProofMode {
+        self.mode
+    }
+
+    fn polynomial(
+        &self,
+        id: PolynomialId,
+    ) -> Result<Cow<'_, DensePolynomial<F>>, Error> {
+        let polynomial = match id {
+            PolynomialId::QM => &self.arithmetic.q_m.0,
+            PolynomialId::QL => &self.arithmetic.q_l.0,
+            PolynomialId::QR => &self.arithmetic.q_r.0,
+            PolynomialId::QO => &self.arithmetic.q_o.0,
+            PolynomialId::Q4 => &self.arithmetic.q_4.0,
+            PolynomialId::QC => &self.arithmetic.q_c.0,
+            PolynomialId::QArith => &self.arithmetic.q_arith.0,
+            PolynomialId::QRange => &self.range_selector.0,
+            PolynomialId::QLogic => &self.logic_selector.0,
+            PolynomialId::QFixedGroupAdd => &self.fixed_group_add_selector.0,
+            PolynomialId::QVariableGroupAdd => {
+                &self.variable_group_add_selector.0
+            }
+            PolynomialId::QVariableGroupDouble => {
+                &self.variable_group_double_selector.0
+            }
+            PolynomialId::LeftSigma => &self.permutation.left_sigma.0,
+            PolynomialId::RightSigma => &self.permutation.right_sigma.0,
+            PolynomialId::OutSigma => &self.permutation.out_sigma.0,
+            PolynomialId::FourthSigma => &self.permutation.fourth_sigma.0,
+        };
+        Ok(Cow::Borrowed(polynomial))
+    }
+
+    fn resident_bytes(&self) -> usize {
+        // Coefficients and `4n` evaluations of every polynomial, plus the
+        // linear and vanishing polynomial evaluations.
+        let coefficients: usize = PolynomialId::ALL
+            .iter()
+            .map(|id| self.polynomial(*id).map_or(0, |p| p.coeffs.len()))
+            .sum();
+        let evaluations = (PolynomialId::ALL.len() + 2) * 4 * self.n;
+        (coefficients + evaluations) * size_of::<F>()
+    }
+}
+
+/// PLONK circuit Proving Key without the coset evaluations of the
+/// [`ProverKey`], for use with the low-memory prover.
+#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct LowMemoryProverKey<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Circuit size
+    pub(crate) n: usize,
+
+    /// Variant of the protocol produced by this key
+    pub(crate) mode: ProofMode,
+
+    /// Preprocessed polynomials, in [`PolynomialId::ALL`] order
+    pub(crate) polynomials: Vec<DensePolynomial<F>>,
+
+    /// Type Parameter Marker
+    pub(crate) __: PhantomData<P>,
+}
+
+impl<F, P> LowMemoryProverKey<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Sets the [`ProofMode`] of the proofs created with this key.
+    pub fn set_mode(&mut self, mode: ProofMode) {
+        self.mode = mode;
+    }
+}
+
+impl<F, P> From<&ProverKey<F, P>> for LowMemoryProverKey<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    fn from(prover_key: &ProverKey<F, P>) -> Self {
+        Self {
+            n: prover_key.n,
+            mode: prover_key.mode,
+            polynomials: PolynomialId::ALL
+                .iter()
+                .map(|id| prover_key.polynomial(*id).unwrap().into_owned())
+                .collect(),
+            __: PhantomData,
+        }
+    }
+}
+
+impl<F, P> PolynomialStore<F> for LowMemoryProverKey<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    fn domain_size(&self) -> usize {
+        self.n
+    }
+
+    fn mode(&self) -> ProofMode {
+        self.mode
+    }
+
+    fn polynomial(
+        &self,
+        id: PolynomialId,
+    ) -> Result<Cow<'_, DensePolynomial<F>>, Error> {
+        Ok(Cow::Borrowed(&self.polynomials[id as usize]))
+    }
+
+    fn resident_bytes(&self) -> usize {
+        self.polynomials
+            .iter()
+            .map(|p| p.coeffs.len() * size_of::<F>())
+            .sum()
+    }
+}
+
+/// [`LowMemoryProverKey`] kept on disk and memory-mapped, so that each
+/// polynomial is only decoded while it is in use.
+///
+/// The file holds a [`LowMemoryProverKey`] serialized with
+/// [`CanonicalSerialize::serialize`].
+#[cfg(feature = "mmap")]
+pub struct MmapProverKey<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Circuit size
+    n: usize,
+
+    /// Variant of the protocol produced by this key
+    mode: ProofMode,
+
+    /// Mapped key file
+    map: memmap2::Mmap,
+
+    /// Byte range of every polynomial, in [`PolynomialId::ALL`] order
+    ranges: Vec<core::ops::Range<usize>>,
+
+    /// Type Parameter Marker
+    __: PhantomData<(F, P)>,
+}
+
+#[cfg(feature = "mmap")]
+impl<F, P> MmapProverKey<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    /// Maps the key stored in the file at `path`.
+    pub fn open<T>(path: T) -> Result<Self, Error>
+    where
+        T: AsRef<std::path::Path>,
+    {
+        let file =
+            std::fs::File::open(path).map_err(SerializationError::from)?;
+        // SAFETY: the key file must not be modified while it is mapped.
+        let map = unsafe { memmap2::Mmap::map(&file) }
+            .map_err(SerializationError::from)?;
+
+        let mut reader = &map[..];
+        let n = usize::deserialize(&mut reader)?;
+        let mode = ProofMode::deserialize(&mut reader)?;
+        let count = usize::deserialize(&mut reader)?;
+        if count != PolynomialId::ALL.len() {
+            return Err(SerializationError::InvalidData.into());
+        }
+
+        let element_size = F::zero().serialized_size();
+        let mut ranges = Vec::with_capacity(count);
+        let mut offset = map.len() - reader.len();
+        for _ in 0..count {
+            let len = usize::deserialize(&map[offset..])?;
+            let end = len
+                .checked_mul(element_size)
+                .and_then(|size| size.checked_add(offset + 8))
+                .filter(|end| *end <= map.len())
+                .ok_or(SerializationError::InvalidData)?;
+            ranges.push(offset..end);
+            offset = end;
+        }
+
+        Ok(Self {
+            n,
+            mode,
+            map,
+            ranges,
+            __: PhantomData,
+        })
+    }
+
+    /// Sets the [`ProofMode`] of the proofs created with this key.
+    pub fn set_mode(&mut self, mode: ProofMode) {
+        self.mode = mode;
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<F, P> PolynomialStore<F> for MmapProverKey<F, P>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+{
+    fn domain_size(&self) -> usize {
+        self.n
+    }
+
+    fn mode(&self) -> ProofMode {
+        self.mode
+    }
+
+    fn polynomial(
+        &self,
+        id: PolynomialId,
+    ) -> Result<Cow<'_, DensePolynomial<F>>, Error> {
+        let bytes = &self.map[self.ranges[id as usize].clone()];
+        Ok(Cow::Owned(DensePolynomial::deserialize(bytes)?))
+    }
+
+    fn resident_bytes(&self) -> usize {
+        0
+    }
+}
+
+/// Memory used while creating a proof with the low-memory prover.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryReport {
+    /// Largest number of bytes of field element buffers held by the prover
+    /// at once. Scratch space used inside a single FFT or MSM is not counted.
+    pub peak_bytes: usize,
+
+    /// Bytes of the [`PolynomialStore`] kept in memory.
+    pub resident_key_bytes: usize,
+}
+
+/// Tracks the field element buffers held by the prover.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryMeter {
+    /// Bytes currently held
+    current: AtomicUsize,
+
+    /// Largest value of `current`
+    peak: AtomicUsize,
+}
+
+impl MemoryMeter {
+    /// Records a buffer of `len` field elements, released when the returned
+    /// guard is dropped.
+    pub(crate) fn track<F>(&self, len: usize) -> Allocation<'_> {
+        let bytes = len * size_of::<F>();
+        let current = self.current.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+        Allocation { meter: self, bytes }
+    }
+
+    /// Records `polynomial` if it was loaded into a new buffer.
+    #[allow(clippy::ptr_arg)] // NOTE: Only owned polynomials are recorded.
+    pub(crate) fn track_loaded<F>(
+        &self,
+        polynomial: &Cow<'_, DensePolynomial<F>>,
+    ) -> Allocation<'_>
+    where
+        F: PrimeField,
+    {
+        match polynomial {
+            Cow::Borrowed(_) => self.track::<F>(0),
+            Cow::Owned(p) => self.track::<F>(p.coeffs.len()),
+        }
+    }
+
+    /// Largest number of bytes held at once.
+    pub(crate) fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+/// Buffer recorded by a [`MemoryMeter`].
+pub(crate) struct Allocation<'a> {
+    /// Meter the buffer is recorded in
+    meter: &'a MemoryMeter,
+
+    /// Size of the buffer
+    bytes: usize,
+}
+
+impl Drop for Allocation<'_> {
+    fn drop(&mut self) {
+        self.meter.current.fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::constraint_system::StandardComposer;
+    use crate::proof_system::{Proof, Prover, Verifier};
+    use crate::setup::PublicParameters;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::twisted_edwards_extended::GroupAffine;
+    use ark_ec::PairingEngine;
+    use ark_poly_commit::kzg10::Powers;
+    use rand_core::OsRng;
+
+    /// Circuit using every gate type.
+    fn circuit<E, P>(composer: &mut StandardComposer<E, P>)
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let a = composer.add_input(E::Fr::from(500u64));
+        let b = composer.add_input(E::Fr::from(357u64));
+        composer.range_gate(a, 10).unwrap();
+        composer.xor_gate(a, b, 10).unwrap();
+
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        let generator = GroupAffine::new(x, y);
+        let point = composer.fixed_base_scalar_mul(b, generator);
+        let sum = composer.point_addition_gate(point, point);
+        let sum = composer.point_doubling_gate(sum);
+        composer.public_input("sum_x", *sum.x()).unwrap();
+    }
+
+    /// Creates a proof with the in-memory prover.
+    fn prove_in_memory<E, P>(powers: &Powers<E>, mode: ProofMode) -> Proof<E, P>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut prover = Prover::<E, P>::new(b"low_memory");
+        circuit(prover.mut_cs());
+        prover.preprocess(powers).unwrap();
+        prover.prover_key.as_mut().unwrap().set_mode(mode);
+        prover.prove(powers).unwrap()
+    }
+
+    fn test_low_memory_proof<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let pp = PublicParameters::<E>::setup(1 << 11, &mut OsRng).unwrap();
+        let (powers, vk) = pp.trim(1 << 10).unwrap();
+
+        let mut verifier = Verifier::<E, P>::new(b"low_memory");
+        circuit(verifier.mut_cs());
+        verifier.preprocess(&powers).unwrap();
+
+        for mode in [ProofMode::Standard, ProofMode::Compact] {
+            let expected = prove_in_memory::<E, P>(&powers, mode);
+
+            let mut prover = Prover::<E, P>::new(b"low_memory");
+            circuit(prover.mut_cs());
+            let pi = prover.mut_cs().construct_dense_pi_vec();
+            let mut key = prover.preprocess_low_memory(&powers).unwrap();
+            key.set_mode(mode);
+            let (proof, report) =
+                prover.prove_low_memory(&powers, &key).unwrap();
+            assert_eq!(proof, expected);
+
+            verifier.verifier_key.as_mut().unwrap().set_mode(mode);
+            assert!(verifier.verify(&proof, &vk, &pi).is_ok());
+
+            // The prover holds less than the 4n evaluations of the key
+            let mut in_memory = Prover::<E, P>::new(b"low_memory");
+            circuit(in_memory.mut_cs());
+            in_memory.preprocess(&powers).unwrap();
+            let prover_key = in_memory.prover_key.as_ref().unwrap();
+            assert_eq!(report.resident_key_bytes, key.resident_bytes());
+            assert!(report.peak_bytes < prover_key.resident_bytes());
+
+            // The key built from a ProverKey is the same
+            let converted = LowMemoryProverKey::from(prover_key);
+            assert_eq!(converted.polynomials, key.polynomials);
+
+            #[cfg(feature = "mmap")]
+            {
+                let dir = tempdir::TempDir::new("low_memory").unwrap();
+                let path = dir.path().join("prover_key");
+                let mut file = std::fs::File::create(&path).unwrap();
+                key.serialize(&mut file).unwrap();
+                drop(file);
+
+                let mapped = MmapProverKey::<E::Fr, P>::open(&path).unwrap();
+                circuit(prover.mut_cs());
+                let (proof, report) =
+                    prover.prove_low_memory(&powers, &mapped).unwrap();
+                assert_eq!(proof, expected);
+                assert_eq!(report.resident_key_bytes, 0);
+            }
+        }
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [test_low_memory_proof],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [test_low_memory_proof],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -14,10 +14,16 @@ mod preprocess;
 mod quotient_poly;
 mod widget;
 
+pub mod low_memory;
 pub mod proof;
 pub mod prover;
 pub mod verifier;
 
+#[cfg(feature = "mmap")]
+pub use low_memory::MmapProverKey;
+pub use low_memory::{
+    LowMemoryProverKey, MemoryReport, PolynomialId, PolynomialStore,
+};
 pub use proof::*;
 pub use prover::Prover;
 pub use verifier::Verifier;
@@ -10,13 +10,12 @@
 
 use crate::permutation::constants::{K1, K2, K3};
 use crate::proof_system::linearisation_poly::ProofEvaluations;
+use alloc::vec::Vec;
 use ark_ec::PairingEngine;
 use ark_ff::Field;
 use ark_ff::PrimeField;
 use ark_poly::polynomial::univariate::DensePolynomial;
-use ark_poly::{
-    EvaluationDomain, Evaluations, GeneralEvaluationDomain, Polynomial,
-};
+use ark_poly::{EvaluationDomain, Evaluations, GeneralEvaluationDomain};
 use ark_poly_commit::sonic_pc::Commitment;
 use ark_serialize::*;
 
@@ -154,32 +153,32 @@ where
 
     /// Computes the linearisation polynomial.
     pub fn compute_linearisation(
-        &self,
+        domain: &GeneralEvaluationDomain<F>,
         z_challenge: F,
         (alpha, beta, gamma): (F, F, F),
         (a_eval, b_eval, c_eval, d_eval): (F, F, F, F),
         (sigma_1_eval, sigma_2_eval, sigma_3_eval): (F, F, F),
         z_eval: F,
         z_poly: &DensePolynomial<F>,
+        fourth_sigma_poly: &DensePolynomial<F>,
     ) -> DensePolynomial<F> {
-        let a = self.compute_lineariser_identity_range_check(
+        let a = Self::compute_lineariser_identity_range_check(
             (a_eval, b_eval, c_eval, d_eval),
             z_challenge,
             (alpha, beta, gamma),
             z_poly,
         );
-        let b = self.compute_lineariser_copy_range_check(
+        let b = Self::compute_lineariser_copy_range_check(
             (a_eval, b_eval, c_eval),
             z_eval,
             sigma_1_eval,
             sigma_2_eval,
             sigma_3_eval,
             (alpha, beta, gamma),
-            &self.fourth_sigma.0,
+            fourth_sigma_poly,
         );
-        let domain = GeneralEvaluationDomain::new(z_poly.degree()).unwrap();
-        let c = self.compute_lineariser_check_is_one(
-            &domain,
+        let c = Self::compute_lineariser_check_is_one(
+            domain,
             z_challenge,
             alpha.square(),
             z_poly,
@@ -194,7 +193,6 @@ where
     /// gamma)(c_eval + beta * K2 * z_challenge + gamma) * alpha z(X)
     /// ```
     fn compute_lineariser_identity_range_check(
-        &self,
         (a_eval, b_eval, c_eval, d_eval): (F, F, F, F),
         z_challenge: F,
         (alpha, beta, gamma): (F, F, F),
@@ -239,7 +237,6 @@ where
     /// (c_eval + beta * sigma_3 + gamma) * beta *z_eval * alpha^2 * Sigma_4(X)
     /// ```
     fn compute_lineariser_copy_range_check(
-        &self,
         (a_eval, b_eval, c_eval): (F, F, F),
         z_eval: F,
         sigma_1_eval: F,
@@ -278,7 +275,6 @@ where
 
     /// Computes the lineariser check.
     fn compute_lineariser_check_is_one(
-        &self,
         domain: &GeneralEvaluationDomain<F>,
         z_challenge: F,
         alpha_sq: F,
@@ -10,13 +10,18 @@
 
 use crate::constraint_system::StandardComposer;
 use crate::error::Error;
-use crate::proof_system::{widget, ProverKey};
+use crate::proof_system::{widget, LowMemoryProverKey, ProofMode, ProverKey};
 use crate::transcript::TranscriptWrapper;
+use crate::util;
+use alloc::vec::Vec;
 use ark_ec::{PairingEngine, TEModelParameters};
 use ark_ff::PrimeField;
 use ark_poly::polynomial::univariate::DensePolynomial;
-use ark_poly::{EvaluationDomain, Evaluations, GeneralEvaluationDomain};
+use ark_poly::{
+    EvaluationDomain, Evaluations, GeneralEvaluationDomain, UVPolynomial,
+};
 use ark_poly_commit::kzg10::{Powers, KZG10};
+use core::marker::PhantomData;
 use num_traits::{One, Zero};
 
 /// Struct that contains all of the selector and permutation [`Polynomial`]s in
@@ -38,6 +43,7 @@ where
     q_logic: DensePolynomial<F>,
     q_fixed_group_add: DensePolynomial<F>,
     q_variable_group_add: DensePolynomial<F>,
+    q_variable_group_double: DensePolynomial<F>,
     left_sigma: DensePolynomial<F>,
     right_sigma: DensePolynomial<F>,
     out_sigma: DensePolynomial<F>,
@@ -72,6 +78,7 @@ where
         self.q_logic.extend(zeroes_scalar.iter());
         self.q_fixed_group_add.extend(zeroes_scalar.iter());
         self.q_variable_group_add.extend(zeroes_scalar.iter());
+        self.q_variable_group_double.extend(zeroes_scalar.iter());
 
         self.w_l.extend(zeroes_var.iter());
         self.w_r.extend(zeroes_var.iter());
@@ -96,6 +103,7 @@ where
             && self.q_logic.len() == k
             && self.q_fixed_group_add.len() == k
             && self.q_variable_group_add.len() == k
+            && self.q_variable_group_double.len() == k
             && self.w_l.len() == k
             && self.w_r.len() == k
             && self.w_o.len() == k
@@ -118,9 +126,30 @@ where
     ) -> Result<ProverKey<E::Fr, P>, Error> {
         let (_, selectors, domain) =
             self.preprocess_shared(commit_key, transcript)?;
+        Self::prover_key(selectors, domain)
+    }
 
-        let domain_4n =
-            GeneralEvaluationDomain::new(4 * domain.size()).unwrap();
+    /// Preprocesses the circuit once for both the prover and the verifier,
+    /// sharing the selector interpolation and commitments between the
+    /// [`ProverKey`] and the [`VerifierKey`](widget::VerifierKey).
+    #[allow(clippy::type_complexity)] // NOTE: This is an ok type for a key pair.
+    pub fn preprocess(
+        &mut self,
+        commit_key: &Powers<E>,
+        transcript: &mut TranscriptWrapper<E>,
+    ) -> Result<(ProverKey<E::Fr, P>, widget::VerifierKey<E, P>), Error> {
+        let (verifier_key, selectors, domain) =
+            self.preprocess_shared(commit_key, transcript)?;
+        Ok((Self::prover_key(selectors, domain)?, verifier_key))
+    }
+
+    /// Computes the `4n` evaluations of the preprocessed polynomials and
+    /// assembles the [`ProverKey`].
+    fn prover_key(
+        selectors: SelectorPolynomials<E::Fr>,
+        domain: GeneralEvaluationDomain<E::Fr>,
+    ) -> Result<ProverKey<E::Fr, P>, Error> {
+        let domain_4n = util::domain(4 * domain.size())?;
         let q_m_eval_4n = Evaluations::from_vec_and_domain(
             domain_4n.coset_fft(&selectors.q_m),
             domain_4n,
@@ -165,6 +194,10 @@ where
             domain_4n.coset_fft(&selectors.q_variable_group_add),
             domain_4n,
         );
+        let q_variable_group_double_eval_4n = Evaluations::from_vec_and_domain(
+            domain_4n.coset_fft(&selectors.q_variable_group_double),
+            domain_4n,
+        );
 
         let left_sigma_eval_4n = Evaluations::from_vec_and_domain(
             domain_4n.coset_fft(&selectors.left_sigma),
@@ -205,6 +238,10 @@ where
             (selectors.q_logic, q_logic_eval_4n),
             (selectors.q_fixed_group_add, q_fixed_group_add_eval_4n),
             (selectors.q_variable_group_add, q_variable_group_add_eval_4n),
+            (
+                selectors.q_variable_group_double,
+                q_variable_group_double_eval_4n,
+            ),
             (selectors.left_sigma, left_sigma_eval_4n),
             (selectors.right_sigma, right_sigma_eval_4n),
             (selectors.out_sigma, out_sigma_eval_4n),
@@ -214,6 +251,41 @@ where
         ))
     }
 
+    /// Preprocesses the circuit for the low-memory prover, skipping the
+    /// FFTs needed to compute the `4n` evaluations of the [`ProverKey`].
+    pub fn preprocess_low_memory_prover(
+        &mut self,
+        commit_key: &Powers<E>,
+        transcript: &mut TranscriptWrapper<E>,
+    ) -> Result<LowMemoryProverKey<E::Fr, P>, Error> {
+        let (_, selectors, domain) =
+            self.preprocess_shared(commit_key, transcript)?;
+
+        Ok(LowMemoryProverKey {
+            n: domain.size(),
+            mode: ProofMode::Standard,
+            polynomials: vec![
+                selectors.q_m,
+                selectors.q_l,
+                selectors.q_r,
+                selectors.q_o,
+                selectors.q_4,
+                selectors.q_c,
+                selectors.q_arith,
+                selectors.q_range,
+                selectors.q_logic,
+                selectors.q_fixed_group_add,
+                selectors.q_variable_group_add,
+                selectors.q_variable_group_double,
+                selectors.left_sigma,
+                selectors.right_sigma,
+                selectors.out_sigma,
+                selectors.fourth_sigma,
+            ],
+            __: PhantomData,
+        })
+    }
+
     /// The verifier only requires the commitments in order to verify a
     /// [`Proof`](super::Proof) We can therefore speed up preprocessing for the
     /// verifier by skipping the FFTs needed to compute the 4n evaluations.
@@ -244,7 +316,7 @@ where
         ),
         Error,
     > {
-        let domain = GeneralEvaluationDomain::new(self.circuit_size()).unwrap();
+        let domain = util::domain(self.circuit_size())?;
 
         // Check that the length of the wires is consistent.
         self.check_poly_same_len()?;
@@ -252,40 +324,39 @@ where
         // 1. Pad circuit to a power of two
         self.pad(domain.size() as usize - self.n);
 
-        let q_m_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_m),
-        };
-        let q_r_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_r),
-        };
-        let q_l_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_l),
-        };
-        let q_o_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_o),
-        };
-        let q_c_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_c),
-        };
-        let q_4_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_4),
-        };
-        let q_arith_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_arith),
-        };
-        let q_range_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_range),
-        };
-        let q_logic_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_logic),
-        };
-        let q_fixed_group_add_poly: DensePolynomial<E::Fr> = DensePolynomial {
-            coeffs: domain.ifft(&self.q_fixed_group_add),
-        };
+        // Interpolations are trimmed like the sigma polynomials, since a
+        // selector can have a zero leading coefficient, which `KZG10::commit`
+        // rejects with a panic when computing the degree.
+        let q_m_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_m));
+        let q_r_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_r));
+        let q_l_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_l));
+        let q_o_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_o));
+        let q_c_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_c));
+        let q_4_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_4));
+        let q_arith_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_arith));
+        let q_range_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_range));
+        let q_logic_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(domain.ifft(&self.q_logic));
+        let q_fixed_group_add_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(
+                domain.ifft(&self.q_fixed_group_add),
+            );
         let q_variable_group_add_poly: DensePolynomial<E::Fr> =
-            DensePolynomial {
-                coeffs: domain.ifft(&self.q_variable_group_add),
-            };
+            DensePolynomial::from_coefficients_vec(
+                domain.ifft(&self.q_variable_group_add),
+            );
+        let q_variable_group_double_poly: DensePolynomial<E::Fr> =
+            DensePolynomial::from_coefficients_vec(
+                domain.ifft(&self.q_variable_group_double),
+            );
 
         // 2. Compute the sigma polynomials
         let (
@@ -356,6 +427,14 @@ where
                 None,
             )?;
 
+        let q_variable_group_double_poly_commit =
+            KZG10::<E, DensePolynomial<E::Fr>>::commit(
+                commit_key,
+                &q_variable_group_double_poly,
+                None,
+                None,
+            )?;
+
         let left_sigma_poly_commit =
             KZG10::<E, DensePolynomial<E::Fr>>::commit(
                 commit_key,
@@ -400,6 +479,7 @@ where
             q_logic_poly_commit.0,
             q_fixed_group_add_poly_commit.0,
             q_variable_group_add_poly_commit.0,
+            q_variable_group_double_poly_commit.0,
             left_sigma_poly_commit.0,
             right_sigma_poly_commit.0,
             out_sigma_poly_commit.0,
@@ -418,6 +498,7 @@ where
             q_logic: q_logic_poly,
             q_fixed_group_add: q_fixed_group_add_poly,
             q_variable_group_add: q_variable_group_add_poly,
+            q_variable_group_double: q_variable_group_double_poly,
             left_sigma: left_sigma_poly,
             right_sigma: right_sigma_poly,
             out_sigma: out_sigma_poly,
@@ -462,7 +543,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{batch_test, constraint_system::helper::*};
+    use crate::{batch_test, testing::*};
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
 
@@ -492,14 +573,40 @@ mod test {
         assert!(composer.q_logic.len() == size);
         assert!(composer.q_fixed_group_add.len() == size);
         assert!(composer.q_variable_group_add.len() == size);
+        assert!(composer.q_variable_group_double.len() == size);
         assert!(composer.w_l.len() == size);
         assert!(composer.w_r.len() == size);
         assert!(composer.w_o.len() == size);
     }
 
+    /// Tests that a selector with a zero leading coefficient can be committed.
+    fn test_selector_with_zero_leading_coefficient<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        gadget_tester(
+            |composer: &mut StandardComposer<E, P>| {
+                // The first three gates have `q_c = [0, 4, 127]`, so a fourth
+                // constant gate with `q_c = 4 - 127 / ω` cancels the `X^3`
+                // coefficient of the interpolation.
+                let domain = util::domain::<E::Fr>(4).unwrap();
+                let constant =
+                    E::Fr::from(127u64) / domain.element(1) - E::Fr::from(4u64);
+                let var = composer.add_input(constant);
+                composer.constrain_to_constant(var, constant, None);
+
+                assert_eq!(composer.circuit_size(), 4);
+                assert!(domain.ifft(&composer.q_c)[3].is_zero());
+            },
+            4,
+        )
+        .expect("Circuit with a zero leading coefficient is provable");
+    }
+
     // Bls12-381 tests
     batch_test!(
-        [test_pad],
+        [test_pad, test_selector_with_zero_leading_coefficient],
         [] => (
             Bls12_381,
             ark_ed_on_bls12_381::EdwardsParameters
@@ -508,7 +615,7 @@ mod test {
 
     // Bls12-377 tests
     batch_test!(
-        [test_pad],
+        [test_pad, test_selector_with_zero_leading_coefficient],
         [] => (
             Bls12_377,
             ark_ed_on_bls12_377::EdwardsParameters
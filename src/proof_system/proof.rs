@@ -13,6 +13,7 @@
 //! `Proof` structure and it's methods.
 
 use crate::proof_system::ecc::CurveAddition;
+use crate::proof_system::ecc::CurveDoubling;
 use crate::proof_system::ecc::FixedBaseScalarMul;
 use crate::proof_system::linearisation_poly::ProofEvaluations;
 use crate::proof_system::logic::Logic;
@@ -23,6 +24,7 @@ use crate::transcript::TranscriptProtocol;
 use crate::util;
 use crate::util::EvaluationDomainExt;
 use crate::{error::Error, transcript::TranscriptWrapper};
+use alloc::vec::Vec;
 use ark_ec::{msm::VariableBaseMSM, AffineCurve, TEModelParameters};
 use ark_ec::{PairingEngine, ProjectiveCurve};
 use ark_ff::{fields::batch_inversion, Field, PrimeField};
@@ -34,7 +36,55 @@ use ark_serialize::{
     CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write,
 };
 use core::marker::PhantomData;
-use rand_core::OsRng;
+use num_traits::Zero;
+use rand_core::{CryptoRng, RngCore};
+
+/// Variant of the PLONK protocol used to create and verify a [`Proof`].
+///
+/// The mode is fixed per [`ProverKey`](super::ProverKey) and
+/// [`VerifierKey`](super::VerifierKey), and both must agree.
+///
+/// Compact proofs only leave out the evaluation of the linearisation
+/// polynomial, and open it together with the quotient polynomial. The
+/// selector evaluations are still sent in both modes, since the custom gates
+/// multiply them with other selectors that can't be linearised.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ProofMode {
+    /// The proof carries the evaluation of the linearisation polynomial and
+    /// the verifier recomputes the evaluation of the quotient polynomial.
+    #[default]
+    Standard,
+
+    /// The verifier folds the quotient chunks, merged with powers of `z^n`,
+    /// into the linearisation commitment, whose evaluation it derives itself
+    /// (Maller's optimisation). Proofs are one scalar smaller and open one
+    /// polynomial less at `z`.
+    Compact,
+}
+
+impl CanonicalSerialize for ProofMode {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        let tag: u8 = match self {
+            Self::Standard => 0,
+            Self::Compact => 1,
+        };
+        tag.serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        1
+    }
+}
+
+impl CanonicalDeserialize for ProofMode {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        match u8::deserialize(reader)? {
+            0 => Ok(Self::Standard),
+            1 => Ok(Self::Compact),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
 
 /// A Proof is a composition of `Commitment`s to the Witness, Permutation,
 /// Quotient, Shifted and Opening polynomials as well as the
@@ -46,7 +96,12 @@ use rand_core::OsRng;
 /// [`Verifier`](super::Verifier) have in common succintly and without any
 /// capabilities of adquiring any kind of knowledge about the witness used to
 /// construct the Proof.
-#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+///
+/// [`ProofMode::Standard`] proofs are serialized as their commitments followed
+/// by their [`ProofEvaluations`]. [`ProofMode::Compact`] proofs leave out the
+/// linearisation evaluation, and are deserialized with
+/// [`Proof::deserialize_with_mode`].
+#[derive(derivative::Derivative)]
 #[derivative(
     Clone(bound = ""),
     Debug(bound = ""),
@@ -95,26 +150,270 @@ where
     /// Subset of all of the evaluations added to the proof.
     pub(crate) evaluations: ProofEvaluations<E::Fr>,
 
+    /// Variant of the protocol used to create the proof.
+    pub(crate) mode: ProofMode,
+
     /// Type Parameter Marker
     pub(crate) __: PhantomData<P>,
 }
 
+impl<E, P> CanonicalSerialize for Proof<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    fn serialize<W: Write>(
+        &self,
+        mut writer: W,
+    ) -> Result<(), SerializationError> {
+        self.write(
+            &mut writer,
+            |comm, writer| comm.serialize(writer),
+            |eval, writer| eval.serialize(writer),
+        )
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.commitments()
+            .iter()
+            .map(|comm| comm.serialized_size())
+            .chain(
+                self.serialized_evaluations()
+                    .iter()
+                    .map(|eval| eval.serialized_size()),
+            )
+            .sum()
+    }
+
+    fn serialize_uncompressed<W: Write>(
+        &self,
+        mut writer: W,
+    ) -> Result<(), SerializationError> {
+        self.write(
+            &mut writer,
+            |comm, writer| comm.serialize_uncompressed(writer),
+            |eval, writer| eval.serialize_uncompressed(writer),
+        )
+    }
+
+    fn serialize_unchecked<W: Write>(
+        &self,
+        mut writer: W,
+    ) -> Result<(), SerializationError> {
+        self.write(
+            &mut writer,
+            |comm, writer| comm.serialize_unchecked(writer),
+            |eval, writer| eval.serialize_unchecked(writer),
+        )
+    }
+
+    fn uncompressed_size(&self) -> usize {
+        self.commitments()
+            .iter()
+            .map(|comm| comm.uncompressed_size())
+            .chain(
+                self.serialized_evaluations()
+                    .iter()
+                    .map(|eval| eval.uncompressed_size()),
+            )
+            .sum()
+    }
+}
+
+/// Deserializes [`ProofMode::Standard`] proofs.
+impl<E, P> CanonicalDeserialize for Proof<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Self::deserialize_with_mode(reader, ProofMode::Standard)
+    }
+
+    fn deserialize_uncompressed<R: Read>(
+        mut reader: R,
+    ) -> Result<Self, SerializationError> {
+        Self::read(
+            &mut reader,
+            ProofMode::Standard,
+            |reader| Commitment::deserialize_uncompressed(reader),
+            |reader| E::Fr::deserialize_uncompressed(reader),
+        )
+    }
+
+    fn deserialize_unchecked<R: Read>(
+        mut reader: R,
+    ) -> Result<Self, SerializationError> {
+        Self::read(
+            &mut reader,
+            ProofMode::Standard,
+            |reader| Commitment::deserialize_unchecked(reader),
+            |reader| E::Fr::deserialize_unchecked(reader),
+        )
+    }
+}
+
 impl<E, P> Proof<E, P>
 where
     E: PairingEngine,
     P: TEModelParameters<BaseField = E::Fr>,
 {
+    /// Returns the [`ProofMode`] the proof was created in.
+    pub fn mode(&self) -> ProofMode {
+        self.mode
+    }
+
+    /// Deserializes a proof created in `mode`, which is found in the
+    /// [`VerifierKey`](super::VerifierKey) checking it.
+    pub fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        mode: ProofMode,
+    ) -> Result<Self, SerializationError> {
+        Self::read(
+            &mut reader,
+            mode,
+            |reader| Commitment::deserialize(reader),
+            |reader| E::Fr::deserialize(reader),
+        )
+    }
+
+    /// Returns the commitments of the proof, in serialization order.
+    fn commitments(&self) -> [&Commitment<E>; 11] {
+        [
+            &self.a_comm,
+            &self.b_comm,
+            &self.c_comm,
+            &self.d_comm,
+            &self.z_comm,
+            &self.t_1_comm,
+            &self.t_2_comm,
+            &self.t_3_comm,
+            &self.t_4_comm,
+            &self.w_z_comm,
+            &self.w_zw_comm,
+        ]
+    }
+
+    /// Returns the evaluations of the proof, in serialization order.
+    fn serialized_evaluations(&self) -> Vec<E::Fr> {
+        let evals = &self.evaluations;
+        let mut serialized = vec![
+            evals.a_eval,
+            evals.b_eval,
+            evals.c_eval,
+            evals.d_eval,
+            evals.a_next_eval,
+            evals.b_next_eval,
+            evals.d_next_eval,
+            evals.q_arith_eval,
+            evals.q_c_eval,
+            evals.q_l_eval,
+            evals.q_r_eval,
+            evals.left_sigma_eval,
+            evals.right_sigma_eval,
+            evals.out_sigma_eval,
+        ];
+        if self.mode == ProofMode::Standard {
+            serialized.push(evals.linearisation_polynomial_eval);
+        }
+        serialized.push(evals.permutation_eval);
+        serialized
+    }
+
+    /// Writes the commitments and evaluations of the proof with the given
+    /// functions.
+    fn write<W: Write>(
+        &self,
+        writer: &mut W,
+        write_commitment: impl Fn(
+            &Commitment<E>,
+            &mut W,
+        ) -> Result<(), SerializationError>,
+        write_scalar: impl Fn(&E::Fr, &mut W) -> Result<(), SerializationError>,
+    ) -> Result<(), SerializationError> {
+        for comm in self.commitments() {
+            write_commitment(comm, writer)?;
+        }
+        for eval in &self.serialized_evaluations() {
+            write_scalar(eval, writer)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a proof created in `mode` with the given functions.
+    fn read<R: Read>(
+        reader: &mut R,
+        mode: ProofMode,
+        mut read_commitment: impl FnMut(
+            &mut R,
+        )
+            -> Result<Commitment<E>, SerializationError>,
+        mut read_scalar: impl FnMut(&mut R) -> Result<E::Fr, SerializationError>,
+    ) -> Result<Self, SerializationError> {
+        let mut comms = Vec::with_capacity(11);
+        for _ in 0..11 {
+            comms.push(read_commitment(reader)?);
+        }
+        let mut evals = Vec::with_capacity(16);
+        for _ in 0..14 {
+            evals.push(read_scalar(reader)?);
+        }
+        let linearisation_polynomial_eval = match mode {
+            ProofMode::Standard => read_scalar(reader)?,
+            ProofMode::Compact => E::Fr::zero(),
+        };
+        let permutation_eval = read_scalar(reader)?;
+        Ok(Self {
+            a_comm: comms[0],
+            b_comm: comms[1],
+            c_comm: comms[2],
+            d_comm: comms[3],
+            z_comm: comms[4],
+            t_1_comm: comms[5],
+            t_2_comm: comms[6],
+            t_3_comm: comms[7],
+            t_4_comm: comms[8],
+            w_z_comm: comms[9],
+            w_zw_comm: comms[10],
+            evaluations: ProofEvaluations {
+                a_eval: evals[0],
+                b_eval: evals[1],
+                c_eval: evals[2],
+                d_eval: evals[3],
+                a_next_eval: evals[4],
+                b_next_eval: evals[5],
+                d_next_eval: evals[6],
+                q_arith_eval: evals[7],
+                q_c_eval: evals[8],
+                q_l_eval: evals[9],
+                q_r_eval: evals[10],
+                left_sigma_eval: evals[11],
+                right_sigma_eval: evals[12],
+                out_sigma_eval: evals[13],
+                linearisation_polynomial_eval,
+                permutation_eval,
+            },
+            mode,
+            __: PhantomData,
+        })
+    }
+
     /// Performs the verification of a [`Proof`] returning a boolean result.
-    pub(crate) fn verify(
+    ///
+    /// The two openings are checked in a single batch combined with
+    /// randomness drawn from `rng`, which must be unpredictable to the prover.
+    pub(crate) fn verify<R>(
         &self,
         plonk_verifier_key: &PlonkVerifierKey<E, P>,
         transcript: &mut TranscriptWrapper<E>,
         verifier_key: &VerifierKey<E>,
         pub_inputs: &[E::Fr],
-    ) -> Result<(), Error> {
-        let domain =
-            GeneralEvaluationDomain::<E::Fr>::new(plonk_verifier_key.n)
-                .unwrap();
+        rng: &mut R,
+    ) -> Result<(), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let domain = util::domain::<E::Fr>(plonk_verifier_key.n)?;
 
         // Subgroup checks are done when the proof is deserialised.
 
@@ -136,7 +435,9 @@ where
         transcript.append_scalar(b"beta", &beta);
         let gamma = transcript.challenge_scalar(b"gamma");
 
-        assert!(beta != gamma, "challenges must be different");
+        if beta == gamma {
+            return Err(Error::EqualChallenges);
+        }
 
         // Add commitment to permutation polynomial to transcript
         transcript.append_commitment(b"z", &self.z_comm);
@@ -166,27 +467,21 @@ where
 
         // Compute first lagrange polynomial evaluated at `z_challenge`
         let l1_eval =
-            compute_first_lagrange_evaluation(&domain, &z_h_eval, &z_challenge);
+            compute_first_lagrange_evaluation(&domain, &z_h_eval, &z_challenge)
+                .ok_or(Error::ProofVerificationError)?;
 
-        // Compute quotient polynomial evaluated at `z_challenge`
-        let t_eval = self.compute_quotient_evaluation(
+        // Compute `r(z) - Z_H(z) * t(z)` from the wire evaluations
+        let r_offset = self.compute_linearisation_offset(
             &domain,
             pub_inputs,
             alpha,
             beta,
             gamma,
             z_challenge,
-            z_h_eval,
             l1_eval,
             self.evaluations.permutation_eval,
         );
 
-        // Compute commitment to quotient polynomial
-        // This method is necessary as we pass the `un-splitted` variation
-        // to our commitment scheme
-        let t_comm =
-            self.compute_quotient_commitment(&z_challenge, domain.size());
-
         // Add evaluations to transcript
         transcript.append_scalar(b"a_eval", &self.evaluations.a_eval);
         transcript.append_scalar(b"b_eval", &self.evaluations.b_eval);
@@ -210,14 +505,9 @@ where
         transcript.append_scalar(b"q_r_eval", &self.evaluations.q_r_eval);
         transcript
             .append_scalar(b"perm_eval", &self.evaluations.permutation_eval);
-        transcript.append_scalar(b"t_eval", &t_eval);
-        transcript.append_scalar(
-            b"r_eval",
-            &self.evaluations.linearisation_polynomial_eval,
-        );
 
-        // Compute linearisation commitment
-        let r_comm = self.compute_linearisation_commitment(
+        // Compute the terms of the linearisation commitment
+        let (mut scalars, mut points) = self.linearisation_commitment_terms(
             alpha,
             beta,
             gamma,
@@ -230,6 +520,46 @@ where
             plonk_verifier_key,
         );
 
+        if self.mode != plonk_verifier_key.mode {
+            return Err(Error::ProofVerificationError);
+        }
+        let opening_terms = match self.mode {
+            ProofMode::Standard => {
+                let r_eval = self.evaluations.linearisation_polynomial_eval;
+                // Compute quotient polynomial evaluated at `z_challenge`
+                let t_eval = (r_eval - r_offset)
+                    * z_h_eval
+                        .inverse()
+                        .ok_or(Error::ProofVerificationError)?;
+
+                // Compute commitment to quotient polynomial
+                // This method is necessary as we pass the `un-splitted`
+                // variation to our commitment scheme
+                let t_comm = self
+                    .compute_quotient_commitment(&z_challenge, domain.size());
+
+                transcript.append_scalar(b"t_eval", &t_eval);
+                transcript.append_scalar(b"r_eval", &r_eval);
+
+                let r_comm = msm::<E>(&points, &scalars);
+                vec![(t_eval, t_comm), (r_eval, r_comm)]
+            }
+            ProofMode::Compact => {
+                // Subtract the quotient chunks merged with powers of `z^n`
+                // so that `r(z)` is known to the verifier.
+                let z_n = z_challenge.pow([domain.size() as u64, 0, 0, 0]);
+                let mut scalar = -z_h_eval;
+                for t_comm in
+                    [self.t_1_comm, self.t_2_comm, self.t_3_comm, self.t_4_comm]
+                {
+                    scalars.push(scalar);
+                    points.push(t_comm.0);
+                    scalar *= z_n;
+                }
+                vec![(r_offset, msm::<E>(&points, &scalars))]
+            }
+        };
+
         // Commitment Scheme
         // Now we delegate computation to the commitment scheme by batch
         // checking two proofs.
@@ -247,9 +577,7 @@ where
         // The proof consists of the witness commitment with no blinder
         let (aggregate_proof_commitment, aggregate_proof_eval) = self
             .gen_aggregate_proof(
-                t_eval,
-                t_comm,
-                r_comm,
+                &opening_terms,
                 plonk_verifier_key,
                 transcript,
             );
@@ -280,49 +608,44 @@ where
             &[z_challenge, (z_challenge * group_gen)],
             &[aggregate_proof_eval, aggregate_shift_proof_eval],
             &[aggregate_proof, aggregate_shift_proof],
-            &mut OsRng,
+            rng,
         ) {
             Ok(true) => Ok(()),
             Ok(false) => Err(Error::ProofVerificationError),
-            Err(e) => panic!("{:?}", e),
+            Err(e) => Err(e.into()),
         }
     }
 
-    // TODO: Doc this
+    /// Aggregates the evaluations at `z` and their commitments, starting
+    /// with the mode dependent `leading_terms`, into a single opening.
     fn gen_aggregate_proof(
         &self,
-        t_eval: E::Fr,
-        t_comm: Commitment<E>,
-        r_comm: Commitment<E>,
+        leading_terms: &[(E::Fr, Commitment<E>)],
         plonk_verifier_key: &PlonkVerifierKey<E, P>,
         transcript: &mut TranscriptWrapper<E>,
     ) -> (Commitment<E>, E::Fr) {
         let challenge = transcript.challenge_scalar(b"aggregate_witness");
-        util::linear_combination(
-            &[
-                t_eval,
-                self.evaluations.linearisation_polynomial_eval,
-                self.evaluations.a_eval,
-                self.evaluations.b_eval,
-                self.evaluations.c_eval,
-                self.evaluations.d_eval,
-                self.evaluations.left_sigma_eval,
-                self.evaluations.right_sigma_eval,
-                self.evaluations.out_sigma_eval,
-            ],
-            &[
-                t_comm,
-                r_comm,
-                self.a_comm,
-                self.b_comm,
-                self.c_comm,
-                self.d_comm,
-                plonk_verifier_key.permutation.left_sigma,
-                plonk_verifier_key.permutation.right_sigma,
-                plonk_verifier_key.permutation.out_sigma,
-            ],
-            challenge,
-        )
+        let (mut evals, mut comms): (Vec<_>, Vec<_>) =
+            leading_terms.iter().copied().unzip();
+        evals.extend([
+            self.evaluations.a_eval,
+            self.evaluations.b_eval,
+            self.evaluations.c_eval,
+            self.evaluations.d_eval,
+            self.evaluations.left_sigma_eval,
+            self.evaluations.right_sigma_eval,
+            self.evaluations.out_sigma_eval,
+        ]);
+        comms.extend([
+            self.a_comm,
+            self.b_comm,
+            self.c_comm,
+            self.d_comm,
+            plonk_verifier_key.permutation.left_sigma,
+            plonk_verifier_key.permutation.right_sigma,
+            plonk_verifier_key.permutation.out_sigma,
+        ]);
+        util::linear_combination(&evals, &comms, challenge)
     }
 
     // TODO: Doc this
@@ -343,8 +666,10 @@ where
         )
     }
 
-    // TODO: Doc this
-    fn compute_quotient_evaluation(
+    /// Computes `r(z) - Z_H(z) * t(z)`, the part of the constraint
+    /// polynomial evaluated at `z` which is not covered by the linearisation
+    /// polynomial.
+    fn compute_linearisation_offset(
         &self,
         domain: &GeneralEvaluationDomain<E::Fr>,
         pub_inputs: &[E::Fr],
@@ -352,7 +677,6 @@ where
         beta: E::Fr,
         gamma: E::Fr,
         z_challenge: E::Fr,
-        z_h_eval: E::Fr,
         l1_eval: E::Fr,
         z_hat_eval: E::Fr,
     ) -> E::Fr {
@@ -360,8 +684,6 @@ where
         let pi_eval = compute_barycentric_eval(pub_inputs, z_challenge, domain);
 
         let alpha_sq = alpha.square();
-        // r + PI(z)
-        let a = self.evaluations.linearisation_polynomial_eval + pi_eval;
 
         // a + beta * sigma_1 + gamma
         let beta_sig1 = beta * self.evaluations.left_sigma_eval;
@@ -383,8 +705,7 @@ where
         // l_1(z) * alpha^2
         let c = l1_eval * alpha_sq;
 
-        // Return t_eval
-        (a - b - c) * z_h_eval.inverse().unwrap()
+        b + c - pi_eval
     }
 
     /// Computes the quotient polynomial commitment at `z_challenge`.
@@ -404,8 +725,10 @@ where
         Commitment(t_comm.into_affine())
     }
 
-    /// Computes the commitment to `[r]_1`.
-    fn compute_linearisation_commitment(
+    /// Computes the scalars and points whose multi-scalar multiplication is
+    /// the commitment to `[r]_1`.
+    #[allow(clippy::type_complexity)]
+    fn linearisation_commitment_terms(
         &self,
         alpha: E::Fr,
         beta: E::Fr,
@@ -417,7 +740,7 @@ where
         z_challenge: E::Fr,
         l1_eval: E::Fr,
         plonk_verifier_key: &PlonkVerifierKey<E, P>,
-    ) -> Commitment<E> {
+    ) -> (Vec<E::Fr>, Vec<E::G1Affine>) {
         let mut scalars = Vec::with_capacity(6);
         let mut points = Vec::with_capacity(6);
 
@@ -461,6 +784,14 @@ where
             &mut points,
         );
 
+        CurveDoubling::<_, P>::extend_linearisation_commitment::<E>(
+            plonk_verifier_key.variable_group_double_selector_commitment,
+            var_base_sep_challenge,
+            &self.evaluations,
+            &mut scalars,
+            &mut points,
+        );
+
         plonk_verifier_key
             .permutation
             .compute_linearisation_commitment(
@@ -473,15 +804,19 @@ where
                 self.z_comm.0,
             );
 
-        let scalars_repr =
-            scalars.iter().map(E::Fr::into_repr).collect::<Vec<_>>();
-
-        Commitment(
-            VariableBaseMSM::multi_scalar_mul(&points, &scalars_repr).into(),
-        )
+        (scalars, points)
     }
 }
 
+/// Computes the commitment `sum(scalars[i] * points[i])`.
+fn msm<E>(points: &[E::G1Affine], scalars: &[E::Fr]) -> Commitment<E>
+where
+    E: PairingEngine,
+{
+    let scalars_repr = scalars.iter().map(E::Fr::into_repr).collect::<Vec<_>>();
+    Commitment(VariableBaseMSM::multi_scalar_mul(points, &scalars_repr).into())
+}
+
 /// The first lagrange polynomial has the expression:
 ///
 /// ```text
@@ -498,17 +833,19 @@ where
 /// ```text
 /// L_0(X) = (X^n - 1) / n * (X - 1)
 /// ```
+///
+/// Returns `None` if `z_challenge` is one.
 fn compute_first_lagrange_evaluation<F>(
     domain: &GeneralEvaluationDomain<F>,
     z_h_eval: &F,
     z_challenge: &F,
-) -> F
+) -> Option<F>
 where
     F: PrimeField,
 {
     let n_fr = F::from(domain.size() as u64);
     let denom = n_fr * (*z_challenge - F::one());
-    *z_h_eval * denom.inverse().unwrap()
+    Some(*z_h_eval * denom.inverse()?)
 }
 
 fn compute_barycentric_eval<F>(
@@ -562,6 +899,9 @@ mod test {
     use ark_bls12_377::Bls12_377;
     use ark_bls12_381::Bls12_381;
     use ark_ff::UniformRand;
+    use num_traits::{One, Zero};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
     use rand_core::OsRng;
 
     fn test_serde_proof<E, P>()
@@ -599,6 +939,7 @@ mod test {
                 linearisation_polynomial_eval: E::Fr::rand(&mut OsRng),
                 permutation_eval: E::Fr::rand(&mut OsRng),
             },
+            mode: ProofMode::Standard,
             __: PhantomData,
         };
 
@@ -609,11 +950,121 @@ mod test {
             Proof::deserialize(proof_bytes.as_slice()).unwrap();
 
         assert!(proof == obtained_proof);
+
+        // Compact proofs are read back with the mode of their key
+        let mut compact_proof = proof.clone();
+        compact_proof.mode = ProofMode::Compact;
+        compact_proof.evaluations.linearisation_polynomial_eval = E::Fr::zero();
+        let mut compact_bytes = vec![];
+        compact_proof.serialize(&mut compact_bytes).unwrap();
+        assert_eq!(compact_bytes.len(), compact_proof.serialized_size());
+        assert_eq!(
+            proof_bytes.len() - compact_bytes.len(),
+            E::Fr::zero().serialized_size()
+        );
+
+        let obtained_proof = Proof::deserialize_with_mode(
+            compact_bytes.as_slice(),
+            ProofMode::Compact,
+        )
+        .unwrap();
+        assert!(compact_proof == obtained_proof);
+    }
+
+    fn test_proof_modes<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        use crate::proof_system::{Prover, Verifier};
+        use crate::setup::PublicParameters;
+        use crate::testing::dummy_gadget;
+
+        let pp = PublicParameters::<E>::setup(1 << 6, &mut OsRng).unwrap();
+        let (powers, vk) = pp.trim(1 << 5).unwrap();
+
+        let mut verifier = Verifier::<E, P>::new(b"modes");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&powers).unwrap();
+        let pi = vec![E::Fr::zero(); verifier.circuit_size()];
+
+        let mut sizes = Vec::new();
+        for mode in [ProofMode::Standard, ProofMode::Compact] {
+            let mut prover = Prover::<E, P>::new(b"modes");
+            dummy_gadget(10, prover.mut_cs());
+            prover.preprocess(&powers).unwrap();
+            prover.prover_key.as_mut().unwrap().set_mode(mode);
+            let proof = prover.prove(&powers).unwrap();
+            sizes.push(proof.serialized_size());
+
+            // Only a verifier key of the same mode accepts the proof
+            for verifier_mode in [ProofMode::Standard, ProofMode::Compact] {
+                verifier
+                    .verifier_key
+                    .as_mut()
+                    .unwrap()
+                    .set_mode(verifier_mode);
+                assert_eq!(
+                    verifier.verify(&proof, &vk, &pi).is_ok(),
+                    mode == verifier_mode
+                );
+            }
+        }
+        assert_eq!(sizes[0] - sizes[1], E::Fr::zero().serialized_size());
+    }
+
+    fn test_malformed_proofs<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        use crate::proof_system::{Prover, Verifier};
+        use crate::setup::PublicParameters;
+        use crate::testing::dummy_gadget;
+
+        let pp = PublicParameters::<E>::setup(1 << 5, &mut OsRng).unwrap();
+        let (powers, vk) = pp.trim(1 << 4).unwrap();
+
+        let mut prover = Prover::<E, P>::new(b"malformed");
+        dummy_gadget(5, prover.mut_cs());
+        let proof = prover.prove(&powers).unwrap();
+        let mut bytes = Vec::new();
+        proof.serialize(&mut bytes).unwrap();
+
+        // An unpreprocessed verifier refuses to verify
+        let mut verifier = Verifier::<E, P>::new(b"malformed");
+        assert!(matches!(
+            verifier.verify(&proof, &vk, &[]),
+            Err(Error::UninitializedVerifierKey)
+        ));
+        dummy_gadget(5, verifier.mut_cs());
+        verifier.preprocess(&powers).unwrap();
+        let pi = vec![E::Fr::zero(); verifier.circuit_size()];
+        verifier.verify(&proof, &vk, &pi).unwrap();
+        verifier
+            .verify_with_rng(&proof, &vk, &pi, &mut StdRng::seed_from_u64(0))
+            .unwrap();
+
+        // Truncated and corrupted proofs are rejected without panicking
+        for len in 0..bytes.len() {
+            assert!(Proof::<E, P>::deserialize(&bytes[..len]).is_err());
+        }
+        for i in (0..bytes.len()).step_by(7) {
+            let mut corrupted = bytes.clone();
+            corrupted[i] ^= 1 << (i % 8);
+            if let Ok(proof) = Proof::<E, P>::deserialize(&corrupted[..]) {
+                assert!(verifier.verify(&proof, &vk, &pi).is_err());
+            }
+        }
+
+        // So are wrong and oversized public inputs
+        assert!(verifier.verify(&proof, &vk, &[E::Fr::one()]).is_err());
+        assert!(verifier.verify(&proof, &vk, &[E::Fr::one(); 40]).is_err());
     }
 
     // Bls12-381 tests
     batch_test!(
-        [test_serde_proof],
+        [test_serde_proof, test_proof_modes, test_malformed_proofs],
         [] => (
             Bls12_381,
             ark_ed_on_bls12_381::EdwardsParameters
@@ -622,7 +1073,7 @@ mod test {
 
     // Bls12-377 tests
     batch_test!(
-        [test_serde_proof],
+        [test_serde_proof, test_proof_modes, test_malformed_proofs],
         [] => (
             Bls12_377,
             ark_ed_on_bls12_377::EdwardsParameters
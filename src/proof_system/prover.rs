@@ -12,16 +12,22 @@ use crate::{
     constraint_system::{StandardComposer, Variable},
     error::Error,
     proof_system::{
-        linearisation_poly, proof::Proof, quotient_poly, ProverKey,
+        linearisation_poly,
+        low_memory::MemoryMeter,
+        proof::{Proof, ProofMode},
+        quotient_poly, LowMemoryProverKey, MemoryReport, PolynomialId,
+        PolynomialStore, ProverKey,
     },
     transcript::{TranscriptProtocol, TranscriptWrapper},
     util,
 };
+#[cfg(feature = "parallel")]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use ark_ec::{PairingEngine, TEModelParameters};
 use ark_ff::Field;
 use ark_poly::{
-    univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
-    UVPolynomial,
+    univariate::DensePolynomial, EvaluationDomain, Polynomial, UVPolynomial,
 };
 use ark_poly_commit::kzg10::{Powers, KZG10};
 use core::marker::PhantomData;
@@ -46,6 +52,10 @@ where
     ///
     /// This is copied each time, we make a proof.
     pub preprocessed_transcript: TranscriptWrapper<E>,
+
+    /// Thread pool running the proving work, the global rayon pool if `None`.
+    #[cfg(feature = "parallel")]
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl<E, P> Prover<E, P>
@@ -59,6 +69,8 @@ where
             prover_key: None,
             cs: StandardComposer::new(),
             preprocessed_transcript: TranscriptWrapper::new(label),
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
         }
     }
 
@@ -68,7 +80,29 @@ where
             prover_key: None,
             cs: StandardComposer::with_expected_size(size),
             preprocessed_transcript: TranscriptWrapper::new(label),
+            #[cfg(feature = "parallel")]
+            thread_pool: None,
+        }
+    }
+
+    /// Runs the proving work of this `Prover` on `thread_pool`, bounding the
+    /// number of threads used for each proof.
+    #[cfg(feature = "parallel")]
+    pub fn set_thread_pool(&mut self, thread_pool: Arc<rayon::ThreadPool>) {
+        self.thread_pool = Some(thread_pool);
+    }
+
+    /// Runs `op` on the thread pool of this `Prover`.
+    fn install<R, OP>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        #[cfg(feature = "parallel")]
+        if let Some(thread_pool) = &self.thread_pool {
+            return thread_pool.install(op);
         }
+        op()
     }
 
     /// Returns a mutable copy of the underlying [`StandardComposer`].
@@ -197,8 +231,31 @@ where
         commit_key: &Powers<E>,
         prover_key: &ProverKey<E::Fr, P>,
     ) -> Result<Proof<E, P>, Error> {
-        let domain =
-            GeneralEvaluationDomain::new(self.cs.circuit_size()).unwrap();
+        self.install(|| {
+            self.prove_inner(
+                commit_key,
+                prover_key,
+                Some(prover_key),
+                &MemoryMeter::default(),
+            )
+        })
+    }
+
+    /// Creates a [`Proof`] from the preprocessed polynomials in `store`.
+    ///
+    /// The quotient polynomial is computed from the `4n` evaluations of
+    /// `coset_key` when given, and coset-by-coset otherwise.
+    fn prove_inner<S>(
+        &self,
+        commit_key: &Powers<E>,
+        store: &S,
+        coset_key: Option<&ProverKey<E::Fr, P>>,
+        meter: &MemoryMeter,
+    ) -> Result<Proof<E, P>, Error>
+    where
+        S: PolynomialStore<E::Fr>,
+    {
+        let domain = util::domain(self.cs.circuit_size())?;
 
         // Since the caller is passing a pre-processed circuit
         // We assume that the Transcript has been seeded with the preprocessed
@@ -214,23 +271,35 @@ where
         let w_r_scalar = &[&self.to_scalars(&self.cs.w_r)[..], &pad].concat();
         let w_o_scalar = &[&self.to_scalars(&self.cs.w_o)[..], &pad].concat();
         let w_4_scalar = &[&self.to_scalars(&self.cs.w_4)[..], &pad].concat();
+        let _w_scalars = meter.track::<E::Fr>(4 * domain.size());
 
         // Witnesses are now in evaluation form, convert them to coefficients
-        // so that we may commit to them.
-        let w_l_poly =
-            DensePolynomial::from_coefficients_vec(domain.ifft(w_l_scalar));
-        let w_r_poly =
-            DensePolynomial::from_coefficients_vec(domain.ifft(w_r_scalar));
-        let w_o_poly =
-            DensePolynomial::from_coefficients_vec(domain.ifft(w_o_scalar));
-        let w_4_poly =
-            DensePolynomial::from_coefficients_vec(domain.ifft(w_4_scalar));
-
-        // Commit to witness polynomials.
-        let w_l_poly_commit = KZG10::commit(commit_key, &w_l_poly, None, None)?;
-        let w_r_poly_commit = KZG10::commit(commit_key, &w_r_poly, None, None)?;
-        let w_o_poly_commit = KZG10::commit(commit_key, &w_o_poly, None, None)?;
-        let w_4_poly_commit = KZG10::commit(commit_key, &w_4_poly, None, None)?;
+        // so that we may commit to them, each wire independently.
+        let commit_wire = |scalars: &[E::Fr]| -> Result<_, Error> {
+            let poly =
+                DensePolynomial::from_coefficients_vec(domain.ifft(scalars));
+            let commit = KZG10::commit(commit_key, &poly, None, None)?;
+            Ok((poly, commit))
+        };
+        let ((w_l, w_r), (w_o, w_4)) = util::join(
+            || {
+                util::join(
+                    || commit_wire(w_l_scalar),
+                    || commit_wire(w_r_scalar),
+                )
+            },
+            || {
+                util::join(
+                    || commit_wire(w_o_scalar),
+                    || commit_wire(w_4_scalar),
+                )
+            },
+        );
+        let (w_l_poly, w_l_poly_commit) = w_l?;
+        let (w_r_poly, w_r_poly_commit) = w_r?;
+        let (w_o_poly, w_o_poly_commit) = w_o?;
+        let (w_4_poly, w_4_poly_commit) = w_4?;
+        let _w_polys = meter.track::<E::Fr>(4 * domain.size());
 
         // Add witness polynomial commitments to transcript.
         transcript.append_commitment(b"w_l", &w_l_poly_commit.0);
@@ -245,22 +314,32 @@ where
         transcript.append_scalar(b"beta", &beta);
         let gamma = transcript.challenge_scalar(b"gamma");
 
-        assert!(beta != gamma, "challenges must be different");
-
-        let z_poly = DensePolynomial::from_coefficients_slice(
-            &self.cs.perm.compute_permutation_poly(
-                &domain,
-                (w_l_scalar, w_r_scalar, w_o_scalar, w_4_scalar),
-                beta,
-                gamma,
-                (
-                    &prover_key.permutation.left_sigma.0,
-                    &prover_key.permutation.right_sigma.0,
-                    &prover_key.permutation.out_sigma.0,
-                    &prover_key.permutation.fourth_sigma.0,
+        if beta == gamma {
+            return Err(Error::EqualChallenges);
+        }
+
+        let z_poly = {
+            let sigmas = [
+                store.polynomial(PolynomialId::LeftSigma)?,
+                store.polynomial(PolynomialId::RightSigma)?,
+                store.polynomial(PolynomialId::OutSigma)?,
+                store.polynomial(PolynomialId::FourthSigma)?,
+            ];
+            let _sigmas = sigmas
+                .iter()
+                .map(|sigma| meter.track_loaded(sigma))
+                .collect::<Vec<_>>();
+            DensePolynomial::from_coefficients_slice(
+                &self.cs.perm.compute_permutation_poly(
+                    &domain,
+                    (w_l_scalar, w_r_scalar, w_o_scalar, w_4_scalar),
+                    beta,
+                    gamma,
+                    (&sigmas[0], &sigmas[1], &sigmas[2], &sigmas[3]),
                 ),
-            ),
-        );
+            )
+        };
+        let _z_poly = meter.track::<E::Fr>(domain.size());
 
         // Commit to permutation polynomial.
         let z_poly_commit = KZG10::<E, DensePolynomial<E::Fr>>::commit(
@@ -274,6 +353,7 @@ where
         let pi_poly = DensePolynomial::from_coefficients_vec(
             domain.ifft(&self.cs.construct_dense_pi_vec()),
         );
+        let _pi_poly = meter.track::<E::Fr>(domain.size());
 
         // 4. Compute quotient polynomial
         //
@@ -289,33 +369,61 @@ where
         let var_base_sep_challenge =
             transcript.challenge_scalar(b"variable base separation challenge");
 
-        let t_poly = quotient_poly::compute(
-            &domain,
-            prover_key,
-            &z_poly,
-            &w_l_poly,
-            &w_r_poly,
-            &w_o_poly,
-            &w_4_poly,
-            &pi_poly,
-            &alpha,
-            &beta,
-            &gamma,
-            &range_sep_challenge,
-            &logic_sep_challenge,
-            &fixed_base_sep_challenge,
-            &var_base_sep_challenge,
-        )?;
-
         // Split quotient polynomial into 4 degree `n` polynomials
-        let (t_1_poly, t_2_poly, t_3_poly, t_4_poly) =
-            self.split_tx_poly(domain.size(), &t_poly);
+        let (t_1_poly, t_2_poly, t_3_poly, t_4_poly) = match coset_key {
+            Some(prover_key) => {
+                let t_poly = quotient_poly::compute(
+                    &domain,
+                    prover_key,
+                    &z_poly,
+                    &w_l_poly,
+                    &w_r_poly,
+                    &w_o_poly,
+                    &w_4_poly,
+                    &pi_poly,
+                    &alpha,
+                    &beta,
+                    &gamma,
+                    &range_sep_challenge,
+                    &logic_sep_challenge,
+                    &fixed_base_sep_challenge,
+                    &var_base_sep_challenge,
+                )?;
+                self.split_tx_poly(domain.size(), &t_poly)
+            }
+            None => {
+                let [t_1_poly, t_2_poly, t_3_poly, t_4_poly] =
+                    quotient_poly::compute_by_coset::<_, P, _>(
+                        &domain,
+                        store,
+                        meter,
+                        &z_poly,
+                        &w_l_poly,
+                        &w_r_poly,
+                        &w_o_poly,
+                        &w_4_poly,
+                        &pi_poly,
+                        &alpha,
+                        &beta,
+                        &gamma,
+                        &range_sep_challenge,
+                        &logic_sep_challenge,
+                        &fixed_base_sep_challenge,
+                        &var_base_sep_challenge,
+                    )?;
+                (t_1_poly, t_2_poly, t_3_poly, t_4_poly)
+            }
+        };
+        let _t_polys = meter.track::<E::Fr>(4 * domain.size());
 
         // Commit to splitted quotient polynomial
-        let t_1_commit = KZG10::commit(commit_key, &t_1_poly, None, None)?;
-        let t_2_commit = KZG10::commit(commit_key, &t_2_poly, None, None)?;
-        let t_3_commit = KZG10::commit(commit_key, &t_3_poly, None, None)?;
-        let t_4_commit = KZG10::commit(commit_key, &t_4_poly, None, None)?;
+        let commit = |poly| KZG10::commit(commit_key, poly, None, None);
+        let ((t_1_commit, t_2_commit), (t_3_commit, t_4_commit)) = util::join(
+            || util::join(|| commit(&t_1_poly), || commit(&t_2_poly)),
+            || util::join(|| commit(&t_3_poly), || commit(&t_4_poly)),
+        );
+        let (t_1_commit, t_2_commit, t_3_commit, t_4_commit) =
+            (t_1_commit?, t_2_commit?, t_3_commit?, t_4_commit?);
 
         // Add quotient polynomial commitments to transcript
         transcript.append_commitment(b"t_1", &t_1_commit.0);
@@ -328,9 +436,10 @@ where
         // Compute evaluation challenge; `z`.
         let z_challenge = transcript.challenge_scalar(b"z");
 
-        let (lin_poly, evaluations) = linearisation_poly::compute(
+        let (lin_poly, mut evaluations) = linearisation_poly::compute::<_, P, _>(
             &domain,
-            prover_key,
+            store,
+            meter,
             &alpha,
             &beta,
             &gamma,
@@ -343,46 +452,29 @@ where
             &w_r_poly,
             &w_o_poly,
             &w_4_poly,
-            &t_poly,
             &z_poly,
-        );
+        )?;
+        let _lin_poly = meter.track::<E::Fr>(lin_poly.coeffs.len());
 
         // Add evaluations to transcript.
-        transcript.append_scalar(b"a_eval", &evaluations.proof.a_eval);
-        transcript.append_scalar(b"b_eval", &evaluations.proof.b_eval);
-        transcript.append_scalar(b"c_eval", &evaluations.proof.c_eval);
-        transcript.append_scalar(b"d_eval", &evaluations.proof.d_eval);
-        transcript
-            .append_scalar(b"a_next_eval", &evaluations.proof.a_next_eval);
-        transcript
-            .append_scalar(b"b_next_eval", &evaluations.proof.b_next_eval);
-        transcript
-            .append_scalar(b"d_next_eval", &evaluations.proof.d_next_eval);
-        transcript.append_scalar(
-            b"left_sig_eval",
-            &evaluations.proof.left_sigma_eval,
-        );
-        transcript.append_scalar(
-            b"right_sig_eval",
-            &evaluations.proof.right_sigma_eval,
-        );
-        transcript
-            .append_scalar(b"out_sig_eval", &evaluations.proof.out_sigma_eval);
+        transcript.append_scalar(b"a_eval", &evaluations.a_eval);
+        transcript.append_scalar(b"b_eval", &evaluations.b_eval);
+        transcript.append_scalar(b"c_eval", &evaluations.c_eval);
+        transcript.append_scalar(b"d_eval", &evaluations.d_eval);
+        transcript.append_scalar(b"a_next_eval", &evaluations.a_next_eval);
+        transcript.append_scalar(b"b_next_eval", &evaluations.b_next_eval);
+        transcript.append_scalar(b"d_next_eval", &evaluations.d_next_eval);
         transcript
-            .append_scalar(b"q_arith_eval", &evaluations.proof.q_arith_eval);
-        transcript.append_scalar(b"q_c_eval", &evaluations.proof.q_c_eval);
-        transcript.append_scalar(b"q_l_eval", &evaluations.proof.q_l_eval);
-        transcript.append_scalar(b"q_r_eval", &evaluations.proof.q_r_eval);
+            .append_scalar(b"left_sig_eval", &evaluations.left_sigma_eval);
         transcript
-            .append_scalar(b"perm_eval", &evaluations.proof.permutation_eval);
-        transcript.append_scalar(b"t_eval", &evaluations.quot_eval);
-        transcript.append_scalar(
-            b"r_eval",
-            &evaluations.proof.linearisation_polynomial_eval,
-        );
+            .append_scalar(b"right_sig_eval", &evaluations.right_sigma_eval);
+        transcript.append_scalar(b"out_sig_eval", &evaluations.out_sigma_eval);
+        transcript.append_scalar(b"q_arith_eval", &evaluations.q_arith_eval);
+        transcript.append_scalar(b"q_c_eval", &evaluations.q_c_eval);
+        transcript.append_scalar(b"q_l_eval", &evaluations.q_l_eval);
+        transcript.append_scalar(b"q_r_eval", &evaluations.q_r_eval);
+        transcript.append_scalar(b"perm_eval", &evaluations.permutation_eval);
 
-        // 5. Compute Openings using KZG10
-        //
         // We merge the quotient polynomial using the `z_challenge` so the SRS
         // is linear in the circuit size `n`
         let quot = Self::compute_quotient_opening_poly(
@@ -393,48 +485,82 @@ where
             &t_4_poly,
             &z_challenge,
         );
+        let _quot = meter.track::<E::Fr>(quot.coeffs.len());
+
+        // In compact mode the verifier derives the evaluations of both the
+        // quotient and the linearisation polynomials.
+        match store.mode() {
+            ProofMode::Standard => {
+                transcript
+                    .append_scalar(b"t_eval", &quot.evaluate(&z_challenge));
+                transcript.append_scalar(
+                    b"r_eval",
+                    &evaluations.linearisation_polynomial_eval,
+                );
+            }
+            ProofMode::Compact => {
+                evaluations.linearisation_polynomial_eval = E::Fr::zero();
+            }
+        }
 
-        // Compute aggregate witness to polynomials evaluated at the evaluation
-        // challenge `z`
+        // 5. Compute Openings using KZG10
+        let mut opened_polys = match store.mode() {
+            ProofMode::Standard => vec![quot, lin_poly],
+            ProofMode::Compact => {
+                // Fold the quotient into the linearisation polynomial so that
+                // it is opened at a value known to the verifier.
+                let z_h_eval =
+                    domain.evaluate_vanishing_polynomial(z_challenge);
+                vec![&lin_poly - &(&quot * z_h_eval)]
+            }
+        };
+        opened_polys.extend([
+            w_l_poly.clone(),
+            w_r_poly.clone(),
+            w_o_poly,
+            w_4_poly.clone(),
+            store.polynomial(PolynomialId::LeftSigma)?.into_owned(),
+            store.polynomial(PolynomialId::RightSigma)?.into_owned(),
+            store.polynomial(PolynomialId::OutSigma)?.into_owned(),
+        ]);
+        let _opened_polys = meter
+            .track::<E::Fr>(opened_polys.iter().map(|p| p.coeffs.len()).sum());
+
+        // Compute aggregate witnesses to polynomials evaluated at the
+        // evaluation challenge `z`, and at the shifted evaluation challenge
         let aw_challenge: E::Fr =
             transcript.challenge_scalar(b"aggregate_witness");
-        let aggregate_witness = Self::compute_aggregate_witness(
-            &[
-                quot,
-                lin_poly,
-                w_l_poly.clone(),
-                w_r_poly.clone(),
-                w_o_poly,
-                w_4_poly.clone(),
-                prover_key.permutation.left_sigma.0.clone(),
-                prover_key.permutation.right_sigma.0.clone(),
-                prover_key.permutation.out_sigma.0.clone(),
-            ],
-            &z_challenge,
-            aw_challenge,
-        );
-        let w_z_comm = KZG10::<E, DensePolynomial<E::Fr>>::commit(
-            commit_key,
-            &aggregate_witness,
-            None,
-            None,
-        )?;
-
-        // Compute aggregate witness to polynomials evaluated at the shifted
-        // evaluation challenge
         let saw_challenge: E::Fr =
             transcript.challenge_scalar(b"aggregate_witness");
-        let shifted_aggregate_witness = Self::compute_aggregate_witness(
-            &[z_poly, w_l_poly, w_r_poly, w_4_poly],
-            &(z_challenge * domain.element(1)),
-            saw_challenge,
+        let (w_z_comm, w_zw_comm) = util::join(
+            || {
+                let aggregate_witness = Self::compute_aggregate_witness(
+                    &opened_polys,
+                    &z_challenge,
+                    aw_challenge,
+                );
+                KZG10::<E, DensePolynomial<E::Fr>>::commit(
+                    commit_key,
+                    &aggregate_witness,
+                    None,
+                    None,
+                )
+            },
+            || {
+                let shifted_aggregate_witness = Self::compute_aggregate_witness(
+                    &[z_poly, w_l_poly, w_r_poly, w_4_poly],
+                    &(z_challenge * domain.element(1)),
+                    saw_challenge,
+                );
+                KZG10::<E, DensePolynomial<E::Fr>>::commit(
+                    commit_key,
+                    &shifted_aggregate_witness,
+                    None,
+                    None,
+                )
+            },
         );
-        let w_zw_comm = KZG10::<E, DensePolynomial<E::Fr>>::commit(
-            commit_key,
-            &shifted_aggregate_witness,
-            None,
-            None,
-        )?;
+        let (w_z_comm, w_zw_comm) = (w_z_comm?, w_zw_comm?);
 
         Ok(Proof {
             a_comm: w_l_poly_commit.0,
@@ -448,7 +574,8 @@ where
             t_4_comm: t_4_commit.0,
             w_z_comm: w_z_comm.0,
             w_zw_comm: w_zw_comm.0,
-            evaluations: evaluations.proof,
+            evaluations,
+            mode: store.mode(),
             __: PhantomData,
         })
     }
@@ -460,24 +587,70 @@ where
         &mut self,
         commit_key: &Powers<E>,
     ) -> Result<Proof<E, P>, Error> {
-        if self.prover_key.is_none() {
+        let prover_key = match self.prover_key.take() {
+            Some(prover_key) => prover_key,
             // Preprocess circuit and store preprocessed circuit and transcript
             // in the Prover.
-            self.prover_key = Some(self.cs.preprocess_prover(
+            None => self.cs.preprocess_prover(
                 commit_key,
                 &mut self.preprocessed_transcript,
-            )?);
-        }
+            )?,
+        };
 
-        let prover_key = self.prover_key.as_ref().unwrap();
-
-        let proof = self.prove_with_preprocessed(commit_key, prover_key)?;
+        let proof = self.prove_with_preprocessed(commit_key, &prover_key);
+        self.prover_key = Some(prover_key);
+        let proof = proof?;
 
         // Clear witness and reset composer variables
         self.clear_witness();
 
         Ok(proof)
     }
+
+    /// Preprocesses the underlying constraint system into a
+    /// [`LowMemoryProverKey`], which only holds the coefficients of the
+    /// preprocessed polynomials.
+    ///
+    /// The key can be used as is or written to disk and opened as an
+    /// `MmapProverKey`, for [`Prover::prove_low_memory`].
+    pub fn preprocess_low_memory(
+        &mut self,
+        commit_key: &Powers<E>,
+    ) -> Result<LowMemoryProverKey<E::Fr, P>, Error> {
+        self.cs.preprocess_low_memory_prover(
+            commit_key,
+            &mut self.preprocessed_transcript,
+        )
+    }
+
+    /// Proves a circuit is satisfied using the preprocessed polynomials in
+    /// `key`, then clears the witness variables.
+    ///
+    /// The quotient polynomial is computed one coset of the domain at a time
+    /// and the preprocessed polynomials are loaded only when needed, instead
+    /// of holding their `4n` evaluations. The resulting [`Proof`] is identical
+    /// to the one from [`Prover::prove`].
+    pub fn prove_low_memory<S>(
+        &mut self,
+        commit_key: &Powers<E>,
+        key: &S,
+    ) -> Result<(Proof<E, P>, MemoryReport), Error>
+    where
+        S: PolynomialStore<E::Fr>,
+    {
+        let meter = MemoryMeter::default();
+        let proof =
+            self.install(|| self.prove_inner(commit_key, key, None, &meter))?;
+        let report = MemoryReport {
+            peak_bytes: meter.peak(),
+            resident_key_bytes: key.resident_bytes(),
+        };
+
+        // Clear witness and reset composer variables
+        self.clear_witness();
+
+        Ok((proof, report))
+    }
 }
 
 impl<E, P> Default for Prover<E, P>
@@ -490,3 +663,56 @@ where
         Prover::new(b"plonk")
     }
 }
+
+#[cfg(all(test, feature = "parallel"))]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::setup::PublicParameters;
+    use crate::testing::dummy_gadget;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use rand_core::OsRng;
+
+    fn test_thread_pool<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let pp = PublicParameters::<E>::setup(1 << 8, &mut OsRng).unwrap();
+        let powers = pp.commit_key(1 << 7).unwrap();
+
+        let mut prover = Prover::<E, P>::new(b"thread_pool");
+        dummy_gadget(100, prover.mut_cs());
+        let expected = prover.prove(&powers).unwrap();
+
+        // Proofs do not depend on the number of threads
+        for num_threads in [1, 3] {
+            let thread_pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap();
+            prover.set_thread_pool(Arc::new(thread_pool));
+            dummy_gadget(100, prover.mut_cs());
+            assert_eq!(prover.prove(&powers).unwrap(), expected);
+        }
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [test_thread_pool],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [test_thread_pool],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
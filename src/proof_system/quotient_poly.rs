@@ -6,13 +6,17 @@
 //
 // Copyright (c) ZK-INFRA. All rights reserved.
 
+use crate::permutation::constants::{K1, K2, K3};
 use crate::proof_system::ecc::CurveAddition;
+use crate::proof_system::ecc::CurveDoubling;
 use crate::proof_system::ecc::FixedBaseScalarMul;
 use crate::proof_system::logic::Logic;
+use crate::proof_system::low_memory::MemoryMeter;
 use crate::proof_system::range::Range;
 use crate::proof_system::widget::GateConstraint;
-use crate::proof_system::GateValues;
-use crate::{error::Error, proof_system::ProverKey};
+use crate::proof_system::{GateValues, PolynomialId, PolynomialStore};
+use crate::{error::Error, proof_system::ProverKey, util};
+use alloc::vec::Vec;
 use ark_ec::TEModelParameters;
 use ark_ff::PrimeField;
 use ark_poly::{
@@ -43,60 +47,70 @@ where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
 {
-    let domain_4n =
-        GeneralEvaluationDomain::<F>::new(4 * domain.size()).unwrap();
-
-    let mut z_eval_4n = domain_4n.coset_fft(z_poly);
-    z_eval_4n.push(z_eval_4n[0]);
-    z_eval_4n.push(z_eval_4n[1]);
-    z_eval_4n.push(z_eval_4n[2]);
-    z_eval_4n.push(z_eval_4n[3]);
-
-    let mut wl_eval_4n = domain_4n.coset_fft(w_l_poly);
-    wl_eval_4n.push(wl_eval_4n[0]);
-    wl_eval_4n.push(wl_eval_4n[1]);
-    wl_eval_4n.push(wl_eval_4n[2]);
-    wl_eval_4n.push(wl_eval_4n[3]);
-
-    let mut wr_eval_4n = domain_4n.coset_fft(w_r_poly);
-    wr_eval_4n.push(wr_eval_4n[0]);
-    wr_eval_4n.push(wr_eval_4n[1]);
-    wr_eval_4n.push(wr_eval_4n[2]);
-    wr_eval_4n.push(wr_eval_4n[3]);
-
-    let wo_eval_4n = domain_4n.coset_fft(w_o_poly);
-
-    let mut w4_eval_4n = domain_4n.coset_fft(w_4_poly);
-    w4_eval_4n.push(w4_eval_4n[0]);
-    w4_eval_4n.push(w4_eval_4n[1]);
-    w4_eval_4n.push(w4_eval_4n[2]);
-    w4_eval_4n.push(w4_eval_4n[3]);
-
-    let gate_constraints = compute_gate_constraint_satisfiability(
-        domain,
-        *range_challenge,
-        *logic_challenge,
-        *fixed_base_challenge,
-        *var_base_challenge,
-        prover_key,
-        &wl_eval_4n,
-        &wr_eval_4n,
-        &wo_eval_4n,
-        &w4_eval_4n,
-        public_inputs_poly,
-    );
+    let domain_4n = util::domain::<F>(4 * domain.size())?;
+
+    // Evaluations over the 4n coset, followed by the first four evaluations
+    // again for the polynomials also evaluated at the next point.
+    let coset_fft_4n = |poly: &DensePolynomial<F>, next: bool| {
+        let mut evals = domain_4n.coset_fft(poly);
+        if next {
+            evals.extend_from_within(0..4);
+        }
+        evals
+    };
 
-    let permutation = compute_permutation_checks(
-        domain,
-        prover_key,
-        &wl_eval_4n,
-        &wr_eval_4n,
-        &wo_eval_4n,
-        &w4_eval_4n,
-        &z_eval_4n,
-        *alpha,
-        *beta,
-        *gamma,
+    let ((z_eval_4n, wl_eval_4n), ((wr_eval_4n, wo_eval_4n), w4_eval_4n)) =
+        util::join(
+            || {
+                util::join(
+                    || coset_fft_4n(z_poly, true),
+                    || coset_fft_4n(w_l_poly, true),
+                )
+            },
+            || {
+                util::join(
+                    || {
+                        util::join(
+                            || coset_fft_4n(w_r_poly, true),
+                            || coset_fft_4n(w_o_poly, false),
+                        )
+                    },
+                    || coset_fft_4n(w_4_poly, true),
+                )
+            },
+        );
+
+    let (gate_constraints, permutation) = util::join(
+        || {
+            compute_gate_constraint_satisfiability(
+                &domain_4n,
+                *range_challenge,
+                *logic_challenge,
+                *fixed_base_challenge,
+                *var_base_challenge,
+                prover_key,
+                &wl_eval_4n,
+                &wr_eval_4n,
+                &wo_eval_4n,
+                &w4_eval_4n,
+                public_inputs_poly,
+            )
+        },
+        || {
+            compute_permutation_checks(
+                domain,
+                &domain_4n,
+                prover_key,
+                &wl_eval_4n,
+                &wr_eval_4n,
+                &wo_eval_4n,
+                &w4_eval_4n,
+                &z_eval_4n,
+                *alpha,
+                *beta,
+                *gamma,
+            )
+        },
     );
 
     let quotient = (0..domain_4n.size())
@@ -112,9 +126,255 @@ where
     })
 }
 
+/// Computes the Quotient [`DensePolynomial`], split into its four `n`-sized
+/// chunks, without the `4n` evaluations of the [`ProverKey`].
+///
+/// The `4n` coset `g * H_4n` is the union of the four `n`-sized cosets
+/// `g * w_4n^j * H_n`, the `k`-th point of coset `j` being the `(4k + j)`-th
+/// point of the `4n` coset. Each coset is evaluated in turn, loading the
+/// preprocessed polynomials from `store` one at a time, and the four chunks
+/// are then interpolated in place.
+pub fn compute_by_coset<F, P, S>(
+    domain: &GeneralEvaluationDomain<F>,
+    store: &S,
+    meter: &MemoryMeter,
+    z_poly: &DensePolynomial<F>,
+    w_l_poly: &DensePolynomial<F>,
+    w_r_poly: &DensePolynomial<F>,
+    w_o_poly: &DensePolynomial<F>,
+    w_4_poly: &DensePolynomial<F>,
+    public_inputs_poly: &DensePolynomial<F>,
+    alpha: &F,
+    beta: &F,
+    gamma: &F,
+    range_challenge: &F,
+    logic_challenge: &F,
+    fixed_base_challenge: &F,
+    var_base_challenge: &F,
+) -> Result<[DensePolynomial<F>; 4], Error>
+where
+    F: PrimeField,
+    P: TEModelParameters<BaseField = F>,
+    S: PolynomialStore<F>,
+{
+    let n = domain.size();
+    let domain_4n = util::domain::<F>(4 * n)?;
+    let l1_poly_alpha =
+        compute_first_lagrange_poly_scaled(domain, alpha.square());
+    let _l1_poly_alpha = meter.track::<F>(n);
+
+    let mut chunks = Vec::with_capacity(4);
+    let mut chunk_allocations = Vec::with_capacity(4);
+    for j in 0..4 {
+        let offset = F::multiplicative_generator() * domain_4n.element(j);
+        let coset_evals = |polynomial: &DensePolynomial<F>| {
+            let evals = coset_fft(domain, polynomial, offset);
+            (evals, meter.track::<F>(n))
+        };
+        let load_coset_evals = |id| -> Result<_, Error> {
+            let polynomial = store.polynomial(id)?;
+            let _polynomial = meter.track_loaded(&polynomial);
+            Ok(coset_evals(&polynomial))
+        };
+
+        let (wl, _wl) = coset_evals(w_l_poly);
+        let (wr, _wr) = coset_evals(w_r_poly);
+        let (wo, _wo) = coset_evals(w_o_poly);
+        let (w4, _w4) = coset_evals(w_4_poly);
+        let (z, _z) = coset_evals(z_poly);
+        let (mut numerator, numerator_allocation) =
+            coset_evals(public_inputs_poly);
+
+        // Arithmetic gates
+        {
+            let (q_arith, _q_arith) = load_coset_evals(PolynomialId::QArith)?;
+            let terms: [(PolynomialId, &dyn Fn(usize) -> F); 6] = [
+                (PolynomialId::QM, &|k| wl[k] * wr[k]),
+                (PolynomialId::QL, &|k| wl[k]),
+                (PolynomialId::QR, &|k| wr[k]),
+                (PolynomialId::QO, &|k| wo[k]),
+                (PolynomialId::Q4, &|k| w4[k]),
+                (PolynomialId::QC, &|_| F::one()),
+            ];
+            for (id, wire) in terms {
+                let (selector, _selector) = load_coset_evals(id)?;
+                for k in 0..n {
+                    numerator[k] += selector[k] * wire(k) * q_arith[k];
+                }
+            }
+        }
+
+        // Custom gates
+        {
+            let (q_l, _q_l) = load_coset_evals(PolynomialId::QL)?;
+            let (q_r, _q_r) = load_coset_evals(PolynomialId::QR)?;
+            let (q_c, _q_c) = load_coset_evals(PolynomialId::QC)?;
+            let values = |k: usize| GateValues {
+                left: wl[k],
+                right: wr[k],
+                output: wo[k],
+                fourth: w4[k],
+                left_next: wl[(k + 1) % n],
+                right_next: wr[(k + 1) % n],
+                fourth_next: w4[(k + 1) % n],
+                left_selector: q_l[k],
+                right_selector: q_r[k],
+                constant_selector: q_c[k],
+            };
+            let terms: [(PolynomialId, QuotientTerm<F>, F); 5] = [
+                (PolynomialId::QRange, Range::quotient_term, *range_challenge),
+                (PolynomialId::QLogic, Logic::quotient_term, *logic_challenge),
+                (
+                    PolynomialId::QFixedGroupAdd,
+                    FixedBaseScalarMul::<_, P>::quotient_term,
+                    *fixed_base_challenge,
+                ),
+                (
+                    PolynomialId::QVariableGroupAdd,
+                    CurveAddition::<_, P>::quotient_term,
+                    *var_base_challenge,
+                ),
+                (
+                    PolynomialId::QVariableGroupDouble,
+                    CurveDoubling::<_, P>::quotient_term,
+                    *var_base_challenge,
+                ),
+            ];
+            for (id, quotient_term, challenge) in terms {
+                let (selector, _selector) = load_coset_evals(id)?;
+                for k in 0..n {
+                    numerator[k] +=
+                        quotient_term(selector[k], challenge, values(k));
+                }
+            }
+        }
+
+        // Permutation checks
+        {
+            let (l1_alpha_sq, _l1_alpha_sq) = coset_evals(&l1_poly_alpha);
+            let omega = domain.element(1);
+            let mut x = offset;
+            for k in 0..n {
+                let beta_x = *beta * x;
+                numerator[k] += (wl[k] + beta_x + gamma)
+                    * (wr[k] + K1::<F>() * beta_x + gamma)
+                    * (wo[k] + K2::<F>() * beta_x + gamma)
+                    * (w4[k] + K3::<F>() * beta_x + gamma)
+                    * z[k]
+                    * alpha;
+                numerator[k] += (z[k] - F::one()) * l1_alpha_sq[k];
+                x *= omega;
+            }
+
+            let mut product =
+                (0..n).map(|k| z[(k + 1) % n] * alpha).collect::<Vec<_>>();
+            let _product = meter.track::<F>(n);
+            for (id, wire) in [
+                (PolynomialId::LeftSigma, &wl),
+                (PolynomialId::RightSigma, &wr),
+                (PolynomialId::OutSigma, &wo),
+                (PolynomialId::FourthSigma, &w4),
+            ] {
+                let (sigma, _sigma) = load_coset_evals(id)?;
+                for k in 0..n {
+                    product[k] *= wire[k] + *beta * sigma[k] + gamma;
+                }
+            }
+            for k in 0..n {
+                numerator[k] -= product[k];
+            }
+        }
+
+        // Divide by the vanishing polynomial, constant over the coset
+        let v_h_inv = (offset.pow([n as u64]) - F::one()).inverse().unwrap();
+        for value in numerator.iter_mut() {
+            *value *= v_h_inv;
+        }
+        chunks.push(numerator);
+        chunk_allocations.push(numerator_allocation);
+    }
+
+    // Interpolate every coset, giving the quotient polynomial modulo
+    // `X^n - c_j^n` for coset offset `c_j`.
+    for (j, chunk) in chunks.iter_mut().enumerate() {
+        let offset = F::multiplicative_generator() * domain_4n.element(j);
+        domain.ifft_in_place(chunk);
+        let offset_inv = offset.inverse().unwrap();
+        let mut power = F::one();
+        for coeff in chunk.iter_mut() {
+            *coeff *= power;
+            power *= offset_inv;
+        }
+    }
+
+    // With `c_j^n = g^n * zeta^j` for `zeta` a 4th root of unity, the `i`th
+    // coefficients of the four remainders are the size 4 DFT of the
+    // `i + l * n`th coefficients of the quotient, scaled by `g^(l * n)`.
+    let zeta_inv = domain_4n.element(n).inverse().unwrap();
+    let g_n_inv = F::multiplicative_generator()
+        .pow([n as u64])
+        .inverse()
+        .unwrap();
+    let four_inv = F::from(4u64).inverse().unwrap();
+    for i in 0..n {
+        let remainders =
+            [chunks[0][i], chunks[1][i], chunks[2][i], chunks[3][i]];
+        let mut scale = four_inv;
+        for (l, chunk) in chunks.iter_mut().enumerate() {
+            let zeta_l_inv = zeta_inv.pow([l as u64]);
+            let mut zeta_jl_inv = F::one();
+            let mut coeff = F::zero();
+            for remainder in remainders {
+                coeff += remainder * zeta_jl_inv;
+                zeta_jl_inv *= zeta_l_inv;
+            }
+            chunk[i] = coeff * scale;
+            scale *= g_n_inv;
+        }
+    }
+
+    let mut chunks = chunks
+        .into_iter()
+        .map(DensePolynomial::from_coefficients_vec);
+    Ok([
+        chunks.next().unwrap(),
+        chunks.next().unwrap(),
+        chunks.next().unwrap(),
+        chunks.next().unwrap(),
+    ])
+}
+
+/// Contribution of a custom gate to the quotient at a single point.
+type QuotientTerm<F> = fn(F, F, GateValues<F>) -> F;
+
+/// Evaluates `polynomial`, of degree lower than the size of `domain`, over
+/// the coset `offset * domain`.
+fn coset_fft<F>(
+    domain: &GeneralEvaluationDomain<F>,
+    polynomial: &DensePolynomial<F>,
+    offset: F,
+) -> Vec<F>
+where
+    F: PrimeField,
+{
+    assert!(polynomial.coeffs.len() <= domain.size());
+    let mut power = F::one();
+    let mut evals = polynomial
+        .coeffs
+        .iter()
+        .map(|coeff| {
+            let scaled = *coeff * power;
+            power *= offset;
+            scaled
+        })
+        .collect::<Vec<_>>();
+    domain.fft_in_place(&mut evals);
+    evals
+}
+
 /// Ensures that the gate constraints are satisfied.
 fn compute_gate_constraint_satisfiability<F, P>(
-    domain: &GeneralEvaluationDomain<F>,
+    domain_4n: &GeneralEvaluationDomain<F>,
     range_challenge: F,
     logic_challenge: F,
     fixed_base_challenge: F,
@@ -130,8 +390,6 @@ where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
 {
-    let domain_4n =
-        GeneralEvaluationDomain::<F>::new(4 * domain.size()).unwrap();
     let pi_eval_4n = domain_4n.coset_fft(pi_poly);
 
     (0..domain_4n.size())
@@ -182,11 +440,18 @@ where
                 values,
             );
 
+            let curve_doubling = CurveDoubling::<_, P>::quotient_term(
+                prover_key.variable_group_double_selector.1[i],
+                var_base_challenge,
+                values,
+            );
+
             (arithmetic + pi_eval_4n[i])
                 + range
                 + logic
                 + fixed_base_scalar_mul
                 + curve_addition
+                + curve_doubling
         })
         .collect()
 }
@@ -195,6 +460,7 @@ where
 /// `domain`.
 fn compute_permutation_checks<F, P>(
     domain: &GeneralEvaluationDomain<F>,
+    domain_4n: &GeneralEvaluationDomain<F>,
     prover_key: &ProverKey<F, P>,
     wl_eval_4n: &[F],
     wr_eval_4n: &[F],
@@ -209,8 +475,6 @@ where
     F: PrimeField,
     P: TEModelParameters<BaseField = F>,
 {
-    let domain_4n =
-        GeneralEvaluationDomain::<F>::new(4 * domain.size()).unwrap();
     let l1_poly_alpha =
         compute_first_lagrange_poly_scaled(domain, alpha.square());
     let l1_alpha_sq_evals = domain_4n.coset_fft(&l1_poly_alpha.coeffs);
@@ -15,6 +15,7 @@ use crate::proof_system::Proof;
 use crate::transcript::TranscriptWrapper;
 use ark_ec::{PairingEngine, TEModelParameters};
 use ark_poly_commit::kzg10::{Powers, VerifierKey};
+use rand_core::{CryptoRng, RngCore};
 
 /// Abstraction structure designed verify [`Proof`]s.
 pub struct Verifier<E, P>
@@ -95,17 +96,46 @@ where
     }
 
     /// Verifies a [`Proof`] using `pc_verifier_key` and `public_inputs`.
+    #[cfg(feature = "std")]
     pub fn verify(
         &self,
         proof: &Proof<E, P>,
         pc_verifier_key: &VerifierKey<E>,
         public_inputs: &[E::Fr],
     ) -> Result<(), Error> {
+        self.verify_with_rng(
+            proof,
+            pc_verifier_key,
+            public_inputs,
+            &mut rand_core::OsRng,
+        )
+    }
+
+    /// Verifies a [`Proof`] using `pc_verifier_key` and `public_inputs`,
+    /// drawing the randomness of the batched opening check from `rng`.
+    ///
+    /// The randomness must be unpredictable to the prover, who could
+    /// otherwise forge a proof passing the batched check.
+    ///
+    /// Unlike `verify`, this is available without the `std` feature.
+    pub fn verify_with_rng<R>(
+        &self,
+        proof: &Proof<E, P>,
+        pc_verifier_key: &VerifierKey<E>,
+        public_inputs: &[E::Fr],
+        rng: &mut R,
+    ) -> Result<(), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
         proof.verify(
-            self.verifier_key.as_ref().unwrap(),
+            self.verifier_key
+                .as_ref()
+                .ok_or(Error::UninitializedVerifierKey)?,
             &mut self.preprocessed_transcript.clone(),
             pc_verifier_key,
             public_inputs,
+            rng,
         )
     }
 }
@@ -9,6 +9,7 @@
 //! Arithmetic Gates
 
 use crate::proof_system::linearisation_poly::ProofEvaluations;
+use alloc::vec::Vec;
 use ark_ec::PairingEngine;
 use ark_ff::PrimeField;
 use ark_poly::polynomial::univariate::DensePolynomial;
@@ -0,0 +1,49 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Elliptic Curve Point Doubling Gate
+
+use crate::proof_system::widget::{GateConstraint, GateValues};
+use ark_ec::TEModelParameters;
+use ark_ff::Field;
+use core::marker::PhantomData;
+
+/// Curve Doubling Gate
+#[derive(derivative::Derivative)]
+#[derivative(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CurveDoubling<F, P>(PhantomData<(F, P)>)
+where
+    F: Field,
+    P: TEModelParameters<BaseField = F>;
+
+impl<F, P> GateConstraint<F> for CurveDoubling<F, P>
+where
+    F: Field,
+    P: TEModelParameters<BaseField = F>,
+{
+    #[inline]
+    fn constraints(separation_challenge: F, values: GateValues<F>) -> F {
+        let x_1 = values.left;
+        let y_1 = values.right;
+        let x_3 = values.output;
+        let y_3 = values.fourth;
+
+        let a_x1_x1 = P::COEFF_A * x_1.square();
+        let y1_y1 = y_1.square();
+
+        // Check that `x_3 * (a * x_1^2 + y_1^2) = 2 * x_1 * y_1`
+        let x3_consistency = x_3 * (a_x1_x1 + y1_y1) - (x_1 * y_1).double();
+
+        // Check that `y_3 * (2 - a * x_1^2 - y_1^2) = y_1^2 - a * x_1^2`
+        let two = F::one().double();
+        let y3_consistency = y_3 * (two - a_x1_x1 - y1_y1) - (y1_y1 - a_x1_x1);
+
+        (x3_consistency + y3_consistency * separation_challenge)
+            * separation_challenge
+    }
+}
@@ -9,7 +9,9 @@
 //! Elliptic Curve Cryptography Gates
 
 mod curve_addition;
+mod curve_doubling;
 mod fixed_base_scalar_mul;
 
 pub use curve_addition::*;
+pub use curve_doubling::*;
 pub use fixed_base_scalar_mul::*;
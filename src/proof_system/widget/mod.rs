@@ -15,7 +15,9 @@ pub mod range;
 
 use crate::proof_system::linearisation_poly::ProofEvaluations;
 use crate::proof_system::permutation;
+use crate::proof_system::proof::ProofMode;
 use crate::transcript::TranscriptProtocol;
+use alloc::vec::Vec;
 use ark_ec::{PairingEngine, TEModelParameters};
 use ark_ff::{Field, PrimeField};
 use ark_poly::{univariate::DensePolynomial, Evaluations};
@@ -170,9 +172,15 @@ where
     /// Variable Group Addition Selector Commitment
     pub(crate) variable_group_add_selector_commitment: Commitment<E>,
 
+    /// Variable Group Doubling Selector Commitment
+    pub(crate) variable_group_double_selector_commitment: Commitment<E>,
+
     /// VerifierKey for permutation checks
     pub(crate) permutation: permutation::VerifierKey<E>,
 
+    /// Variant of the protocol accepted by this key
+    pub(crate) mode: ProofMode,
+
     /// Type Parameter Marker
     __: PhantomData<P>,
 }
@@ -198,6 +206,7 @@ where
         q_logic: Commitment<E>,
         q_fixed_group_add: Commitment<E>,
         q_variable_group_add: Commitment<E>,
+        q_variable_group_double: Commitment<E>,
         left_sigma: Commitment<E>,
         right_sigma: Commitment<E>,
         out_sigma: Commitment<E>,
@@ -218,12 +227,14 @@ where
             logic_selector_commitment: q_logic,
             fixed_group_add_selector_commitment: q_fixed_group_add,
             variable_group_add_selector_commitment: q_variable_group_add,
+            variable_group_double_selector_commitment: q_variable_group_double,
             permutation: permutation::VerifierKey {
                 left_sigma,
                 right_sigma,
                 out_sigma,
                 fourth_sigma,
             },
+            mode: ProofMode::Standard,
             __: PhantomData,
         }
     }
@@ -232,6 +243,17 @@ where
     pub fn padded_circuit_size(&self) -> usize {
         self.n.next_power_of_two()
     }
+
+    /// Returns the [`ProofMode`] of the proofs accepted by this key.
+    pub fn mode(&self) -> ProofMode {
+        self.mode
+    }
+
+    /// Sets the [`ProofMode`] of the proofs accepted by this key. It must
+    /// match the mode of the [`ProverKey`] that created them.
+    pub fn set_mode(&mut self, mode: ProofMode) {
+        self.mode = mode;
+    }
 }
 
 impl<E, P> VerifierKey<E, P>
@@ -259,6 +281,10 @@ where
             b"q_variable_group_add",
             &self.variable_group_add_selector_commitment,
         );
+        transcript.append_commitment(
+            b"q_variable_group_double",
+            &self.variable_group_double_selector_commitment,
+        );
         transcript.append_commitment(
             b"q_fixed_group_add",
             &self.fixed_group_add_selector_commitment,
@@ -309,6 +335,10 @@ where
     pub(crate) variable_group_add_selector:
         (DensePolynomial<F>, Evaluations<F>),
 
+    /// Variable Group Doubling Selector
+    pub(crate) variable_group_double_selector:
+        (DensePolynomial<F>, Evaluations<F>),
+
     /// ProverKey for permutation checks
     pub(crate) permutation: permutation::ProverKey<F>,
 
@@ -320,6 +350,9 @@ where
     /// polynomial without having to perform IFFT
     pub(crate) v_h_coset_4n: Evaluations<F>,
 
+    /// Variant of the protocol produced by this key
+    pub(crate) mode: ProofMode,
+
     /// Type Parameter Marker
     __: PhantomData<P>,
 }
@@ -333,6 +366,17 @@ where
         &self.v_h_coset_4n
     }
 
+    /// Returns the [`ProofMode`] of the proofs created with this key.
+    pub fn mode(&self) -> ProofMode {
+        self.mode
+    }
+
+    /// Sets the [`ProofMode`] of the proofs created with this key. It must
+    /// match the mode of the [`VerifierKey`] that checks them.
+    pub fn set_mode(&mut self, mode: ProofMode) {
+        self.mode = mode;
+    }
+
     /// Constructs a [`ProverKey`] from the widget ProverKey's that are
     /// constructed based on the selector polynomials and the
     /// sigma polynomials and it's evaluations.
@@ -349,6 +393,7 @@ where
         q_logic: (DensePolynomial<F>, Evaluations<F>),
         q_fixed_group_add: (DensePolynomial<F>, Evaluations<F>),
         q_variable_group_add: (DensePolynomial<F>, Evaluations<F>),
+        q_variable_group_double: (DensePolynomial<F>, Evaluations<F>),
         left_sigma: (DensePolynomial<F>, Evaluations<F>),
         right_sigma: (DensePolynomial<F>, Evaluations<F>),
         out_sigma: (DensePolynomial<F>, Evaluations<F>),
@@ -371,6 +416,7 @@ where
             logic_selector: q_logic,
             fixed_group_add_selector: q_fixed_group_add,
             variable_group_add_selector: q_variable_group_add,
+            variable_group_double_selector: q_variable_group_double,
             permutation: permutation::ProverKey {
                 left_sigma,
                 right_sigma,
@@ -379,6 +425,7 @@ where
                 linear_evaluations,
             },
             v_h_coset_4n,
+            mode: ProofMode::Standard,
             __: PhantomData,
         }
     }
@@ -433,6 +480,7 @@ mod test {
         let q_logic = rand_poly_eval(n);
         let q_fixed_group_add = rand_poly_eval(n);
         let q_variable_group_add = rand_poly_eval(n);
+        let q_variable_group_double = rand_poly_eval(n);
 
         let left_sigma = rand_poly_eval(n);
         let right_sigma = rand_poly_eval(n);
@@ -455,6 +503,7 @@ mod test {
             q_logic,
             q_fixed_group_add,
             q_variable_group_add,
+            q_variable_group_double,
             left_sigma,
             right_sigma,
             out_sigma,
@@ -492,6 +541,7 @@ mod test {
         let q_logic = Commitment(G1Affine::default());
         let q_fixed_group_add = Commitment(G1Affine::default());
         let q_variable_group_add = Commitment(G1Affine::default());
+        let q_variable_group_double = Commitment(G1Affine::default());
 
         let left_sigma = Commitment(G1Affine::default());
         let right_sigma = Commitment(G1Affine::default());
@@ -511,6 +561,7 @@ mod test {
             q_logic,
             q_fixed_group_add,
             q_variable_group_add,
+            q_variable_group_double,
             left_sigma,
             right_sigma,
             out_sigma,
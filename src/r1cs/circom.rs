@@ -0,0 +1,512 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Circom `.r1cs` and `.wtns` files.
+//!
+//! A [`CircomR1cs`] and its witness are turned into a [`CircomCircuit`],
+//! which is compiled and proven through [`R1csCircuit`](super::R1csCircuit)
+//! like any other R1CS circuit. The public signals of the Circom circuit, its
+//! outputs followed by its public inputs, are the public inputs of the proof.
+
+use super::R1csSynthesizer;
+use crate::error::Error;
+use alloc::vec::Vec;
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystemRef, LinearCombination,
+    SynthesisError, Variable,
+};
+use core::convert::TryInto;
+
+/// Section of an `.r1cs` file holding its header.
+const R1CS_HEADER: u32 = 1;
+
+/// Section of an `.r1cs` file holding its constraints.
+const R1CS_CONSTRAINTS: u32 = 2;
+
+/// Section of an `.r1cs` file mapping every wire to its label.
+const R1CS_WIRE_LABELS: u32 = 3;
+
+/// Section of a `.wtns` file holding its header.
+const WTNS_HEADER: u32 = 1;
+
+/// Section of a `.wtns` file holding the witness values.
+const WTNS_VALUES: u32 = 2;
+
+/// Linear combination of Circom wires.
+type WireCombination<F> = Vec<(usize, F)>;
+
+/// Constraints of a Circom circuit, read from an `.r1cs` file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircomR1cs<F>
+where
+    F: PrimeField,
+{
+    /// Number of wires, including the constant one
+    num_wires: usize,
+
+    /// Number of public signals: outputs and public inputs
+    num_public: usize,
+
+    /// `A`, `B` and `C` of every constraint `A * B - C = 0`
+    constraints: Vec<[WireCombination<F>; 3]>,
+}
+
+impl<F> CircomR1cs<F>
+where
+    F: PrimeField,
+{
+    /// Reads the contents of an `.r1cs` file, whose prime must be the
+    /// modulus of `F`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let sections = read_sections(bytes, b"r1cs", 1)?;
+        let mut header = Reader(section(&sections, R1CS_HEADER)?);
+        let n8 = read_prime::<F>(&mut header)?;
+        let num_wires = header.u32()? as usize;
+        let num_outputs = header.u32()? as usize;
+        let num_public_inputs = header.u32()? as usize;
+        let _num_private_inputs = header.u32()?;
+        let _num_labels = header.u64()?;
+        let num_constraints = header.u32()? as usize;
+        // The sum of the counts may overflow on 32-bit targets
+        let num_public = num_outputs
+            .checked_add(num_public_inputs)
+            .filter(|num_public| *num_public < num_wires)
+            .ok_or(Error::InvalidCircomFile {
+                reason: "more public signals than wires",
+            })?;
+        // The wires are allocated when synthesizing the circuit, so their
+        // count is checked against the label of every wire.
+        let wire_labels = section(&sections, R1CS_WIRE_LABELS)?;
+        if num_wires.checked_mul(8) != Some(wire_labels.len()) {
+            return Err(Error::InvalidCircomFile {
+                reason: "wire count does not match the wire labels",
+            });
+        }
+
+        let mut reader = Reader(section(&sections, R1CS_CONSTRAINTS)?);
+        let mut combination = || -> Result<WireCombination<F>, Error> {
+            let len = reader.u32()? as usize;
+            // Don't allocate more terms than the section can hold
+            let max_len = reader.0.len() / (4 + n8);
+            let mut terms = Vec::with_capacity(len.min(max_len));
+            for _ in 0..len {
+                let wire = reader.u32()? as usize;
+                if wire >= num_wires {
+                    return Err(Error::InvalidCircomFile {
+                        reason: "constraint on an unknown wire",
+                    });
+                }
+                terms.push((wire, reader.field_element(n8)?));
+            }
+            Ok(terms)
+        };
+        let constraints = (0..num_constraints)
+            .map(|_| Ok([combination()?, combination()?, combination()?]))
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self {
+            num_wires,
+            num_public,
+            constraints,
+        })
+    }
+
+    /// Returns the number of wires, including the constant one.
+    pub fn num_wires(&self) -> usize {
+        self.num_wires
+    }
+
+    /// Returns the number of public signals, which come right after the
+    /// constant one in the wires.
+    pub fn num_public(&self) -> usize {
+        self.num_public
+    }
+
+    /// Returns the number of constraints.
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+}
+
+/// Reads the contents of a `.wtns` file, whose prime must be the modulus of
+/// `F`, and returns the value of every wire.
+pub fn read_witness<F>(bytes: &[u8]) -> Result<Vec<F>, Error>
+where
+    F: PrimeField,
+{
+    let sections = read_sections(bytes, b"wtns", 2)?;
+    let mut header = Reader(section(&sections, WTNS_HEADER)?);
+    let n8 = read_prime::<F>(&mut header)?;
+    let len = header.u32()? as usize;
+    let mut values = Reader(section(&sections, WTNS_VALUES)?);
+    (0..len).map(|_| values.field_element(n8)).collect()
+}
+
+/// Circom circuit, optionally with its witness.
+///
+/// Every Circom circuit has the same
+/// [`CIRCUIT_ID`](R1csSynthesizer::CIRCUIT_ID), so keys of different
+/// circuits must not share a key store.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircomCircuit<F>
+where
+    F: PrimeField,
+{
+    /// Constraints
+    r1cs: CircomR1cs<F>,
+
+    /// Value of every wire
+    witness: Option<Vec<F>>,
+}
+
+impl<F> CircomCircuit<F>
+where
+    F: PrimeField,
+{
+    /// Creates a circuit to be proven with `witness`, read by
+    /// [`read_witness`].
+    pub fn new(r1cs: CircomR1cs<F>, witness: Vec<F>) -> Result<Self, Error> {
+        if witness.len() != r1cs.num_wires || witness[0] != F::one() {
+            return Err(Error::InvalidCircomFile {
+                reason: "witness does not match the constraints",
+            });
+        }
+        Ok(Self {
+            r1cs,
+            witness: Some(witness),
+        })
+    }
+
+    /// Creates a circuit without a witness, to compile its keys through
+    /// [`R1csCircuit::setup`](super::R1csCircuit::setup).
+    pub fn without_witness(r1cs: CircomR1cs<F>) -> Self {
+        Self {
+            r1cs,
+            witness: None,
+        }
+    }
+
+    /// Returns the values of the public signals, if the witness is known.
+    pub fn public_inputs(&self) -> Option<&[F]> {
+        let witness = self.witness.as_ref()?;
+        Some(&witness[1..=self.r1cs.num_public])
+    }
+}
+
+impl<F> ConstraintSynthesizer<F> for CircomCircuit<F>
+where
+    F: PrimeField,
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<F>,
+    ) -> Result<(), SynthesisError> {
+        let witness = self.witness.as_deref();
+        let value = |wire: usize| {
+            witness
+                .map(|witness| witness[wire])
+                .ok_or(SynthesisError::AssignmentMissing)
+        };
+        let mut variables = Vec::with_capacity(self.r1cs.num_wires);
+        variables.push(Variable::One);
+        for wire in 1..self.r1cs.num_wires {
+            variables.push(if wire <= self.r1cs.num_public {
+                cs.new_input_variable(|| value(wire))?
+            } else {
+                cs.new_witness_variable(|| value(wire))?
+            });
+        }
+
+        let combination = |terms: &WireCombination<F>| {
+            LinearCombination(
+                terms
+                    .iter()
+                    .map(|(wire, coeff)| (*coeff, variables[*wire]))
+                    .collect(),
+            )
+        };
+        for [a, b, c] in &self.r1cs.constraints {
+            cs.enforce_constraint(
+                combination(a),
+                combination(b),
+                combination(c),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<F> R1csSynthesizer<F> for CircomCircuit<F>
+where
+    F: PrimeField,
+{
+    const CIRCUIT_ID: [u8; 32] = *b"ark-plonk/circom/r1cs-circuit-v1";
+}
+
+/// Reads the sections of a Circom binary file with the given magic number
+/// and version, as their type and contents.
+fn read_sections<'a>(
+    bytes: &'a [u8],
+    magic: &[u8; 4],
+    version: u32,
+) -> Result<Vec<(u32, &'a [u8])>, Error> {
+    let mut reader = Reader(bytes);
+    if reader.bytes(4)? != magic {
+        return Err(Error::InvalidCircomFile {
+            reason: "unknown magic number",
+        });
+    }
+    if reader.u32()? != version {
+        return Err(Error::InvalidCircomFile {
+            reason: "unsupported version",
+        });
+    }
+    let num_sections = reader.u32()?;
+    (0..num_sections)
+        .map(|_| {
+            let ty = reader.u32()?;
+            let len = reader.u64()?.try_into().map_err(|_| {
+                Error::InvalidCircomFile {
+                    reason: "section too large",
+                }
+            })?;
+            Ok((ty, reader.bytes(len)?))
+        })
+        .collect()
+}
+
+/// Returns the contents of the first section of type `ty`.
+fn section<'a>(
+    sections: &[(u32, &'a [u8])],
+    ty: u32,
+) -> Result<&'a [u8], Error> {
+    sections
+        .iter()
+        .find(|(section, _)| *section == ty)
+        .map(|(_, contents)| *contents)
+        .ok_or(Error::InvalidCircomFile {
+            reason: "missing section",
+        })
+}
+
+/// Reads the size of field elements and the prime of a header, checking
+/// that it is the modulus of `F`.
+fn read_prime<F>(header: &mut Reader) -> Result<usize, Error>
+where
+    F: PrimeField,
+{
+    let n8 = header.u32()? as usize;
+    let modulus = F::Params::MODULUS.to_bytes_le();
+    if n8 != modulus.len() || header.bytes(n8)? != modulus {
+        return Err(Error::InvalidCircomFile {
+            reason: "prime is not the scalar field modulus",
+        });
+    }
+    Ok(n8)
+}
+
+/// Little-endian reader over the bytes of a Circom file.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(Error::NotEnoughBytes);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        let bytes = self.bytes(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Reads a field element in canonical form.
+    fn field_element<F>(&mut self, n8: usize) -> Result<F, Error>
+    where
+        F: PrimeField,
+    {
+        let bytes = self.bytes(n8)?;
+        let element = F::from_le_bytes_mod_order(bytes);
+        if element.into_repr().to_bytes_le() != bytes {
+            return Err(Error::ScalarMalformed);
+        }
+        Ok(element)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::circuit::{verify_proof, Circuit, FeIntoPubInput};
+    use crate::constraint_system::StandardComposer;
+    use crate::r1cs::R1csCircuit;
+    use crate::testing::{public_parameters, unsatisfied_gate};
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::{PairingEngine, TEModelParameters};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    const R1CS: &[u8] =
+        include_bytes!("../../tests/fixtures/circom/example.r1cs");
+    const WTNS: &[u8] =
+        include_bytes!("../../tests/fixtures/circom/example.wtns");
+
+    fn test_read_circom_files<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let r1cs = CircomR1cs::<E::Fr>::from_bytes(R1CS).unwrap();
+        assert_eq!(r1cs.num_wires(), 5);
+        assert_eq!(r1cs.num_public(), 1);
+        assert_eq!(r1cs.num_constraints(), 2);
+        let witness = read_witness::<E::Fr>(WTNS).unwrap();
+        assert_eq!(witness, [1u64, 1097, 3, 11, 33].map(E::Fr::from).to_vec());
+
+        let circuit = CircomCircuit::new(r1cs, witness).unwrap();
+        assert_eq!(circuit.public_inputs(), Some(&[E::Fr::from(1097u64)][..]));
+        let cs = ConstraintSystem::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        let mut composer = StandardComposer::<E, P>::new();
+        composer.append_constraint_system(cs).unwrap();
+        assert_eq!(unsatisfied_gate(&composer), None);
+    }
+
+    fn test_invalid_circom_files<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        assert!(matches!(
+            CircomR1cs::<E::Fr>::from_bytes(WTNS),
+            Err(Error::InvalidCircomFile {
+                reason: "unknown magic number"
+            })
+        ));
+        assert!(matches!(
+            CircomR1cs::<E::Fr>::from_bytes(&R1CS[..R1CS.len() - 1]),
+            Err(Error::NotEnoughBytes)
+        ));
+        assert!(matches!(
+            CircomR1cs::<P::ScalarField>::from_bytes(R1CS),
+            Err(Error::InvalidCircomFile {
+                reason: "prime is not the scalar field modulus"
+            })
+        ));
+        assert!(read_witness::<P::ScalarField>(WTNS).is_err());
+
+        // Public signal counts of `u32::MAX` are refused
+        let mut r1cs = R1CS.to_vec();
+        r1cs[0x40..0x48].fill(0xff);
+        assert!(matches!(
+            CircomR1cs::<E::Fr>::from_bytes(&r1cs),
+            Err(Error::InvalidCircomFile {
+                reason: "more public signals than wires"
+            })
+        ));
+
+        // So are wire counts of `u32::MAX` without as many wire labels
+        let mut r1cs = R1CS.to_vec();
+        r1cs[0x3c..0x40].fill(0xff);
+        assert!(matches!(
+            CircomR1cs::<E::Fr>::from_bytes(&r1cs),
+            Err(Error::InvalidCircomFile {
+                reason: "wire count does not match the wire labels"
+            })
+        ));
+
+        // Huge constraint and term counts in a truncated file are refused
+        // without being allocated first
+        let mut r1cs = R1CS[..0x58].to_vec();
+        r1cs[0x54..0x58].fill(0xff);
+        r1cs.extend(2u32.to_le_bytes());
+        r1cs.extend(4u64.to_le_bytes());
+        r1cs.extend(u32::MAX.to_le_bytes());
+        r1cs.extend(3u32.to_le_bytes());
+        r1cs.extend(40u64.to_le_bytes());
+        r1cs.extend([0; 40]);
+        assert!(matches!(
+            CircomR1cs::<E::Fr>::from_bytes(&r1cs),
+            Err(Error::NotEnoughBytes)
+        ));
+
+        let r1cs = CircomR1cs::<E::Fr>::from_bytes(R1CS).unwrap();
+        let mut witness = read_witness::<E::Fr>(WTNS).unwrap();
+        witness.pop();
+        assert!(CircomCircuit::new(r1cs.clone(), witness).is_err());
+
+        // A wrong output still synthesizes but fails to satisfy the gates
+        let mut witness = read_witness::<E::Fr>(WTNS).unwrap();
+        witness[1] += E::Fr::from(1u64);
+        let cs = ConstraintSystem::new_ref();
+        CircomCircuit::new(r1cs, witness)
+            .unwrap()
+            .generate_constraints(cs.clone())
+            .unwrap();
+        let mut composer = StandardComposer::<E, P>::new();
+        composer.append_constraint_system(cs).unwrap();
+        assert!(unsatisfied_gate(&composer).is_some());
+    }
+
+    fn test_circom_circuit<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let r1cs = CircomR1cs::<E::Fr>::from_bytes(R1CS).unwrap();
+        let mut circuit = R1csCircuit::<E, P, _>::setup(
+            CircomCircuit::without_witness(r1cs.clone()),
+        )
+        .unwrap();
+        let pp = public_parameters::<E>(circuit.padded_circuit_size()).unwrap();
+        let (pk, vd) = circuit.compile(&pp).unwrap();
+
+        let witness = read_witness(WTNS).unwrap();
+        let circom = CircomCircuit::new(r1cs, witness).unwrap();
+        let public_inputs: Vec<_> = circom
+            .public_inputs()
+            .unwrap()
+            .iter()
+            .map(|pi| FeIntoPubInput::<_>::into_pi(*pi))
+            .collect();
+        let mut circuit = R1csCircuit::<E, P, _>::new(circom).unwrap();
+        let proof = circuit.gen_proof(&pp, pk, b"Circom").unwrap();
+        verify_proof::<E, P>(
+            &pp,
+            vd.key,
+            &proof,
+            &public_inputs,
+            &vd.pi_pos,
+            b"Circom",
+        )
+        .unwrap();
+    }
+
+    // The fixtures are over the Bls12_381 scalar field
+    batch_test!(
+        [
+            test_read_circom_files,
+            test_invalid_circom_files,
+            test_circom_circuit
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+}
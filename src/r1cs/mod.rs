@@ -0,0 +1,525 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Compiles `ark-relations` R1CS circuits to PLONK.
+//!
+//! Every R1CS constraint `<a, z> * <b, z> = <c, z>` is lowered to a single
+//! [`StandardComposer::big_arith_gate`] once its linear combinations are
+//! folded down to the wires of the gate. The instance variables, except for
+//! the leading one, become public inputs in the same order, so a proof is
+//! verified with the R1CS instance as public input values.
+
+use crate::circuit::Circuit;
+use crate::constraint_system::{StandardComposer, Variable};
+use crate::error::Error;
+use alloc::vec::Vec;
+use ark_ec::{PairingEngine, TEModelParameters};
+use ark_ff::Field;
+use ark_relations::r1cs::{
+    ConstraintMatrices, ConstraintSynthesizer, ConstraintSystem,
+    ConstraintSystemRef, OptimizationGoal, SynthesisError, SynthesisMode,
+};
+use core::marker::PhantomData;
+use num_traits::{One, Zero};
+
+pub mod circom;
+
+/// R1CS circuit with an identifier, so that it can be compiled as a PLONK
+/// [`Circuit`] through [`R1csCircuit`].
+pub trait R1csSynthesizer<F>: ConstraintSynthesizer<F>
+where
+    F: Field,
+{
+    /// Circuit identifier, see [`Circuit::CIRCUIT_ID`].
+    const CIRCUIT_ID: [u8; 32];
+}
+
+/// Synthesized R1CS circuit implementing [`Circuit`].
+///
+/// The circuit is synthesized once, when the wrapper is built, and lowered
+/// into the composer by [`Circuit::gadget`]. It is also lowered into a
+/// scratch composer at that point to find its size.
+#[derive(derivative::Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct R1csCircuit<E, P, C>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Constraint Matrices
+    matrices: ConstraintMatrices<E::Fr>,
+
+    /// Values of the instance variables followed by the witness variables
+    assignment: Vec<E::Fr>,
+
+    /// Size of the circuit once lowered
+    circuit_size: usize,
+
+    /// Type Parameter Marker
+    __: PhantomData<(P, C)>,
+}
+
+impl<E, P, C> R1csCircuit<E, P, C>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+    C: R1csSynthesizer<E::Fr>,
+{
+    /// Synthesizes `circuit`, which must assign all of its variables.
+    pub fn new(circuit: C) -> Result<Self, Error> {
+        Self::synthesize(
+            circuit,
+            SynthesisMode::Prove {
+                construct_matrices: true,
+            },
+        )
+    }
+
+    /// Synthesizes `circuit` without assigning its variables, which are set
+    /// to zero. The result can only be compiled into keys.
+    pub fn setup(circuit: C) -> Result<Self, Error> {
+        Self::synthesize(circuit, SynthesisMode::Setup)
+    }
+
+    fn synthesize(circuit: C, mode: SynthesisMode) -> Result<Self, Error> {
+        let cs = ConstraintSystem::new_ref();
+        cs.set_optimization_goal(OptimizationGoal::Constraints);
+        cs.set_mode(mode);
+        circuit.generate_constraints(cs.clone())?;
+        let (matrices, assignment) = finalize(cs)?;
+        let mut composer = StandardComposer::<E, P>::new();
+        composer.append_r1cs(&matrices, &assignment);
+        Ok(Self {
+            circuit_size: composer.circuit_size(),
+            matrices,
+            assignment,
+            __: PhantomData,
+        })
+    }
+
+    /// Returns the values of the instance variables, without the leading
+    /// one, which are the public inputs of the circuit.
+    pub fn instance(&self) -> &[E::Fr] {
+        &self.assignment[1..self.matrices.num_instance_variables]
+    }
+}
+
+impl<E, P, C> Circuit<E, P> for R1csCircuit<E, P, C>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+    C: R1csSynthesizer<E::Fr>,
+{
+    const CIRCUIT_ID: [u8; 32] = C::CIRCUIT_ID;
+
+    fn gadget(
+        &mut self,
+        composer: &mut StandardComposer<E, P>,
+    ) -> Result<(), Error> {
+        composer.append_r1cs(&self.matrices, &self.assignment);
+        Ok(())
+    }
+
+    fn padded_circuit_size(&self) -> usize {
+        self.circuit_size.next_power_of_two()
+    }
+}
+
+impl<E, P> StandardComposer<E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Lowers the constraints of `cs` into gates of the composer.
+    ///
+    /// Returns the composer [`Variable`]s of the R1CS variables, indexed as
+    /// in the constraint matrices: the one, the instance variables and the
+    /// witness variables. Variables of a constraint system in setup mode are
+    /// set to zero.
+    pub fn append_constraint_system(
+        &mut self,
+        cs: ConstraintSystemRef<E::Fr>,
+    ) -> Result<Vec<Variable>, Error> {
+        let (matrices, assignment) = finalize(cs)?;
+        Ok(self.append_r1cs(&matrices, &assignment))
+    }
+
+    /// Lowers the constraint `matrices` with the given `assignment`.
+    fn append_r1cs(
+        &mut self,
+        matrices: &ConstraintMatrices<E::Fr>,
+        assignment: &[E::Fr],
+    ) -> Vec<Variable> {
+        let num_instance = matrices.num_instance_variables;
+        let mut variables = Vec::with_capacity(assignment.len());
+        variables.push(self.add_witness_to_circuit_description(E::Fr::one()));
+        for value in &assignment[1..num_instance] {
+            let var = self.add_input(*value);
+            self.constrain_to_constant(var, E::Fr::zero(), Some(-*value));
+            variables.push(var);
+        }
+        for value in &assignment[num_instance..] {
+            variables.push(self.add_input(*value));
+        }
+
+        for ((a, b), c) in matrices.a.iter().zip(&matrices.b).zip(&matrices.c) {
+            let (a_0, a) = split_constant(a, &variables);
+            let (b_0, b) = split_constant(b, &variables);
+            let (c_0, c) = split_constant(c, &variables);
+            if a.is_empty() || b.is_empty() {
+                self.linear_r1cs_constraint(a_0, a, b_0, b, c_0, c);
+            } else {
+                self.mul_r1cs_constraint((a_0, a), (b_0, b), (c_0, c));
+            }
+        }
+        variables
+    }
+
+    /// Adds `(a_0 + a) * (b_0 + b) = c_0 + c` when `a` or `b` has no
+    /// variable, in which case the constraint is linear.
+    fn linear_r1cs_constraint(
+        &mut self,
+        a_0: E::Fr,
+        a: Vec<(E::Fr, Variable)>,
+        b_0: E::Fr,
+        b: Vec<(E::Fr, Variable)>,
+        c_0: E::Fr,
+        c: Vec<(E::Fr, Variable)>,
+    ) {
+        let scaled = |lc: Vec<(E::Fr, Variable)>, scale: E::Fr| {
+            lc.into_iter().map(move |(coeff, var)| (coeff * scale, var))
+        };
+        let terms = scaled(a, b_0)
+            .chain(scaled(b, a_0))
+            .chain(scaled(c, -E::Fr::one()))
+            .filter(|(coeff, _)| !coeff.is_zero())
+            .collect();
+        let constant = a_0 * b_0 - c_0;
+        let terms = self.fold_terms(terms, 4);
+        if terms.is_empty() && constant.is_zero() {
+            return;
+        }
+        let [l, r, o, d] = self.pad_terms::<4>(&terms);
+        self.big_arith_gate(
+            l.1,
+            r.1,
+            o.1,
+            Some(d.1),
+            E::Fr::zero(),
+            l.0,
+            r.0,
+            o.0,
+            constant,
+            d.0,
+            None,
+        );
+    }
+
+    /// Adds `(a_0 + a) * (b_0 + b) = c_0 + c` as a single gate multiplying
+    /// `a` and `b`, once each one is folded into a single variable.
+    fn mul_r1cs_constraint(
+        &mut self,
+        (a_0, a): (E::Fr, Vec<(E::Fr, Variable)>),
+        (b_0, b): (E::Fr, Vec<(E::Fr, Variable)>),
+        (c_0, c): (E::Fr, Vec<(E::Fr, Variable)>),
+    ) {
+        let (q_a, a) = self.fold_terms(a, 1)[0];
+        let (q_b, b) = self.fold_terms(b, 1)[0];
+        let c = self.fold_terms(c, 2);
+        let [c, d] = self.pad_terms::<2>(&c);
+        self.big_arith_gate(
+            a,
+            b,
+            c.1,
+            Some(d.1),
+            q_a * q_b,
+            q_a * b_0,
+            q_b * a_0,
+            -c.0,
+            a_0 * b_0 - c_0,
+            -d.0,
+            None,
+        );
+    }
+
+    /// Folds `terms` into new variables holding their sums until at most
+    /// `max` of them are left.
+    fn fold_terms(
+        &mut self,
+        mut terms: Vec<(E::Fr, Variable)>,
+        max: usize,
+    ) -> Vec<(E::Fr, Variable)> {
+        while terms.len() > max {
+            let folded = terms.len().min(3).min(terms.len() - max + 1);
+            let [l, r, d] =
+                self.pad_terms::<3>(&terms.split_off(terms.len() - folded));
+            let sum = self.big_add(l, r, Some(d), E::Fr::zero(), None);
+            terms.push((E::Fr::one(), sum));
+        }
+        terms
+    }
+
+    /// Pads `terms` with zero terms up to `N` of them.
+    fn pad_terms<const N: usize>(
+        &self,
+        terms: &[(E::Fr, Variable)],
+    ) -> [(E::Fr, Variable); N] {
+        let mut padded = [(E::Fr::zero(), self.zero_var); N];
+        padded[..terms.len()].copy_from_slice(terms);
+        padded
+    }
+}
+
+/// Inlines the linear combinations of `cs` and returns its constraint
+/// matrices together with the values of its variables, set to zero in setup
+/// mode.
+fn finalize<F>(
+    cs: ConstraintSystemRef<F>,
+) -> Result<(ConstraintMatrices<F>, Vec<F>), Error>
+where
+    F: Field,
+{
+    cs.finalize();
+    let matrices = cs.to_matrices().ok_or(SynthesisError::MissingCS)?;
+    let cs = cs.borrow().ok_or(SynthesisError::MissingCS)?;
+    let assignment = if cs.is_in_setup_mode() {
+        vec![
+            F::zero();
+            matrices.num_instance_variables + matrices.num_witness_variables
+        ]
+    } else {
+        cs.instance_assignment
+            .iter()
+            .chain(&cs.witness_assignment)
+            .copied()
+            .collect()
+    };
+    Ok((matrices, assignment))
+}
+
+/// Splits the R1CS linear combination `lc` into its constant and its terms
+/// over the composer `variables`.
+fn split_constant<F>(
+    lc: &[(F, usize)],
+    variables: &[Variable],
+) -> (F, Vec<(F, Variable)>)
+where
+    F: Field,
+{
+    let mut constant = F::zero();
+    let mut terms = Vec::with_capacity(lc.len());
+    for (coeff, index) in lc {
+        match index {
+            0 => constant += coeff,
+            _ => terms.push((*coeff, variables[*index])),
+        }
+    }
+    (constant, terms)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::circuit::{verify_proof, FeIntoPubInput, PublicInputValue};
+    use crate::testing::{public_parameters, unsatisfied_gate};
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_relations::lc;
+    use ark_relations::r1cs::Variable as R1csVariable;
+
+    /// Proves knowledge of `x` and `y` with `x * y = z` and
+    /// `(x + 2y + 3z + 4) * (x - y + 5) = w + y + z + 7`, where `z` and the
+    /// sum `x + y + z` are public.
+    #[derive(Clone)]
+    struct TestCircuit<F> {
+        x: Option<F>,
+        y: Option<F>,
+        z: Option<F>,
+    }
+
+    impl<F> TestCircuit<F>
+    where
+        F: Field,
+    {
+        fn new(x: u64, y: u64) -> Self {
+            Self {
+                x: Some(F::from(x)),
+                y: Some(F::from(y)),
+                z: Some(F::from(x * y)),
+            }
+        }
+    }
+
+    impl<F> ConstraintSynthesizer<F> for TestCircuit<F>
+    where
+        F: Field,
+    {
+        fn generate_constraints(
+            self,
+            cs: ConstraintSystemRef<F>,
+        ) -> Result<(), SynthesisError> {
+            let value =
+                |v: Option<F>| v.ok_or(SynthesisError::AssignmentMissing);
+            let z = cs.new_input_variable(|| value(self.z))?;
+            let sum = cs.new_input_variable(|| {
+                Ok(value(self.x)? + value(self.y)? + value(self.z)?)
+            })?;
+            let x = cs.new_witness_variable(|| value(self.x))?;
+            let y = cs.new_witness_variable(|| value(self.y))?;
+            let w = cs.new_witness_variable(|| {
+                let (x, y, z) =
+                    (value(self.x)?, value(self.y)?, value(self.z)?);
+                let two = F::from(2u64);
+                Ok((x + two * y + F::from(3u64) * z + F::from(4u64))
+                    * (x - y + F::from(5u64))
+                    - y
+                    - z
+                    - F::from(7u64))
+            })?;
+
+            cs.enforce_constraint(lc!() + x, lc!() + y, lc!() + z)?;
+            cs.enforce_constraint(
+                lc!()
+                    + x
+                    + (F::from(2u64), y)
+                    + (F::from(3u64), z)
+                    + (F::from(4u64), R1csVariable::One),
+                lc!() + x - y + (F::from(5u64), R1csVariable::One),
+                lc!() + w + y + z + (F::from(7u64), R1csVariable::One),
+            )?;
+            cs.enforce_constraint(
+                lc!() + R1csVariable::One,
+                lc!() + x + y + z,
+                lc!() + sum,
+            )?;
+            Ok(())
+        }
+    }
+
+    impl<F> R1csSynthesizer<F> for TestCircuit<F>
+    where
+        F: Field,
+    {
+        const CIRCUIT_ID: [u8; 32] = [0x1c; 32];
+    }
+
+    fn test_append_constraint_system<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let cs = ConstraintSystem::new_ref();
+        TestCircuit::new(3, 5)
+            .generate_constraints(cs.clone())
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        let witness = cs.borrow().unwrap().witness_assignment.clone();
+
+        let mut composer = StandardComposer::<E, P>::new();
+        let variables = composer.append_constraint_system(cs).unwrap();
+        assert_eq!(unsatisfied_gate(&composer), None);
+        assert_eq!(composer.pi_positions().len(), 2);
+        assert_eq!(composer.variables[&variables[0]], E::Fr::one());
+        assert_eq!(composer.variables[&variables[1]], E::Fr::from(15u64));
+        assert_eq!(composer.variables[&variables[3]], witness[0]);
+    }
+
+    fn test_unsatisfied_constraint_system<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let cs = ConstraintSystem::new_ref();
+        let circuit = TestCircuit {
+            z: Some(E::Fr::from(16u64)),
+            ..TestCircuit::new(3, 5)
+        };
+        circuit.generate_constraints(cs.clone()).unwrap();
+
+        let mut composer = StandardComposer::<E, P>::new();
+        composer.append_constraint_system(cs).unwrap();
+        assert!(unsatisfied_gate(&composer).is_some());
+    }
+
+    fn test_missing_assignment<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let circuit = TestCircuit::<E::Fr> {
+            x: None,
+            y: None,
+            z: None,
+        };
+        assert!(matches!(
+            R1csCircuit::<E, P, _>::new(circuit.clone()),
+            Err(Error::SynthesisError { error })
+                if error == SynthesisError::AssignmentMissing.to_string()
+        ));
+        // Variables are set to zero in setup mode
+        let circuit = R1csCircuit::<E, P, _>::setup(circuit).unwrap();
+        assert!(circuit.instance().iter().all(Zero::is_zero));
+        assert_eq!(circuit.padded_circuit_size(), 16);
+    }
+
+    fn test_r1cs_circuit<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut circuit = R1csCircuit::<E, P, _>::setup(TestCircuit {
+            x: None,
+            y: None,
+            z: None,
+        })
+        .unwrap();
+        let pp = public_parameters::<E>(circuit.padded_circuit_size()).unwrap();
+        let (pk, vd) = circuit.compile(&pp).unwrap();
+
+        let mut circuit =
+            R1csCircuit::<E, P, _>::new(TestCircuit::new(3, 5)).unwrap();
+        let proof = circuit.gen_proof(&pp, pk, b"R1CS").unwrap();
+        let public_inputs: Vec<PublicInputValue<P>> = circuit
+            .instance()
+            .iter()
+            .map(|pi| (*pi).into_pi())
+            .collect();
+        assert_eq!(public_inputs.len(), 2);
+        verify_proof(&pp, vd.key, &proof, &public_inputs, &vd.pi_pos, b"R1CS")
+            .unwrap();
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_append_constraint_system,
+            test_unsatisfied_constraint_system,
+            test_missing_assignment,
+            test_r1cs_circuit
+        ],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_append_constraint_system,
+            test_unsatisfied_constraint_system,
+            test_missing_assignment,
+            test_r1cs_circuit
+        ],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
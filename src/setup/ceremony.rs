@@ -0,0 +1,375 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Local updatable setup ceremony.
+//!
+//! Every participant multiplies the powers of `tau` held by a [`Ceremony`] by
+//! the powers of their secret `s`, and appends a [`Contribution`] proving
+//! knowledge of `s`. Nobody knows the final `tau` as long as a single
+//! participant discarded their secret. Ceremonies are exchanged as files with
+//! [`CanonicalSerialize`] and must be [verified](Ceremony::verify) when read.
+
+use super::{universal_params, verify_powers};
+use crate::error::Error;
+use crate::transcript::{TranscriptProtocol, TranscriptWrapper};
+use alloc::vec;
+use alloc::vec::Vec;
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::UniformRand;
+use ark_poly_commit::kzg10::{Commitment, UniversalParams};
+use ark_serialize::*;
+use num_traits::Zero;
+use rand_core::{CryptoRng, RngCore};
+
+/// Proof that a participant updated the powers of a [`Ceremony`] with a
+/// secret `s` they know.
+#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+#[derivative(
+    Clone(bound = ""),
+    Copy(bound = ""),
+    Debug(bound = ""),
+    Eq(bound = ""),
+    PartialEq(bound = "")
+)]
+pub struct Contribution<E>
+where
+    E: PairingEngine,
+{
+    /// `tau * g` after the contribution
+    pub tau_g: E::G1Affine,
+
+    /// `s * g`
+    pub s_g: E::G1Affine,
+
+    /// `s * h`
+    pub s_h: E::G2Affine,
+
+    /// Schnorr commitment `k * g` to a random nonce `k`
+    commitment: E::G1Affine,
+
+    /// Schnorr response `k + c * s` to the challenge `c`
+    response: E::Fr,
+}
+
+impl<E> Contribution<E>
+where
+    E: PairingEngine,
+{
+    /// Computes the Schnorr challenge, binding the proof to the powers it
+    /// updates.
+    fn challenge(
+        previous_tau_g: E::G1Affine,
+        s_g: E::G1Affine,
+        commitment: E::G1Affine,
+    ) -> E::Fr {
+        let mut transcript = TranscriptWrapper::<E>::new(b"ceremony");
+        transcript
+            .append_commitment(b"previous_tau_g", &Commitment(previous_tau_g));
+        transcript.append_commitment(b"s_g", &Commitment(s_g));
+        transcript.append_commitment(b"commitment", &Commitment(commitment));
+        transcript.challenge_scalar(b"challenge")
+    }
+
+    /// Checks this contribution against `tau * g` before it.
+    fn verify(&self, previous_tau_g: E::G1Affine) -> bool {
+        let g = E::G1Affine::prime_subgroup_generator();
+        let h = E::G2Affine::prime_subgroup_generator();
+        if self.s_g.is_zero() {
+            return false;
+        }
+
+        // Knowledge of `s`
+        let challenge =
+            Self::challenge(previous_tau_g, self.s_g, self.commitment);
+        let knows_s = g.mul(self.response)
+            == self.commitment.into_projective() + self.s_g.mul(challenge);
+
+        // Same `s` in both groups, and `tau * g` updated by `s`
+        let same_s = E::pairing(self.s_g, h) == E::pairing(g, self.s_h);
+        let updated =
+            E::pairing(self.tau_g, h) == E::pairing(previous_tau_g, self.s_h);
+
+        knows_s && same_s && updated
+    }
+}
+
+/// Powers of `tau` for polynomials of degree at most a given maximum, with
+/// the chain of contributions they were derived from.
+#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct Ceremony<E>
+where
+    E: PairingEngine,
+{
+    /// Powers of the current `tau`
+    params: UniversalParams<E>,
+
+    /// Contributions, in order
+    contributions: Vec<Contribution<E>>,
+}
+
+impl<E> Ceremony<E>
+where
+    E: PairingEngine,
+{
+    /// Starts a ceremony for polynomials of degree at most `max_degree`, with
+    /// `tau = 1` and no contributions.
+    pub fn new(max_degree: usize) -> Result<Self, Error> {
+        if max_degree == 0 {
+            return Err(Error::DegreeIsZero);
+        }
+        let h = E::G2Affine::prime_subgroup_generator();
+        Ok(Self {
+            params: universal_params(
+                vec![E::G1Affine::prime_subgroup_generator(); max_degree + 1],
+                h,
+                h,
+            ),
+            contributions: Vec::new(),
+        })
+    }
+
+    /// Returns the current powers of `tau`.
+    pub fn params(&self) -> &UniversalParams<E> {
+        &self.params
+    }
+
+    /// Returns the current powers of `tau`, consuming the ceremony.
+    pub fn into_params(self) -> UniversalParams<E> {
+        self.params
+    }
+
+    /// Returns the contributions, in order.
+    pub fn contributions(&self) -> &[Contribution<E>] {
+        &self.contributions
+    }
+
+    /// Updates the powers with a fresh secret drawn from `rng`, which is
+    /// dropped once the [`Contribution`] proving knowledge of it is appended.
+    pub fn contribute<R>(&mut self, rng: &mut R) -> &Contribution<E>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let g = E::G1Affine::prime_subgroup_generator();
+        let h = E::G2Affine::prime_subgroup_generator();
+        let s = loop {
+            let s = E::Fr::rand(rng);
+            if !s.is_zero() {
+                break s;
+            }
+        };
+
+        let powers_of_g = self
+            .params
+            .powers_of_g
+            .iter()
+            .zip(crate::util::powers_of(s))
+            .map(|(power, s_i)| power.mul(s_i))
+            .collect::<Vec<_>>();
+        let previous_tau_g = self.params.powers_of_g[1];
+        self.params = universal_params(
+            E::G1Projective::batch_normalization_into_affine(&powers_of_g),
+            h,
+            self.params.beta_h.mul(s).into_affine(),
+        );
+
+        let s_g = g.mul(s).into_affine();
+        let nonce = E::Fr::rand(rng);
+        let commitment = g.mul(nonce).into_affine();
+        let challenge =
+            Contribution::<E>::challenge(previous_tau_g, s_g, commitment);
+        self.contributions.push(Contribution {
+            tau_g: self.params.powers_of_g[1],
+            s_g,
+            s_h: h.mul(s).into_affine(),
+            commitment,
+            response: nonce + challenge * s,
+        });
+        self.contributions.last().unwrap()
+    }
+
+    /// Checks every contribution in order, then that the powers are those of
+    /// the `tau` they lead to.
+    ///
+    /// Fails with [`Error::InvalidContribution`] on the first invalid
+    /// contribution, and [`Error::PairingCheckFailure`] if the powers do not
+    /// match.
+    pub fn verify<R>(&self, rng: &mut R) -> Result<(), Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let g = E::G1Affine::prime_subgroup_generator();
+        let mut tau_g = g;
+        for (index, contribution) in self.contributions.iter().enumerate() {
+            if !contribution.verify(tau_g) {
+                return Err(Error::InvalidContribution { index });
+            }
+            tau_g = contribution.tau_g;
+        }
+
+        let powers_of_g = &self.params.powers_of_g;
+        if powers_of_g.len() < 2
+            || powers_of_g[0] != g
+            || powers_of_g[1] != tau_g
+            || self.params.h != E::G2Affine::prime_subgroup_generator()
+        {
+            return Err(Error::PairingCheckFailure);
+        }
+        verify_powers(&self.params, rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::circuit::{verify_proof, Circuit};
+    use crate::constraint_system::StandardComposer;
+    use crate::setup::{powers_of_tau, truncate, PublicParameters};
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::TEModelParameters;
+    use core::marker::PhantomData;
+    use rand_core::OsRng;
+
+    /// Circuit adding two private inputs.
+    #[derive(derivative::Derivative)]
+    #[derivative(Default(bound = ""))]
+    struct AddCircuit<E, P>(PhantomData<(E, P)>);
+
+    impl<E, P> Circuit<E, P> for AddCircuit<E, P>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        const CIRCUIT_ID: [u8; 32] = [0x0c; 32];
+
+        fn gadget(
+            &mut self,
+            composer: &mut StandardComposer<E, P>,
+        ) -> Result<(), Error> {
+            let a = composer.add_input(E::Fr::from(2u64));
+            let b = composer.add_input(E::Fr::from(3u64));
+            let c = composer.add_input(E::Fr::from(5u64));
+            let sum = composer.add(
+                (E::Fr::from(1u64), a),
+                (E::Fr::from(1u64), b),
+                E::Fr::from(0u64),
+                None,
+            );
+            composer.assert_equal(sum, c);
+            Ok(())
+        }
+
+        fn padded_circuit_size(&self) -> usize {
+            1 << 3
+        }
+    }
+
+    fn test_ceremony<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut ceremony = Ceremony::<E>::new(1 << 5).unwrap();
+        ceremony.verify(&mut OsRng).unwrap();
+        for _ in 0..3 {
+            ceremony.contribute(&mut OsRng);
+        }
+        assert_eq!(ceremony.contributions().len(), 3);
+
+        // Ceremonies are exchanged as files
+        let mut bytes = Vec::new();
+        ceremony.serialize(&mut bytes).unwrap();
+        let ceremony = Ceremony::<E>::deserialize(&bytes[..]).unwrap();
+        ceremony.verify(&mut OsRng).unwrap();
+
+        // The result proves and verifies like any KZG setup
+        let params = PublicParameters::from(
+            truncate(ceremony.params(), 1 << 4).unwrap(),
+        );
+        let mut circuit = AddCircuit::<E, P>::default();
+        let (prover_key, verifier_data) = circuit.compile(&params).unwrap();
+        let proof = circuit.gen_proof(&params, prover_key, b"Test").unwrap();
+        verify_proof(
+            &params,
+            verifier_data.key,
+            &proof,
+            &[],
+            &verifier_data.pi_pos,
+            b"Test",
+        )
+        .unwrap();
+    }
+
+    fn test_invalid_ceremony<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut ceremony = Ceremony::<E>::new(4).unwrap();
+        ceremony.contribute(&mut OsRng);
+        ceremony.contribute(&mut OsRng);
+
+        // Replacing a contribution breaks the chain after it
+        let mut other = Ceremony::<E>::new(4).unwrap();
+        other.contribute(&mut OsRng);
+        let mut forged = ceremony.clone();
+        forged.contributions[0] = other.contributions[0];
+        assert!(matches!(
+            forged.verify(&mut OsRng),
+            Err(Error::InvalidContribution { index: 1 })
+        ));
+
+        // Contributions prove knowledge of their secret
+        let mut forged = ceremony.clone();
+        forged.contributions[0].response += E::Fr::from(1u64);
+        assert!(matches!(
+            forged.verify(&mut OsRng),
+            Err(Error::InvalidContribution { index: 0 })
+        ));
+
+        // Powers must match the contributions
+        let tau = E::Fr::rand(&mut OsRng);
+        let h = E::G2Affine::prime_subgroup_generator();
+        let mut forged = ceremony.clone();
+        forged.params = universal_params(
+            powers_of_tau(E::G1Affine::prime_subgroup_generator(), tau, 4),
+            h,
+            h.mul(tau).into_affine(),
+        );
+        assert!(matches!(
+            forged.verify(&mut OsRng),
+            Err(Error::PairingCheckFailure)
+        ));
+        let mut forged = ceremony;
+        forged.params.powers_of_g.swap(2, 3);
+        assert!(matches!(
+            forged.verify(&mut OsRng),
+            Err(Error::PairingCheckFailure)
+        ));
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [test_ceremony, test_invalid_ceremony],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [test_ceremony, test_invalid_ceremony],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
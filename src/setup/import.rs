@@ -0,0 +1,580 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Parsers for powers-of-tau ceremony files.
+
+use super::{universal_params, verify_powers};
+use crate::error::Error;
+use alloc::vec;
+use alloc::vec::Vec;
+use ark_ec::{AffineCurve, PairingEngine};
+use ark_poly_commit::kzg10::UniversalParams;
+use ark_serialize::*;
+use num_traits::Zero;
+use rand_core::{CryptoRng, RngCore};
+
+/// Size of the hash prefixing powers-of-tau files.
+const HASH_SIZE: usize = 64;
+
+/// Layout of a powers-of-tau file, as produced by the Zcash `powersoftau`
+/// ceremony and the Perpetual Powers of Tau.
+///
+/// The file holds a 64 byte hash, followed by the `2^(power + 1) - 1` G1 and
+/// `2^power` G2 powers of `tau`. Coordinates are big-endian, with the
+/// `flags` in the top bits of the first byte.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PowersOfTau {
+    /// Base two logarithm of the number of G2 powers.
+    pub power: u32,
+
+    /// Whether the points are compressed, as in `response` files, or not, as
+    /// in `challenge` files.
+    pub compressed: bool,
+
+    /// Layout of the flags of every point.
+    pub flags: PointFlags,
+}
+
+/// Layout of the flags stored in the top bits of the first byte of a point.
+///
+/// How many flags fit depends on the bits the coordinates of the curve leave
+/// unused.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointFlags {
+    /// Compression (`0x80`), infinity (`0x40`) and greatest `y` (`0x20`)
+    /// flags, as serialized by the Zcash ceremony over BLS12-381.
+    Zcash,
+
+    /// Infinity (`0x40`) and, on compressed points only, greatest `y`
+    /// (`0x80`) flags, as serialized by the ceremonies over BN254.
+    Bn254,
+}
+
+impl PointFlags {
+    /// Bits of the first byte holding the flags.
+    fn mask(self) -> u8 {
+        match self {
+            Self::Zcash => 0xe0,
+            Self::Bn254 => 0xc0,
+        }
+    }
+
+    /// Bit set on compressed points, if the layout records the compression.
+    fn compression(self) -> Option<u8> {
+        match self {
+            Self::Zcash => Some(0x80),
+            Self::Bn254 => None,
+        }
+    }
+
+    /// Bit set on the point at infinity.
+    fn infinity(self) -> u8 {
+        0x40
+    }
+
+    /// Bit set on compressed points with the greatest of the two possible
+    /// `y`.
+    fn greatest_y(self) -> u8 {
+        match self {
+            Self::Zcash => 0x20,
+            Self::Bn254 => 0x80,
+        }
+    }
+}
+
+impl PowersOfTau {
+    /// `challenge` file of the Zcash Sapling phase 1 ceremony over
+    /// BLS12-381.
+    pub const ZCASH_SAPLING: Self = Self {
+        power: 21,
+        compressed: false,
+        flags: PointFlags::Zcash,
+    };
+
+    /// `challenge` file of the Perpetual Powers of Tau ceremony over BN254,
+    /// with `2^power` G2 powers.
+    pub fn perpetual(power: u32) -> Self {
+        Self {
+            power,
+            compressed: false,
+            flags: PointFlags::Bn254,
+        }
+    }
+}
+
+/// Encoding of the points of a ceremony file.
+#[derive(Clone, Copy, Debug)]
+enum Encoding {
+    /// Big-endian coordinates, with `flags` in the top bits of the first
+    /// byte. Extension field coefficients start from the highest.
+    Zcash {
+        /// Whether only `x` and the sign of `y` are stored
+        compressed: bool,
+
+        /// Layout of the flags
+        flags: PointFlags,
+    },
+
+    /// Coordinates as 64-bit big-endian limbs, from the least significant.
+    /// Extension field coefficients start from the lowest. There are no
+    /// flags: the transcripts hold no point at infinity, and a set top bit
+    /// makes the coordinate exceed the modulus.
+    Aztec,
+}
+
+/// Reads a powers-of-tau file with the given `layout`, keeping the powers
+/// for polynomials of degree at most `max_degree`, and checks that they are
+/// well-formed with [`verify_powers`].
+pub fn read_powers_of_tau<E, R, RNG>(
+    mut reader: R,
+    layout: PowersOfTau,
+    max_degree: usize,
+    rng: &mut RNG,
+) -> Result<UniversalParams<E>, Error>
+where
+    E: PairingEngine,
+    R: Read,
+    RNG: CryptoRng + RngCore,
+{
+    if max_degree == 0 {
+        return Err(Error::DegreeIsZero);
+    }
+    // Layouts too large to be addressed can't describe a readable file
+    let num_g1_powers = layout
+        .power
+        .checked_add(1)
+        .and_then(|power| 1usize.checked_shl(power))
+        .ok_or(SerializationError::InvalidData)?
+        - 1;
+    if max_degree >= num_g1_powers {
+        return Err(Error::TruncatedDegreeTooLarge);
+    }
+    let encoding = Encoding::Zcash {
+        compressed: layout.compressed,
+        flags: layout.flags,
+    };
+
+    skip(&mut reader, HASH_SIZE)?;
+    let powers_of_g = (0..=max_degree)
+        .map(|_| read_point(&mut reader, encoding))
+        .collect::<Result<Vec<_>, _>>()?;
+    let skipped = (num_g1_powers - max_degree - 1)
+        .checked_mul(point_size::<E::G1Affine>(encoding))
+        .ok_or(SerializationError::InvalidData)?;
+    skip(&mut reader, skipped)?;
+    let h = read_point(&mut reader, encoding)?;
+    let beta_h = read_point(&mut reader, encoding)?;
+
+    let params = universal_params(powers_of_g, h, beta_h);
+    verify_powers(&params, rng)?;
+    Ok(params)
+}
+
+/// Reads the transcripts of the Aztec Ignition ceremony, in order, keeping
+/// the powers for polynomials of degree at most `max_degree`, and checks that
+/// they are well-formed with [`verify_powers`].
+///
+/// Every transcript starts with a header of seven big-endian `u32`, followed
+/// by its G1 and G2 points. Transcripts leave out the generators, starting
+/// from `tau`, and only the first one holds `tau` in G2.
+pub fn read_aztec_ignition<E, I, R, RNG>(
+    transcripts: I,
+    max_degree: usize,
+    rng: &mut RNG,
+) -> Result<UniversalParams<E>, Error>
+where
+    E: PairingEngine,
+    I: IntoIterator<Item = R>,
+    R: Read,
+    RNG: CryptoRng + RngCore,
+{
+    if max_degree == 0 {
+        return Err(Error::DegreeIsZero);
+    }
+    let encoding = Encoding::Aztec;
+    let mut powers_of_g = vec![E::G1Affine::prime_subgroup_generator()];
+    let mut beta_h = None;
+    for mut transcript in transcripts {
+        if powers_of_g.len() > max_degree && beta_h.is_some() {
+            break;
+        }
+
+        let mut header = [0u32; 7];
+        for field in header.iter_mut() {
+            let mut bytes = [0u8; 4];
+            read_exact(&mut transcript, &mut bytes)?;
+            *field = u32::from_be_bytes(bytes);
+        }
+        let [_, _, _, _, num_g1_points, num_g2_points, start_from] = header;
+        if start_from as usize != powers_of_g.len() - 1 {
+            return Err(SerializationError::InvalidData.into());
+        }
+
+        let num_g1_points = num_g1_points as usize;
+        let wanted = num_g1_points.min(max_degree - (powers_of_g.len() - 1));
+        for _ in 0..wanted {
+            powers_of_g.push(read_point(&mut transcript, encoding)?);
+        }
+        let skipped = num_g1_points
+            .checked_sub(wanted)
+            .and_then(|num_points| {
+                num_points.checked_mul(point_size::<E::G1Affine>(encoding))
+            })
+            .ok_or(SerializationError::InvalidData)?;
+        skip(&mut transcript, skipped)?;
+        if num_g2_points > 0 && beta_h.is_none() {
+            beta_h = Some(read_point(&mut transcript, encoding)?);
+        }
+    }
+
+    if powers_of_g.len() <= max_degree {
+        return Err(Error::TruncatedDegreeTooLarge);
+    }
+    let beta_h = beta_h.ok_or(SerializationError::InvalidData)?;
+    let params = universal_params(
+        powers_of_g,
+        E::G2Affine::prime_subgroup_generator(),
+        beta_h,
+    );
+    verify_powers(&params, rng)?;
+    Ok(params)
+}
+
+/// Size of a point of `G` in the given `encoding`.
+fn point_size<G>(encoding: Encoding) -> usize
+where
+    G: AffineCurve,
+{
+    let coordinate_size = G::BaseField::zero().serialized_size();
+    match encoding {
+        Encoding::Zcash {
+            compressed: true, ..
+        } => coordinate_size,
+        _ => 2 * coordinate_size,
+    }
+}
+
+/// Reads a point of `G` in the given `encoding`, checking that it is on the
+/// curve and in the prime order subgroup.
+fn read_point<G, R>(reader: &mut R, encoding: Encoding) -> Result<G, Error>
+where
+    G: AffineCurve,
+    R: Read,
+{
+    let coordinate_size = G::BaseField::zero().serialized_size();
+    let mut bytes = vec![0u8; point_size::<G>(encoding)];
+    read_exact(reader, &mut bytes)?;
+    match encoding {
+        Encoding::Zcash { compressed, flags } => {
+            let bits = bytes[0] & flags.mask();
+            bytes[0] &= !flags.mask();
+            let greatest_y = bits & flags.greatest_y() != 0;
+            let compression_matches = flags
+                .compression()
+                .is_none_or(|bit| (bits & bit != 0) == compressed);
+            if !compression_matches || (!compressed && greatest_y) {
+                return Err(Error::PointMalformed);
+            }
+            if bits & flags.infinity() != 0 {
+                return if !greatest_y && bytes.iter().all(Zero::is_zero) {
+                    Ok(G::zero())
+                } else {
+                    Err(Error::PointMalformed)
+                };
+            }
+
+            // Reversing the bytes gives the little-endian encoding, with
+            // extension field coefficients starting from the lowest.
+            bytes.reverse();
+            if compressed {
+                point_from_x(decode(&bytes)?, greatest_y)
+            } else {
+                let (y, x) = bytes.split_at(coordinate_size);
+                point_from_coordinates(decode(x)?, decode(y)?)
+            }
+        }
+        Encoding::Aztec => {
+            for limb in bytes.chunks_mut(8) {
+                limb.reverse();
+            }
+            let (x, y) = bytes.split_at(coordinate_size);
+            point_from_coordinates(decode(x)?, decode(y)?)
+        }
+    }
+}
+
+/// Decodes a canonical little-endian field element.
+fn decode<F>(bytes: &[u8]) -> Result<F, Error>
+where
+    F: CanonicalDeserialize,
+{
+    F::deserialize(bytes).map_err(|_| Error::PointMalformed)
+}
+
+/// Returns the point with abscissa `x` and the greatest or least of its two
+/// possible ordinates.
+fn point_from_x<G>(x: G::BaseField, greatest_y: bool) -> Result<G, Error>
+where
+    G: AffineCurve,
+{
+    let mut bytes = Vec::new();
+    x.serialize_with_flags(&mut bytes, SWFlags::from_y_sign(greatest_y))?;
+    G::deserialize(&bytes[..]).map_err(|_| Error::PointMalformed)
+}
+
+/// Returns the point `(x, y)`.
+fn point_from_coordinates<G>(
+    x: G::BaseField,
+    y: G::BaseField,
+) -> Result<G, Error>
+where
+    G: AffineCurve,
+{
+    // Recovering the point from `x` checks that it is on the curve.
+    let point = point_from_x::<G>(x, y > -y)?;
+    let mut bytes = Vec::new();
+    x.serialize(&mut bytes)?;
+    y.serialize_with_flags(&mut bytes, SWFlags::from_y_sign(y > -y))?;
+    if G::deserialize_unchecked(&bytes[..])? != point {
+        return Err(Error::PointMalformed);
+    }
+    Ok(point)
+}
+
+/// Fills `bytes` from `reader`.
+fn read_exact<R>(reader: &mut R, bytes: &mut [u8]) -> Result<(), Error>
+where
+    R: Read,
+{
+    reader.read_exact(bytes).map_err(|error| {
+        if error.kind() == ark_std::io::ErrorKind::UnexpectedEof {
+            Error::NotEnoughBytes
+        } else {
+            SerializationError::from(error).into()
+        }
+    })
+}
+
+/// Skips `len` bytes of `reader`.
+fn skip<R>(reader: &mut R, len: usize) -> Result<(), Error>
+where
+    R: Read,
+{
+    let mut buffer = [0u8; 4096];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(buffer.len());
+        read_exact(reader, &mut buffer[..chunk])?;
+        remaining -= chunk;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use rand_core::OsRng;
+
+    // The fixtures open the first `challenge` a ceremony starts from, where
+    // every power is the generator, and its compressed `response`.
+    const BN254_CHALLENGE: &[u8] =
+        include_bytes!("../../tests/fixtures/ceremonies/bn254_challenge");
+    const BN254_RESPONSE: &[u8] =
+        include_bytes!("../../tests/fixtures/ceremonies/bn254_response");
+    const BLS12_381_CHALLENGE: &[u8] =
+        include_bytes!("../../tests/fixtures/ceremonies/bls12_381_challenge");
+    const BLS12_381_RESPONSE: &[u8] =
+        include_bytes!("../../tests/fixtures/ceremonies/bls12_381_response");
+    const IGNITION: [&[u8]; 2] = [
+        include_bytes!("../../tests/fixtures/ceremonies/transcript00.dat"),
+        include_bytes!("../../tests/fixtures/ceremonies/transcript01.dat"),
+    ];
+
+    /// Checks that `params` hold `max_degree + 1` powers of `tau = 1`.
+    fn assert_generators<E>(params: &UniversalParams<E>, max_degree: usize)
+    where
+        E: PairingEngine,
+    {
+        let g = E::G1Affine::prime_subgroup_generator();
+        assert_eq!(params.powers_of_g, vec![g; max_degree + 1]);
+        assert_eq!(params.h, E::G2Affine::prime_subgroup_generator());
+        assert_eq!(params.beta_h, params.h);
+    }
+
+    /// Reads the powers of tau `file` with `layout`, for all the degrees it
+    /// holds, and checks the failures past its end.
+    fn check_powers_of_tau<E>(file: &[u8], layout: PowersOfTau)
+    where
+        E: PairingEngine,
+    {
+        let num_g1_powers = (1 << (layout.power + 1)) - 1;
+        for max_degree in 1..num_g1_powers {
+            let params = read_powers_of_tau::<E, _, _>(
+                file, layout, max_degree, &mut OsRng,
+            )
+            .unwrap();
+            assert_generators(&params, max_degree);
+        }
+        assert!(matches!(
+            read_powers_of_tau::<E, _, _>(
+                file,
+                layout,
+                num_g1_powers,
+                &mut OsRng
+            ),
+            Err(Error::TruncatedDegreeTooLarge)
+        ));
+
+        // Files cut before `beta_h` are refused
+        assert!(matches!(
+            read_powers_of_tau::<E, _, _>(
+                &file[..file.len() - 1],
+                layout,
+                1,
+                &mut OsRng
+            ),
+            Err(Error::NotEnoughBytes)
+        ));
+    }
+
+    #[test]
+    fn test_read_zcash_powers_of_tau() {
+        for (file, compressed) in
+            [(BLS12_381_CHALLENGE, false), (BLS12_381_RESPONSE, true)]
+        {
+            let layout = PowersOfTau {
+                power: 1,
+                compressed,
+                ..PowersOfTau::ZCASH_SAPLING
+            };
+            check_powers_of_tau::<Bls12_381>(file, layout);
+        }
+
+        // Compressed points must carry the compression flag
+        let layout = PowersOfTau {
+            power: 1,
+            compressed: true,
+            ..PowersOfTau::ZCASH_SAPLING
+        };
+        let mut file = BLS12_381_RESPONSE.to_vec();
+        file[HASH_SIZE] &= 0x7f;
+        assert!(matches!(
+            read_powers_of_tau::<Bls12_381, _, _>(
+                &file[..],
+                layout,
+                1,
+                &mut OsRng
+            ),
+            Err(Error::PointMalformed)
+        ));
+    }
+
+    #[test]
+    fn test_read_perpetual_powers_of_tau() {
+        let layout = PowersOfTau::perpetual(2);
+        check_powers_of_tau::<Bn254>(BN254_CHALLENGE, layout);
+        let compressed = PowersOfTau {
+            compressed: true,
+            ..layout
+        };
+        check_powers_of_tau::<Bn254>(BN254_RESPONSE, compressed);
+
+        // The top bit holds the sign of `y` on compressed BN254 points, not
+        // the compression, so setting it on the second power gives `-g`
+        let mut file = BN254_RESPONSE.to_vec();
+        file[HASH_SIZE + 32] |= 0x80;
+        let g = <Bn254 as PairingEngine>::G1Affine::prime_subgroup_generator();
+        let mut reader = &file[HASH_SIZE + 32..];
+        let point: <Bn254 as PairingEngine>::G1Affine = read_point(
+            &mut reader,
+            Encoding::Zcash {
+                compressed: true,
+                flags: PointFlags::Bn254,
+            },
+        )
+        .unwrap();
+        assert_eq!(point, -g);
+        assert!(matches!(
+            read_powers_of_tau::<Bn254, _, _>(
+                &file[..],
+                compressed,
+                4,
+                &mut OsRng
+            ),
+            Err(Error::PairingCheckFailure)
+        ));
+
+        // and uncompressed points must not set it
+        let mut file = BN254_CHALLENGE.to_vec();
+        file[HASH_SIZE] |= 0x80;
+        assert!(matches!(
+            read_powers_of_tau::<Bn254, _, _>(&file[..], layout, 4, &mut OsRng),
+            Err(Error::PointMalformed)
+        ));
+
+        // Points off the curve are refused
+        let mut file = BN254_CHALLENGE.to_vec();
+        file[HASH_SIZE + 2 * 64 - 1] ^= 1;
+        assert!(matches!(
+            read_powers_of_tau::<Bn254, _, _>(&file[..], layout, 4, &mut OsRng),
+            Err(Error::PointMalformed)
+        ));
+
+        // Layouts with more powers than can be addressed are refused
+        for power in [usize::BITS - 1, u32::MAX] {
+            assert!(matches!(
+                read_powers_of_tau::<Bn254, _, _>(
+                    &[][..],
+                    PowersOfTau::perpetual(power),
+                    4,
+                    &mut OsRng
+                ),
+                Err(Error::SerializationError {
+                    error: SerializationError::InvalidData
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_read_aztec_ignition() {
+        for max_degree in 1..=5 {
+            let params = read_aztec_ignition::<Bn254, _, _, _>(
+                IGNITION, max_degree, &mut OsRng,
+            )
+            .unwrap();
+            assert_generators(&params, max_degree);
+        }
+        assert!(matches!(
+            read_aztec_ignition::<Bn254, _, _, _>(IGNITION, 6, &mut OsRng),
+            Err(Error::TruncatedDegreeTooLarge)
+        ));
+
+        // Transcripts must be given in order
+        assert!(read_aztec_ignition::<Bn254, _, _, _>(
+            IGNITION.iter().rev().copied(),
+            4,
+            &mut OsRng,
+        )
+        .is_err());
+
+        // Points off the curve are refused
+        let mut transcript = IGNITION[0].to_vec();
+        transcript[7 * 4 + 2 * 64 - 1] ^= 1;
+        assert!(matches!(
+            read_aztec_ignition::<Bn254, _, _, _>(
+                [&transcript[..], IGNITION[1]],
+                4,
+                &mut OsRng,
+            ),
+            Err(Error::PointMalformed)
+        ));
+    }
+}
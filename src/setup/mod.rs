@@ -0,0 +1,233 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Universal setup parameters from powers-of-tau ceremonies.
+//!
+//! The parameters generated by [`KZG10::setup`](ark_poly_commit::kzg10::KZG10)
+//! are only suitable for testing, since whoever runs it knows the secret
+//! `tau`. This module imports the output of public ceremonies into
+//! [`UniversalParams`], checking with pairings that the imported points are
+//! powers of a single `tau`, and runs local ceremonies with [`Ceremony`].
+
+mod ceremony;
+mod import;
+mod parameters;
+
+pub use ceremony::{Ceremony, Contribution};
+pub use import::{
+    read_aztec_ignition, read_powers_of_tau, PointFlags, PowersOfTau,
+};
+pub use parameters::PublicParameters;
+
+use crate::error::Error;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::PairingEngine;
+use ark_ff::{PrimeField, UniformRand};
+use ark_poly_commit::kzg10::{Powers, UniversalParams};
+use num_traits::Zero;
+use rand_core::{CryptoRng, RngCore};
+
+/// Builds the [`UniversalParams`] with the G1 powers `powers_of_g` of `tau`,
+/// the G2 generator `h` and `beta_h = tau * h`.
+///
+/// Ceremonies provide no hiding powers, so `powers_of_gamma_g` only holds
+/// the identity: the parameters do not support hiding commitments.
+pub(crate) fn universal_params<E>(
+    powers_of_g: Vec<E::G1Affine>,
+    h: E::G2Affine,
+    beta_h: E::G2Affine,
+) -> UniversalParams<E>
+where
+    E: PairingEngine,
+{
+    let powers_of_gamma_g = (0..2).map(|i| (i, E::G1Affine::zero())).collect();
+    UniversalParams {
+        powers_of_g,
+        powers_of_gamma_g,
+        h,
+        beta_h,
+        neg_powers_of_h: BTreeMap::new(),
+        prepared_h: h.into(),
+        prepared_beta_h: beta_h.into(),
+    }
+}
+
+/// Checks that the G1 powers of `params` are successive powers of the `tau`
+/// in `beta_h = tau * h`.
+///
+/// All powers are checked at once with a random linear combination, using
+/// two pairings.
+pub fn verify_powers<E, R>(
+    params: &UniversalParams<E>,
+    rng: &mut R,
+) -> Result<(), Error>
+where
+    E: PairingEngine,
+    R: CryptoRng + RngCore,
+{
+    let powers_of_g = &params.powers_of_g;
+    if powers_of_g.len() < 2 {
+        return Err(Error::DegreeIsZero);
+    }
+    if powers_of_g[0].is_zero() || params.h.is_zero() || params.beta_h.is_zero()
+    {
+        return Err(Error::PairingCheckFailure);
+    }
+
+    // Check that sum(r_i * g_(i + 1)) = tau * sum(r_i * g_i)
+    let scalars = (1..powers_of_g.len())
+        .map(|_| E::Fr::rand(rng).into_repr())
+        .collect::<Vec<_>>();
+    let shifted =
+        VariableBaseMSM::multi_scalar_mul(&powers_of_g[1..], &scalars);
+    let unshifted = VariableBaseMSM::multi_scalar_mul(
+        &powers_of_g[..powers_of_g.len() - 1],
+        &scalars,
+    );
+    if E::pairing(shifted, params.h) != E::pairing(unshifted, params.beta_h) {
+        return Err(Error::PairingCheckFailure);
+    }
+    Ok(())
+}
+
+/// Returns the committer [`Powers`] of `params` for polynomials of degree at
+/// most `degree`.
+pub fn powers<E>(
+    params: &UniversalParams<E>,
+    degree: usize,
+) -> Result<Powers<'_, E>, Error>
+where
+    E: PairingEngine,
+{
+    if degree == 0 {
+        return Err(Error::TruncatedDegreeIsZero);
+    }
+    let powers_of_g = params
+        .powers_of_g
+        .get(..=degree)
+        .ok_or(Error::TruncatedDegreeTooLarge)?;
+    let powers_of_gamma_g = params
+        .powers_of_gamma_g
+        .values()
+        .copied()
+        .collect::<Vec<_>>();
+    Ok(Powers {
+        powers_of_g: powers_of_g.into(),
+        powers_of_gamma_g: powers_of_gamma_g.into(),
+    })
+}
+
+/// Returns `params` truncated to polynomials of degree at most `degree`.
+pub fn truncate<E>(
+    params: &UniversalParams<E>,
+    degree: usize,
+) -> Result<UniversalParams<E>, Error>
+where
+    E: PairingEngine,
+{
+    let powers = powers(params, degree)?;
+    Ok(UniversalParams {
+        powers_of_g: powers.powers_of_g.into_owned(),
+        powers_of_gamma_g: params.powers_of_gamma_g.clone(),
+        h: params.h,
+        beta_h: params.beta_h,
+        neg_powers_of_h: params.neg_powers_of_h.clone(),
+        prepared_h: params.prepared_h.clone(),
+        prepared_beta_h: params.prepared_beta_h.clone(),
+    })
+}
+
+/// Computes `[g, tau * g, ..., tau^degree * g]`.
+#[cfg(test)]
+pub(crate) fn powers_of_tau<G>(
+    g: G,
+    tau: G::ScalarField,
+    degree: usize,
+) -> Vec<G>
+where
+    G: ark_ec::AffineCurve,
+{
+    use ark_ec::ProjectiveCurve;
+
+    let powers = crate::util::powers_of(tau)
+        .take(degree + 1)
+        .map(|power| g.mul(power))
+        .collect::<Vec<_>>();
+    G::Projective::batch_normalization_into_affine(&powers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::{AffineCurve, TEModelParameters};
+    use rand_core::OsRng;
+
+    fn test_truncate<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let tau = E::Fr::rand(&mut OsRng);
+        let h = E::G2Affine::prime_subgroup_generator();
+        let params = universal_params::<E>(
+            powers_of_tau(E::G1Affine::prime_subgroup_generator(), tau, 8),
+            h,
+            h.mul(tau).into(),
+        );
+        verify_powers(&params, &mut OsRng).unwrap();
+
+        let committer = powers(&params, 4).unwrap();
+        assert_eq!(committer.powers_of_g[..], params.powers_of_g[..=4]);
+        let truncated = truncate(&params, 4).unwrap();
+        assert_eq!(truncated.powers_of_g, params.powers_of_g[..=4]);
+        verify_powers(&truncated, &mut OsRng).unwrap();
+        assert!(truncate(&params, 8).is_ok());
+        assert!(matches!(
+            truncate(&params, 9),
+            Err(Error::TruncatedDegreeTooLarge)
+        ));
+        assert!(matches!(
+            powers(&params, 0),
+            Err(Error::TruncatedDegreeIsZero)
+        ));
+
+        // Powers of another tau are refused
+        let other = universal_params::<E>(
+            params.powers_of_g.clone(),
+            h,
+            h.mul(tau + E::Fr::from(1u64)).into(),
+        );
+        assert!(matches!(
+            verify_powers(&other, &mut OsRng),
+            Err(Error::PairingCheckFailure)
+        ));
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [test_truncate],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [test_truncate],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
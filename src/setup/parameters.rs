@@ -0,0 +1,268 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Public parameters trimmed to the size of each circuit.
+
+use super::powers;
+use crate::error::Error;
+use ark_ec::PairingEngine;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::kzg10::{self, Powers, UniversalParams, KZG10};
+use ark_serialize::*;
+use num_traits::Zero;
+use rand_core::{CryptoRng, RngCore};
+
+/// Universal KZG parameters, from which the commit and verifier keys of
+/// circuits of any size up to a maximum are trimmed.
+#[derive(CanonicalDeserialize, CanonicalSerialize, derivative::Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct PublicParameters<E>
+where
+    E: PairingEngine,
+{
+    /// Universal parameters
+    params: UniversalParams<E>,
+}
+
+impl<E> PublicParameters<E>
+where
+    E: PairingEngine,
+{
+    /// Generates parameters for polynomials of degree at most `max_degree`
+    /// with a secret drawn from `rng`.
+    ///
+    /// Whoever runs this knows the secret and can forge proofs, so it is only
+    /// suitable for testing. Use the output of a ceremony otherwise, see
+    /// [`read_powers_of_tau`](super::read_powers_of_tau) and
+    /// [`Ceremony`](super::Ceremony).
+    pub fn setup<R>(max_degree: usize, rng: &mut R) -> Result<Self, Error>
+    where
+        R: CryptoRng + RngCore,
+    {
+        if max_degree == 0 {
+            return Err(Error::DegreeIsZero);
+        }
+        Ok(
+            KZG10::<E, DensePolynomial<E::Fr>>::setup(max_degree, false, rng)?
+                .into(),
+        )
+    }
+
+    /// Returns the universal parameters.
+    pub fn params(&self) -> &UniversalParams<E> {
+        &self.params
+    }
+
+    /// Returns the universal parameters, consuming `self`.
+    pub fn into_params(self) -> UniversalParams<E> {
+        self.params
+    }
+
+    /// Returns the maximum degree of the polynomials these parameters can
+    /// commit to.
+    pub fn max_degree(&self) -> usize {
+        self.params.powers_of_g.len().saturating_sub(1)
+    }
+
+    /// Returns the degree of the commit key of circuits of size
+    /// `circuit_size`.
+    ///
+    /// Circuits are padded to a power of two `n`. The polynomials committed
+    /// to are the selectors, permutations, wires and the permutation
+    /// accumulator, and the four parts of the quotient, all with at most `n`
+    /// coefficients since the polynomials are not blinded. Opening witnesses
+    /// are of lower degree.
+    pub fn degree(circuit_size: usize) -> usize {
+        circuit_size.next_power_of_two() - 1
+    }
+
+    /// Returns the commit key of circuits of size `circuit_size`.
+    ///
+    /// Fails with [`Error::TruncatedDegreeTooLarge`] if the parameters are too
+    /// small for the circuit.
+    pub fn commit_key(
+        &self,
+        circuit_size: usize,
+    ) -> Result<Powers<'_, E>, Error> {
+        powers(&self.params, Self::degree(circuit_size).max(1))
+    }
+
+    /// Returns the verifier key, which is the same for all circuit sizes.
+    pub fn verifier_key(&self) -> Result<kzg10::VerifierKey<E>, Error> {
+        let g = *self
+            .params
+            .powers_of_g
+            .first()
+            .ok_or(Error::TruncatedDegreeTooLarge)?;
+        Ok(kzg10::VerifierKey {
+            g,
+            gamma_g: self
+                .params
+                .powers_of_gamma_g
+                .get(&0)
+                .copied()
+                .unwrap_or_else(E::G1Affine::zero),
+            h: self.params.h,
+            beta_h: self.params.beta_h,
+            prepared_h: self.params.prepared_h.clone(),
+            prepared_beta_h: self.params.prepared_beta_h.clone(),
+        })
+    }
+
+    /// Returns the commit and verifier keys of circuits of size
+    /// `circuit_size`.
+    pub fn trim(
+        &self,
+        circuit_size: usize,
+    ) -> Result<(Powers<'_, E>, kzg10::VerifierKey<E>), Error> {
+        Ok((self.commit_key(circuit_size)?, self.verifier_key()?))
+    }
+}
+
+impl<E> From<UniversalParams<E>> for PublicParameters<E>
+where
+    E: PairingEngine,
+{
+    fn from(params: UniversalParams<E>) -> Self {
+        Self { params }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::batch_test;
+    use crate::circuit::{verify_proof, Circuit};
+    use crate::constraint_system::StandardComposer;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::TEModelParameters;
+    use core::marker::PhantomData;
+    use rand_core::OsRng;
+
+    /// Circuit of `1 << LOG_SIZE` gates.
+    #[derive(derivative::Derivative)]
+    #[derivative(Default(bound = ""))]
+    struct SizedCircuit<E, P, const LOG_SIZE: usize>(PhantomData<(E, P)>);
+
+    impl<E, P, const LOG_SIZE: usize> Circuit<E, P> for SizedCircuit<E, P, LOG_SIZE>
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        const CIRCUIT_ID: [u8; 32] = [0x0d; 32];
+
+        fn gadget(
+            &mut self,
+            composer: &mut StandardComposer<E, P>,
+        ) -> Result<(), Error> {
+            let one = composer.add_input(E::Fr::from(1u64));
+            // The composer adds dummy constraints, filling the circuit
+            for _ in 0..(1 << LOG_SIZE) - 4 {
+                composer.assert_equal(one, one);
+            }
+            Ok(())
+        }
+
+        fn padded_circuit_size(&self) -> usize {
+            1 << LOG_SIZE
+        }
+    }
+
+    fn test_trim<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let pp = PublicParameters::<E>::setup(15, &mut OsRng).unwrap();
+        assert_eq!(pp.max_degree(), 15);
+        assert_eq!(pp.commit_key(16).unwrap().powers_of_g.len(), 16);
+        assert_eq!(pp.commit_key(9).unwrap().powers_of_g.len(), 16);
+        assert_eq!(pp.commit_key(8).unwrap().powers_of_g.len(), 8);
+        assert!(matches!(
+            pp.commit_key(17),
+            Err(Error::TruncatedDegreeTooLarge)
+        ));
+        assert!(matches!(
+            PublicParameters::<E>::setup(0, &mut OsRng),
+            Err(Error::DegreeIsZero)
+        ));
+
+        // Serialized parameters trim to the same keys
+        let mut bytes = Vec::new();
+        pp.serialize(&mut bytes).unwrap();
+        let read = PublicParameters::<E>::deserialize(&bytes[..]).unwrap();
+        assert_eq!(
+            read.commit_key(16).unwrap().powers_of_g,
+            pp.commit_key(16).unwrap().powers_of_g
+        );
+    }
+
+    fn test_boundary_sizes<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        // Parameters of exactly the required degree prove and verify
+        let pp = PublicParameters::<E>::setup(
+            PublicParameters::<E>::degree(1 << 4),
+            &mut OsRng,
+        )
+        .unwrap();
+        let mut circuit = SizedCircuit::<E, P, 4>::default();
+        let (pk, vd) = circuit.compile(&pp).unwrap();
+        let proof = circuit.gen_proof(&pp, pk.clone(), b"Test").unwrap();
+        verify_proof(&pp, vd.key.clone(), &proof, &[], &vd.pi_pos, b"Test")
+            .unwrap();
+
+        // Parameters one degree short are refused instead of panicking
+        let short = PublicParameters::<E>::setup(
+            PublicParameters::<E>::degree(1 << 4) - 1,
+            &mut OsRng,
+        )
+        .unwrap();
+        assert!(matches!(
+            circuit.compile(&short),
+            Err(Error::TruncatedDegreeTooLarge)
+        ));
+        assert!(matches!(
+            circuit.gen_proof(&short, pk, b"Test"),
+            Err(Error::TruncatedDegreeTooLarge)
+        ));
+
+        // A circuit larger than it claims does not fit its commit key
+        let mut circuit = SizedCircuit::<E, P, 5>::default();
+        let pp = PublicParameters::<E>::setup(1 << 5, &mut OsRng).unwrap();
+        let (pk, _) = circuit.compile(&pp).unwrap();
+        let mut prover = crate::proof_system::Prover::<E, P>::new(b"Test");
+        circuit.gadget(prover.mut_cs()).unwrap();
+        prover.prover_key = Some(pk);
+        assert!(matches!(
+            prover.prove(&pp.commit_key(1 << 4).unwrap()),
+            Err(Error::PolynomialDegreeTooLarge)
+        ));
+    }
+
+    // Bls12-381 tests
+    batch_test!(
+        [test_trim, test_boundary_sizes],
+        [] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Bls12-377 tests
+    batch_test!(
+        [test_trim, test_boundary_sizes],
+        [] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
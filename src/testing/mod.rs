@@ -0,0 +1,286 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! End-to-end test harness for gadgets, enabled by the `testing` feature.
+//!
+//! ```ignore
+//! use ark_plonk::{batch_test_curves, testing::*};
+//!
+//! fn test_mul<E, P>()
+//! where
+//!     E: PairingEngine,
+//!     P: TEModelParameters<BaseField = E::Fr>,
+//! {
+//!     gadget_tester(|composer: &mut StandardComposer<E, P>| { ... }, 32)
+//!         .unwrap();
+//! }
+//!
+//! batch_test_curves!([test_mul], []);
+//! ```
+
+#[cfg(not(target_arch = "wasm32"))]
+mod soundness;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use soundness::*;
+
+use crate::constraint_system::StandardComposer;
+use crate::error::Error;
+use crate::proof_system::{ProofMode, Prover, Verifier};
+use crate::setup::PublicParameters;
+use ark_ec::{PairingEngine, TEModelParameters};
+use core::any::{Any, TypeId};
+use num_traits::{One, Zero};
+use rand_core::OsRng;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+#[doc(hidden)]
+pub use {
+    ark_bls12_377, ark_bls12_381, ark_ed_on_bls12_377, ark_ed_on_bls12_381,
+    paste,
+};
+
+/// Returns public parameters supporting polynomials of degree at least
+/// `max_degree`.
+///
+/// Parameters are generated once per pairing engine and shared by every
+/// later call asking for at most the same degree.
+pub fn public_parameters<E>(
+    max_degree: usize,
+) -> Result<Arc<PublicParameters<E>>, Error>
+where
+    E: PairingEngine,
+{
+    type Cache = BTreeMap<TypeId, Box<dyn Any + Send>>;
+    static CACHE: Mutex<Cache> = Mutex::new(BTreeMap::new());
+
+    // A test panicking while holding the lock leaves the cache intact
+    let mut cache = CACHE.lock().unwrap_or_else(|err| err.into_inner());
+    let cached = cache
+        .get(&TypeId::of::<E>())
+        .and_then(|pp| pp.downcast_ref::<Arc<PublicParameters<E>>>());
+    if let Some(pp) = cached.filter(|pp| pp.max_degree() >= max_degree) {
+        return Ok(pp.clone());
+    }
+    let pp = Arc::new(PublicParameters::setup(
+        max_degree.next_power_of_two(),
+        &mut OsRng,
+    )?);
+    cache.insert(TypeId::of::<E>(), Box::new(pp.clone()));
+    Ok(pp)
+}
+
+/// Adds dummy constraints using arithmetic gates.
+pub fn dummy_gadget<E, P>(n: usize, composer: &mut StandardComposer<E, P>)
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    let one = E::Fr::one();
+    let var_one = composer.add_input(one);
+    for _ in 0..n {
+        composer.big_add(
+            (E::Fr::one(), var_one),
+            (E::Fr::one(), var_one),
+            None,
+            E::Fr::zero(),
+            None,
+        );
+    }
+}
+
+/// Takes a generic gadget function with no auxillary input and tests whether it
+/// passes an end-to-end test in every [`ProofMode`].
+pub fn gadget_tester<E, P>(
+    gadget: impl Fn(&mut StandardComposer<E, P>),
+    n: usize,
+) -> Result<(), Error>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    prove_and_verify(&gadget, &gadget, n)
+}
+
+/// Runs the end-to-end test of [`gadget_tester`] with different gadgets for
+/// the prover and the verifier, so that the verifier can build the circuit
+/// from dummy witnesses.
+pub fn prove_and_verify<E, P>(
+    mut prover_gadget: impl FnMut(&mut StandardComposer<E, P>),
+    mut verifier_gadget: impl FnMut(&mut StandardComposer<E, P>),
+    n: usize,
+) -> Result<(), Error>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    // Common View
+    let pp = public_parameters(2 * n)?;
+    for mode in [ProofMode::Standard, ProofMode::Compact] {
+        prove_and_verify_with_mode(
+            &mut prover_gadget,
+            &mut verifier_gadget,
+            &pp,
+            mode,
+        )?;
+    }
+    Ok(())
+}
+
+/// Asserts that the witness assigned by `gadget` does not satisfy the
+/// circuit, that is, that the proof is rejected by the verifier.
+///
+/// # Panics
+///
+/// Panics if the proof is accepted or if the test fails for another reason,
+/// such as a circuit larger than `n`.
+pub fn assert_unsatisfiable<E, P>(
+    gadget: impl Fn(&mut StandardComposer<E, P>),
+    n: usize,
+) where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    match gadget_tester(gadget, n) {
+        Err(Error::ProofVerificationError) => {}
+        Ok(()) => panic!("the gadget is satisfied by the witness"),
+        Err(err) => panic!("the gadget test failed: {}", err),
+    }
+}
+
+/// Runs the end-to-end test of [`prove_and_verify`] for a single
+/// [`ProofMode`].
+fn prove_and_verify_with_mode<E, P>(
+    prover_gadget: &mut impl FnMut(&mut StandardComposer<E, P>),
+    verifier_gadget: &mut impl FnMut(&mut StandardComposer<E, P>),
+    pp: &PublicParameters<E>,
+    mode: ProofMode,
+) -> Result<(), Error>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    // Provers View
+    let (proof, public_inputs) = {
+        // Create a prover struct
+        let mut prover = Prover::new(b"demo");
+
+        // Additionally key the transcript
+        prover.key_transcript(b"key", b"additional seed information");
+
+        // Add gadgets
+        prover_gadget(prover.mut_cs());
+
+        // Commit Key
+        let powers = pp.commit_key(prover.circuit_size())?;
+
+        // Preprocess circuit
+        prover.preprocess(&powers)?;
+        if let Some(prover_key) = prover.prover_key.as_mut() {
+            prover_key.set_mode(mode);
+        }
+
+        // Once the prove method is called, the public inputs are cleared
+        // So pre-fetch these before calling Prove
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+
+        // Compute Proof
+        (prover.prove(&powers)?, public_inputs)
+    };
+    // Verifiers view
+    //
+    // Create a Verifier object
+    let mut verifier = Verifier::new(b"demo");
+
+    // Additionally key the transcript
+    verifier.key_transcript(b"key", b"additional seed information");
+
+    // Add gadgets
+    verifier_gadget(verifier.mut_cs());
+
+    // Compute Commit and Verifier Key
+    let (powers, vk) = pp.trim(verifier.circuit_size())?;
+
+    // Preprocess circuit
+    verifier.preprocess(&powers)?;
+    if let Some(verifier_key) = verifier.verifier_key.as_mut() {
+        verifier_key.set_mode(mode);
+    }
+
+    // Verify proof
+    verifier.verify(&proof, &vk, &public_inputs)
+}
+
+/// Defines a set of tests on a pairing engine / curve combination.
+///
+/// The set of tests is split in two. The first set between `[]` is for regular
+/// tests that should not panic. The second set is for tests that should panic.
+#[macro_export]
+macro_rules! batch_test {
+    ( [$($test_set:ident),*], [$($test_panic_set:ident),*] => ($engine:ty, $params:ty) ) => {
+        $crate::testing::paste::item! {
+            $(
+                #[test]
+                #[allow(non_snake_case)]
+                fn [< $test_set _on_ $engine>]() {
+                    $test_set::<$engine, $params>()
+                }
+            )*
+            $(
+                #[test]
+                #[should_panic]
+                #[allow(non_snake_case)]
+                fn [< $test_panic_set _on_ $engine>]() {
+                    $test_panic_set::<$engine, $params>()
+                }
+            )*
+        }
+    }
+}
+
+/// Defines a set of tests on both BLS12-381 and BLS12-377 with their embedded
+/// Edwards curves, split as in [`batch_test!`].
+#[macro_export]
+macro_rules! batch_test_curves {
+    ( [$($test_set:ident),*], [$($test_panic_set:ident),*] ) => {
+        $crate::batch_test_curves!(
+            @curve [$($test_set),*], [$($test_panic_set),*]
+                => (Bls12_381, ark_bls12_381, ark_ed_on_bls12_381)
+        );
+        $crate::batch_test_curves!(
+            @curve [$($test_set),*], [$($test_panic_set),*]
+                => (Bls12_377, ark_bls12_377, ark_ed_on_bls12_377)
+        );
+    };
+    ( @curve [$($test_set:ident),*], [$($test_panic_set:ident),*] => ($engine:ident, $engine_crate:ident, $params_crate:ident) ) => {
+        $crate::testing::paste::item! {
+            $(
+                #[test]
+                #[allow(non_snake_case)]
+                fn [< $test_set _on_ $engine>]() {
+                    $test_set::<
+                        $crate::testing::$engine_crate::$engine,
+                        $crate::testing::$params_crate::EdwardsParameters,
+                    >()
+                }
+            )*
+            $(
+                #[test]
+                #[should_panic]
+                #[allow(non_snake_case)]
+                fn [< $test_panic_set _on_ $engine>]() {
+                    $test_panic_set::<
+                        $crate::testing::$engine_crate::$engine,
+                        $crate::testing::$params_crate::EdwardsParameters,
+                    >()
+                }
+            )*
+        }
+    };
+}
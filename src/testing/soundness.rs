@@ -0,0 +1,497 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Property-based soundness checks for gadgets.
+//!
+//! Witnesses are checked against the gate equations directly, without
+//! proving, so that many random inputs and mutated witnesses can be tried.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use crate::proof_system::{
+    ecc::{CurveAddition, CurveDoubling, FixedBaseScalarMul},
+    logic::Logic,
+    range::Range,
+    GateConstraint, GateValues,
+};
+use ark_ec::{PairingEngine, TEModelParameters};
+use ark_ff::PrimeField;
+use core::fmt::Debug;
+use num_traits::Zero;
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestRunner};
+use rand_core::OsRng;
+use std::collections::BTreeMap;
+
+/// Returns a strategy for field elements, biased towards `0`, `1`, `-1` and
+/// small values.
+pub fn field_element<F>() -> impl Strategy<Value = F>
+where
+    F: PrimeField,
+{
+    prop_oneof![
+        Just(F::zero()),
+        Just(F::one()),
+        Just(-F::one()),
+        any::<u64>().prop_map(F::from),
+        any::<[u8; 32]>().prop_map(|bytes| F::from_le_bytes_mod_order(&bytes)),
+    ]
+}
+
+/// Returns the index of the first gate of `composer` whose equation is not
+/// satisfied by its witness, if any.
+pub fn unsatisfied_gate<E, P>(
+    composer: &StandardComposer<E, P>,
+) -> Option<usize>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    let checker = GateChecker::new(composer);
+    (0..composer.n)
+        .find(|row| !checker.is_satisfied(*row, |var| checker.value(var)))
+}
+
+/// Returns the variables of `composer` whose value can be changed on its own
+/// without breaking any gate equation.
+///
+/// Variables used by no gate are always free. Every other variable is
+/// replaced in turn by a random different value and the gates using it, or
+/// using it as their next row, are checked.
+/// Variables that only become free together, such as two coordinates of a
+/// point, are not found.
+pub fn unconstrained_variables<E, P>(
+    composer: &StandardComposer<E, P>,
+) -> Vec<Variable>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    let checker = GateChecker::new(composer);
+
+    // Gates reading each variable, through their own row or the next one
+    let mut rows: BTreeMap<usize, (Variable, Vec<usize>)> = composer
+        .variables
+        .keys()
+        .map(|var| (var.0, (*var, Vec::new())))
+        .collect();
+    let wires = [&composer.w_l, &composer.w_r, &composer.w_o, &composer.w_4];
+    for row in 0..composer.n {
+        for var in wires.iter().map(|wire| wire[row]) {
+            let gates = &mut rows.entry(var.0).or_insert((var, Vec::new())).1;
+            gates.push(row);
+            gates.push(row.checked_sub(1).unwrap_or(composer.n - 1));
+        }
+    }
+
+    rows.into_values()
+        .filter(|(var, gates)| {
+            let mutated = checker.value(*var) + nonzero_element::<E::Fr>();
+            let value = |other| {
+                if other == *var {
+                    mutated
+                } else {
+                    checker.value(other)
+                }
+            };
+            gates.iter().all(|row| checker.is_satisfied(*row, value))
+        })
+        .map(|(var, _)| var)
+        .collect()
+}
+
+/// Checks `gadget` on `cases` random inputs drawn from `strategy`.
+///
+/// For every input, the variables returned by the gadget must take the
+/// values computed by `native`, the witness must satisfy every gate and no
+/// variable may be changed on its own, see [`unconstrained_variables`].
+///
+/// # Panics
+///
+/// Panics with the smallest failing input found.
+pub fn assert_sound<E, P, S>(
+    cases: u32,
+    strategy: S,
+    gadget: impl Fn(&mut StandardComposer<E, P>, &S::Value) -> Vec<Variable>,
+    native: impl Fn(&S::Value) -> Vec<E::Fr>,
+) where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+    S: Strategy,
+    S::Value: Debug,
+{
+    let mut runner = TestRunner::new(config(cases));
+    let result = runner.run(&strategy, |input| {
+        let mut composer = StandardComposer::new();
+        let outputs: Vec<_> = gadget(&mut composer, &input)
+            .iter()
+            .map(|var| composer.variables[var])
+            .collect();
+        prop_assert_eq!(outputs, native(&input));
+        prop_assert_eq!(unsatisfied_gate(&composer), None);
+        let free = unconstrained_variables(&composer);
+        prop_assert!(free.is_empty(), "unconstrained variables {:?}", free);
+        Ok(())
+    });
+    if let Err(err) = result {
+        panic!("{}", err);
+    }
+}
+
+/// Checks `gadget` on `cases` random witnesses drawn from `strategy`, which
+/// may be invalid.
+///
+/// The circuit must be satisfied exactly for the witnesses accepted by
+/// `is_valid`, so that witnesses the gadget should reject, such as a
+/// non-boolean bit, are found.
+///
+/// # Panics
+///
+/// Panics with the smallest failing witness found.
+pub fn assert_rejects<E, P, S>(
+    cases: u32,
+    strategy: S,
+    gadget: impl Fn(&mut StandardComposer<E, P>, &S::Value),
+    is_valid: impl Fn(&S::Value) -> bool,
+) where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+    S: Strategy,
+    S::Value: Debug,
+{
+    let mut runner = TestRunner::new(config(cases));
+    let result = runner.run(&strategy, |witness| {
+        let mut composer = StandardComposer::new();
+        gadget(&mut composer, &witness);
+        prop_assert_eq!(
+            unsatisfied_gate(&composer).is_none(),
+            is_valid(&witness),
+            "the circuit accepts exactly the valid witnesses"
+        );
+        Ok(())
+    });
+    if let Err(err) = result {
+        panic!("{}", err);
+    }
+}
+
+/// Returns the configuration running `cases` cases without writing failures
+/// to disk.
+fn config(cases: u32) -> Config {
+    Config {
+        failure_persistence: None,
+        ..Config::with_cases(cases)
+    }
+}
+
+/// Returns a random non-zero field element.
+fn nonzero_element<F>() -> F
+where
+    F: PrimeField,
+{
+    loop {
+        let element = F::rand(&mut OsRng);
+        if !element.is_zero() {
+            return element;
+        }
+    }
+}
+
+/// Evaluates the gate equations of a composer as the quotient polynomial
+/// does, with a random separation challenge.
+struct GateChecker<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    /// Composer
+    composer: &'a StandardComposer<E, P>,
+
+    /// Public Inputs of every gate
+    public_inputs: Vec<E::Fr>,
+
+    /// Separation Challenge
+    separation_challenge: E::Fr,
+}
+
+impl<'a, E, P> GateChecker<'a, E, P>
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    fn new(composer: &'a StandardComposer<E, P>) -> Self {
+        Self {
+            composer,
+            public_inputs: composer.construct_dense_pi_vec(),
+            separation_challenge: nonzero_element(),
+        }
+    }
+
+    fn value(&self, var: Variable) -> E::Fr {
+        self.composer.variables[&var]
+    }
+
+    /// Checks the gate at `row` with the variables taking the values given
+    /// by `value`.
+    fn is_satisfied(
+        &self,
+        row: usize,
+        value: impl Fn(Variable) -> E::Fr,
+    ) -> bool {
+        let cs = self.composer;
+
+        // The circuit is padded with zero rows up to a power of two, and the
+        // last row wraps around to the first one.
+        let next = |wire: &[Variable]| match row + 1 {
+            next if next < cs.n => value(wire[next]),
+            _ if cs.n.is_power_of_two() => value(wire[0]),
+            _ => E::Fr::zero(),
+        };
+        let values = GateValues {
+            left: value(cs.w_l[row]),
+            right: value(cs.w_r[row]),
+            output: value(cs.w_o[row]),
+            fourth: value(cs.w_4[row]),
+            left_next: next(&cs.w_l),
+            right_next: next(&cs.w_r),
+            fourth_next: next(&cs.w_4),
+            left_selector: cs.q_l[row],
+            right_selector: cs.q_r[row],
+            constant_selector: cs.q_c[row],
+        };
+
+        let arithmetic = (values.left * values.right * cs.q_m[row]
+            + values.left * cs.q_l[row]
+            + values.right * cs.q_r[row]
+            + values.output * cs.q_o[row]
+            + values.fourth * cs.q_4[row]
+            + cs.q_c[row])
+            * cs.q_arith[row];
+        let challenge = self.separation_challenge;
+        let custom = [
+            (cs.q_range[row], Range::constraints(challenge, values)),
+            (cs.q_logic[row], Logic::constraints(challenge, values)),
+            (
+                cs.q_fixed_group_add[row],
+                FixedBaseScalarMul::<_, P>::constraints(challenge, values),
+            ),
+            (
+                cs.q_variable_group_add[row],
+                CurveAddition::<_, P>::constraints(challenge, values),
+            ),
+            (
+                cs.q_variable_group_double[row],
+                CurveDoubling::<_, P>::constraints(challenge, values),
+            ),
+        ];
+
+        let quotient = custom
+            .iter()
+            .filter(|(selector, _)| !selector.is_zero())
+            .fold(
+                arithmetic + self.public_inputs[row],
+                |acc, (selector, term)| acc + *selector * term,
+            );
+        quotient.is_zero()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{batch_test, util};
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::Bls12_381;
+    use ark_ec::twisted_edwards_extended::GroupAffine;
+    use ark_ec::AffineCurve;
+    use num_traits::One;
+
+    fn generator<P>() -> GroupAffine<P>
+    where
+        P: TEModelParameters,
+    {
+        let (x, y) = P::AFFINE_GENERATOR_COEFFS;
+        GroupAffine::new(x, y)
+    }
+
+    fn test_unsatisfied_gate<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut composer = StandardComposer::<E, P>::new();
+        let bit = composer.add_input(E::Fr::one());
+        composer.boolean_gate(bit);
+        let point = composer.add_affine(generator());
+        composer.point_doubling_gate(point);
+        assert_eq!(unsatisfied_gate(&composer), None);
+        assert!(unconstrained_variables(&composer).is_empty());
+
+        let row = composer.n;
+        let bit = composer.add_input(E::Fr::from(2u64));
+        composer.boolean_gate(bit);
+        assert_eq!(unsatisfied_gate(&composer), Some(row));
+    }
+
+    fn test_unconstrained_variables<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        let mut composer = StandardComposer::<E, P>::new();
+        let a = composer.add_input(E::Fr::from(3u64));
+        let b = composer.add_input(E::Fr::from(5u64));
+        // `b` has a zero coefficient, so it is not constrained
+        let c = composer.add(
+            (E::Fr::one(), a),
+            (E::Fr::zero(), b),
+            E::Fr::zero(),
+            None,
+        );
+        composer.constrain_to_constant(c, E::Fr::from(3u64), None);
+        assert_eq!(unsatisfied_gate(&composer), None);
+        assert_eq!(unconstrained_variables(&composer), vec![b]);
+    }
+
+    fn test_boolean_gate_rejects_non_bits<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        assert_rejects(
+            64,
+            field_element::<E::Fr>(),
+            |composer: &mut StandardComposer<E, P>, value| {
+                let bit = composer.add_input(*value);
+                composer.boolean_gate(bit);
+            },
+            |value| value.is_zero() || value.is_one(),
+        );
+    }
+
+    fn test_missing_boolean_check<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        // The selector bit is never constrained to be boolean
+        assert_rejects(
+            64,
+            (field_element::<E::Fr>(), any::<u64>(), any::<u64>()),
+            |composer: &mut StandardComposer<E, P>, (bit, a, b)| {
+                let bit = composer.add_input(*bit);
+                let a = composer.add_input(E::Fr::from(*a));
+                let b = composer.add_input(E::Fr::from(*b));
+                composer.conditional_select(bit, a, b);
+            },
+            |(bit, _, _)| bit.is_zero() || bit.is_one(),
+        );
+    }
+
+    fn test_point_addition_sound<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        assert_sound(
+            16,
+            (any::<u64>(), any::<u64>()),
+            |composer: &mut StandardComposer<E, P>, (a, b)| {
+                let a = composer.add_affine(generator::<P>().mul(*a).into());
+                let b = composer.add_affine(generator::<P>().mul(*b).into());
+                let sum = composer.point_addition_gate(a, b);
+                vec![*sum.x(), *sum.y()]
+            },
+            |(a, b)| {
+                let sum: GroupAffine<P> = (generator::<P>().mul(*a)
+                    + generator::<P>().mul(*b))
+                .into();
+                vec![sum.x, sum.y]
+            },
+        );
+    }
+
+    fn test_unconstrained_point<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        // The sum is witnessed without being constrained
+        assert_sound(
+            4,
+            (any::<u64>(), any::<u64>()),
+            |composer: &mut StandardComposer<E, P>, (a, b)| {
+                let sum: GroupAffine<P> = (generator::<P>().mul(*a)
+                    + generator::<P>().mul(*b))
+                .into();
+                let sum = composer.add_affine(sum);
+                vec![*sum.x(), *sum.y()]
+            },
+            |(a, b)| {
+                let sum: GroupAffine<P> = (generator::<P>().mul(*a)
+                    + generator::<P>().mul(*b))
+                .into();
+                vec![sum.x, sum.y]
+            },
+        );
+    }
+
+    fn test_windowed_scalar_mul_sound<E, P>()
+    where
+        E: PairingEngine,
+        P: TEModelParameters<BaseField = E::Fr>,
+    {
+        assert_sound(
+            4,
+            any::<[u8; 31]>()
+                .prop_map(|bytes| E::Fr::from_le_bytes_mod_order(&bytes)),
+            |composer: &mut StandardComposer<E, P>, scalar| {
+                let scalar = composer.add_input(*scalar);
+                let point = composer.add_affine(generator());
+                let result =
+                    composer.windowed_variable_base_scalar_mul(scalar, point);
+                vec![*result.x(), *result.y()]
+            },
+            |scalar| {
+                let result: GroupAffine<P> = generator::<P>()
+                    .mul(util::to_embedded_curve_scalar::<E, P>(*scalar))
+                    .into();
+                vec![result.x, result.y]
+            },
+        );
+    }
+
+    // Tests for Bls12_381
+    batch_test!(
+        [
+            test_unsatisfied_gate,
+            test_unconstrained_variables,
+            test_boolean_gate_rejects_non_bits,
+            test_point_addition_sound,
+            test_windowed_scalar_mul_sound
+        ],
+        [test_missing_boolean_check, test_unconstrained_point] => (
+            Bls12_381,
+            ark_ed_on_bls12_381::EdwardsParameters
+        )
+    );
+
+    // Tests for Bls12_377
+    batch_test!(
+        [
+            test_unsatisfied_gate,
+            test_unconstrained_variables,
+            test_boolean_gate_rejects_non_bits,
+            test_point_addition_sound,
+            test_windowed_scalar_mul_sound
+        ],
+        [test_missing_boolean_check, test_unconstrained_point] => (
+            Bls12_377,
+            ark_ed_on_bls12_377::EdwardsParameters
+        )
+    );
+}
@@ -9,6 +9,7 @@
 //! This is an extension over the [Merlin Transcript](Transcript) which adds a
 //! few extra functionalities.
 
+use alloc::vec::Vec;
 use ark_ec::PairingEngine;
 use ark_ff::{Field, PrimeField};
 use ark_poly_commit::kzg10::Commitment;
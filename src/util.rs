@@ -6,8 +6,12 @@
 //
 // Copyright (c) ZK-INFRA. All rights reserved.
 
+use crate::error::Error;
+use alloc::vec::Vec;
 use ark_ec::{AffineCurve, ModelParameters, PairingEngine, TEModelParameters};
-use ark_ff::{BigInteger, FftField, Field, FpParameters, PrimeField};
+use ark_ff::{
+    BigInteger, FftField, FftParameters, Field, FpParameters, PrimeField,
+};
 use ark_poly::{
     univariate::DensePolynomial, EvaluationDomain, GeneralEvaluationDomain,
     Polynomial, UVPolynomial,
@@ -24,6 +28,40 @@ where
     core::iter::successors(Some(F::one()), move |p| Some(*p * scalar))
 }
 
+/// Returns the smallest evaluation domain of at least `size` elements, or
+/// [`Error::InvalidEvalDomainSize`] if the field has no domain this large.
+pub(crate) fn domain<F>(
+    size: usize,
+) -> Result<GeneralEvaluationDomain<F>, Error>
+where
+    F: FftField,
+{
+    GeneralEvaluationDomain::new(size).ok_or(Error::InvalidEvalDomainSize {
+        log_size_of_group: size.next_power_of_two().trailing_zeros(),
+        adacity: <F::FftParams as FftParameters>::TWO_ADICITY,
+    })
+}
+
+/// Runs `a` and `b`, in parallel when the `parallel` feature is enabled, and
+/// returns both results.
+#[inline]
+pub(crate) fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        rayon::join(a, b)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (a(), b())
+    }
+}
+
 /// Performs polynomial division by `(x - z)` with `x` indeterminant using
 /// Ruffini's algorithm.
 pub fn ruffini<F>(poly: DensePolynomial<F>, z: F) -> DensePolynomial<F>
@@ -0,0 +1,417 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! WebAssembly bindings over BLS12-381 and its embedded Jubjub curve.
+//!
+//! Parameters, keys and proofs cross the boundary as the bytes of their
+//! canonical serialization, and scalars as big-endian hex strings such as
+//! `"0x2a"`. Circuits are registered at compile time with
+//! [`register_wasm_circuits`](crate::register_wasm_circuits), which exports
+//! `prove` and `verify` functions dispatching on the circuit name.
+
+use crate::circuit::{self, Circuit, FeIntoPubInput, VerifierData};
+use crate::error::Error;
+use crate::proof_system::{Proof, ProverKey};
+use crate::setup::PublicParameters;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ed_on_bls12_381::EdwardsParameters;
+use ark_serialize::*;
+use wasm_bindgen::prelude::*;
+
+#[doc(hidden)]
+pub use wasm_bindgen;
+
+/// Circuit that can be proven from WebAssembly.
+pub trait WasmCircuit: Circuit<Bls12_381, EdwardsParameters> {
+    /// Name under which the circuit is registered.
+    const NAME: &'static str;
+
+    /// Label the transcript of the proofs of the circuit is initialised with.
+    const TRANSCRIPT_LABEL: &'static [u8];
+
+    /// Builds the circuit from its witness values.
+    fn from_witness(witness: &[Fr]) -> Result<Self, Error>
+    where
+        Self: Sized;
+}
+
+/// Entry of the circuits registered with
+/// [`register_wasm_circuits`](crate::register_wasm_circuits).
+#[derive(Clone, Copy)]
+pub struct Registration {
+    /// Name of the circuit
+    name: &'static str,
+
+    /// Label the transcript of the proofs of the circuit is initialised with
+    transcript_label: &'static [u8],
+
+    /// Builds the circuit from its witness and proves it
+    prove: ProveFn,
+}
+
+/// Builds a registered circuit from its witness and proves it.
+type ProveFn = fn(
+    &PublicParameters<Bls12_381>,
+    ProverKey<Fr, EdwardsParameters>,
+    &[Fr],
+) -> Result<Proof<Bls12_381, EdwardsParameters>, Error>;
+
+impl Registration {
+    /// Registers the circuit `C`.
+    pub const fn of<C>() -> Self
+    where
+        C: WasmCircuit,
+    {
+        Self {
+            name: C::NAME,
+            transcript_label: C::TRANSCRIPT_LABEL,
+            prove: prove_circuit::<C>,
+        }
+    }
+
+    /// Returns the name of the circuit.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Builds `C` from `witness` and proves it.
+fn prove_circuit<C>(
+    pp: &PublicParameters<Bls12_381>,
+    prover_key: ProverKey<Fr, EdwardsParameters>,
+    witness: &[Fr],
+) -> Result<Proof<Bls12_381, EdwardsParameters>, Error>
+where
+    C: WasmCircuit,
+{
+    C::from_witness(witness)?.gen_proof(pp, prover_key, C::TRANSCRIPT_LABEL)
+}
+
+/// Returns the circuit registered in `registry` under `name`.
+fn find<'r>(
+    registry: &'r [Registration],
+    name: &str,
+) -> Result<&'r Registration, Error> {
+    registry
+        .iter()
+        .find(|registration| registration.name == name)
+        .ok_or_else(|| Error::UnknownCircuit {
+            name: name.to_string(),
+        })
+}
+
+/// Parses a big-endian hex string, with or without a `0x` prefix, into a
+/// scalar.
+///
+/// Fails with [`Error::ScalarMalformed`] if the string is not hex or the
+/// integer is not smaller than the modulus.
+pub fn scalar_from_hex(hex: &str) -> Result<Fr, Error> {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+    let size = Fr::default().serialized_size();
+    if digits.is_empty() || digits.len() > 2 * size {
+        return Err(Error::ScalarMalformed);
+    }
+    let mut bytes = vec![0u8; size];
+    for (i, digit) in digits.iter().rev().enumerate() {
+        let value = (*digit as char)
+            .to_digit(16)
+            .ok_or(Error::ScalarMalformed)? as u8;
+        bytes[i / 2] |= value << (4 * (i % 2));
+    }
+    Fr::deserialize(&bytes[..]).map_err(|_| Error::ScalarMalformed)
+}
+
+/// Parses each of `hex` with [`scalar_from_hex`].
+fn scalars_from_hex(hex: &[String]) -> Result<Vec<Fr>, Error> {
+    hex.iter().map(|scalar| scalar_from_hex(scalar)).collect()
+}
+
+/// Proves the circuit registered in `registry` under `name` for `witness`.
+pub fn prove(
+    registry: &[Registration],
+    name: &str,
+    pp: &PublicParameters<Bls12_381>,
+    prover_key: ProverKey<Fr, EdwardsParameters>,
+    witness: &[String],
+) -> Result<Proof<Bls12_381, EdwardsParameters>, Error> {
+    let registration = find(registry, name)?;
+    (registration.prove)(pp, prover_key, &scalars_from_hex(witness)?)
+}
+
+/// Verifies a `proof` of the circuit registered in `registry` under `name`
+/// against `public_inputs`, in the order of the positions of
+/// `verifier_data`.
+///
+/// A point takes two public inputs, its affine coordinates.
+pub fn verify(
+    registry: &[Registration],
+    name: &str,
+    pp: &PublicParameters<Bls12_381>,
+    verifier_data: &VerifierData<Bls12_381, EdwardsParameters>,
+    proof: &Proof<Bls12_381, EdwardsParameters>,
+    public_inputs: &[String],
+) -> Result<(), Error> {
+    let registration = find(registry, name)?;
+    let public_inputs = scalars_from_hex(public_inputs)?
+        .into_iter()
+        .map(FeIntoPubInput::into_pi)
+        .collect::<Vec<_>>();
+    circuit::verify_proof(
+        pp,
+        verifier_data.key.clone(),
+        proof,
+        &public_inputs,
+        &verifier_data.pi_pos,
+        registration.transcript_label,
+    )
+}
+
+/// Deserializes a `T` from `bytes`, refusing trailing bytes.
+fn from_bytes<T>(bytes: &[u8]) -> Result<T, Error>
+where
+    T: CanonicalDeserialize,
+{
+    let mut reader = bytes;
+    let value = T::deserialize(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(SerializationError::InvalidData.into());
+    }
+    Ok(value)
+}
+
+/// Serializes `value` into bytes.
+fn to_bytes<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: CanonicalSerialize,
+{
+    let mut bytes = Vec::with_capacity(value.serialized_size());
+    value.serialize(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Converts an [`Error`] into a JS `Error`.
+#[doc(hidden)]
+pub fn js_error(error: Error) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+/// Setup parameters, see [`PublicParameters`].
+#[wasm_bindgen(js_name = PublicParameters)]
+pub struct WasmPublicParameters(PublicParameters<Bls12_381>);
+
+#[wasm_bindgen(js_class = PublicParameters)]
+impl WasmPublicParameters {
+    /// Deserializes parameters.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmPublicParameters, JsError> {
+        from_bytes(bytes).map(Self).map_err(js_error)
+    }
+}
+
+impl WasmPublicParameters {
+    /// Returns the wrapped parameters.
+    pub fn inner(&self) -> &PublicParameters<Bls12_381> {
+        &self.0
+    }
+}
+
+impl From<PublicParameters<Bls12_381>> for WasmPublicParameters {
+    fn from(pp: PublicParameters<Bls12_381>) -> Self {
+        Self(pp)
+    }
+}
+
+/// Prover key of a circuit, see [`ProverKey`].
+#[wasm_bindgen(js_name = ProverKey)]
+pub struct WasmProverKey(ProverKey<Fr, EdwardsParameters>);
+
+#[wasm_bindgen(js_class = ProverKey)]
+impl WasmProverKey {
+    /// Deserializes a prover key.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmProverKey, JsError> {
+        from_bytes(bytes).map(Self).map_err(js_error)
+    }
+}
+
+impl WasmProverKey {
+    /// Returns the wrapped prover key.
+    pub fn inner(&self) -> &ProverKey<Fr, EdwardsParameters> {
+        &self.0
+    }
+}
+
+impl From<ProverKey<Fr, EdwardsParameters>> for WasmProverKey {
+    fn from(prover_key: ProverKey<Fr, EdwardsParameters>) -> Self {
+        Self(prover_key)
+    }
+}
+
+/// Verifier key and public input positions of a circuit, see
+/// [`VerifierData`].
+#[wasm_bindgen(js_name = VerifierData)]
+pub struct WasmVerifierData(VerifierData<Bls12_381, EdwardsParameters>);
+
+#[wasm_bindgen(js_class = VerifierData)]
+impl WasmVerifierData {
+    /// Deserializes verifier data.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmVerifierData, JsError> {
+        from_bytes(bytes).map(Self).map_err(js_error)
+    }
+
+    /// Serializes the verifier data.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        to_bytes(&self.0).map_err(js_error)
+    }
+}
+
+impl WasmVerifierData {
+    /// Returns the wrapped verifier data.
+    pub fn inner(&self) -> &VerifierData<Bls12_381, EdwardsParameters> {
+        &self.0
+    }
+}
+
+impl From<VerifierData<Bls12_381, EdwardsParameters>> for WasmVerifierData {
+    fn from(verifier_data: VerifierData<Bls12_381, EdwardsParameters>) -> Self {
+        Self(verifier_data)
+    }
+}
+
+/// Proof of a circuit, see [`Proof`].
+#[wasm_bindgen(js_name = Proof)]
+pub struct WasmProof(Proof<Bls12_381, EdwardsParameters>);
+
+#[wasm_bindgen(js_class = Proof)]
+impl WasmProof {
+    /// Deserializes a [`ProofMode::Standard`](crate::proof_system::ProofMode)
+    /// proof.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmProof, JsError> {
+        from_bytes(bytes).map(Self).map_err(js_error)
+    }
+
+    /// Serializes the proof.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsError> {
+        to_bytes(&self.0).map_err(js_error)
+    }
+}
+
+impl WasmProof {
+    /// Returns the wrapped proof.
+    pub fn inner(&self) -> &Proof<Bls12_381, EdwardsParameters> {
+        &self.0
+    }
+}
+
+impl From<Proof<Bls12_381, EdwardsParameters>> for WasmProof {
+    fn from(proof: Proof<Bls12_381, EdwardsParameters>) -> Self {
+        Self(proof)
+    }
+}
+
+/// Exports `prove` and `verify` functions to JS for the given
+/// [`WasmCircuit`](crate::wasm::WasmCircuit)s.
+///
+/// ```ignore
+/// ark_plonk::register_wasm_circuits!(MyCircuit, MyOtherCircuit);
+/// ```
+///
+/// From JS, `prove(name, parameters, proverKey, witness)` proves the circuit
+/// registered under `name` with the witness given as hex strings, and
+/// `verify(name, parameters, verifierData, proof, publicInputs)` returns
+/// whether the proof is valid, or throws if the inputs are malformed.
+#[macro_export]
+macro_rules! register_wasm_circuits {
+    ($($circuit:ty),* $(,)?) => {
+        /// Circuits exported to JS.
+        pub const WASM_CIRCUITS: &[$crate::wasm::Registration] =
+            &[$($crate::wasm::Registration::of::<$circuit>()),*];
+
+        /// Proves the circuit registered under `name`.
+        #[$crate::wasm::wasm_bindgen::prelude::wasm_bindgen(
+            wasm_bindgen = $crate::wasm::wasm_bindgen
+        )]
+        pub fn prove(
+            name: &str,
+            parameters: &$crate::wasm::WasmPublicParameters,
+            prover_key: &$crate::wasm::WasmProverKey,
+            witness: Vec<String>,
+        ) -> Result<
+            $crate::wasm::WasmProof,
+            $crate::wasm::wasm_bindgen::JsError,
+        > {
+            $crate::wasm::prove(
+                WASM_CIRCUITS,
+                name,
+                parameters.inner(),
+                prover_key.inner().clone(),
+                &witness,
+            )
+            .map(Into::into)
+            .map_err($crate::wasm::js_error)
+        }
+
+        /// Verifies a proof of the circuit registered under `name`.
+        #[$crate::wasm::wasm_bindgen::prelude::wasm_bindgen(
+            wasm_bindgen = $crate::wasm::wasm_bindgen
+        )]
+        pub fn verify(
+            name: &str,
+            parameters: &$crate::wasm::WasmPublicParameters,
+            verifier_data: &$crate::wasm::WasmVerifierData,
+            proof: &$crate::wasm::WasmProof,
+            public_inputs: Vec<String>,
+        ) -> Result<bool, $crate::wasm::wasm_bindgen::JsError> {
+            match $crate::wasm::verify(
+                WASM_CIRCUITS,
+                name,
+                parameters.inner(),
+                verifier_data.inner(),
+                proof.inner(),
+                &public_inputs,
+            ) {
+                Ok(()) => Ok(true),
+                Err($crate::error::Error::ProofVerificationError) => Ok(false),
+                Err(error) => Err($crate::wasm::js_error(error)),
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_scalar_from_hex() {
+        assert_eq!(scalar_from_hex("0x2a").unwrap(), Fr::from(42u64));
+        assert_eq!(scalar_from_hex("2A").unwrap(), Fr::from(42u64));
+        assert_eq!(
+            scalar_from_hex("0x0100000000000000000000000000000000").unwrap(),
+            Fr::from(1u128 << 64) * Fr::from(1u128 << 64)
+        );
+        let modulus =
+            "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+        assert!(matches!(
+            scalar_from_hex(modulus),
+            Err(Error::ScalarMalformed)
+        ));
+        for malformed in ["", "0x", "0xg1", &"1".repeat(65)] {
+            assert!(matches!(
+                scalar_from_hex(malformed),
+                Err(Error::ScalarMalformed)
+            ));
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Uses the public gadget test harness as a downstream crate would.
+
+#![cfg(feature = "testing")]
+
+use ark_ec::{PairingEngine, TEModelParameters};
+use ark_plonk::prelude::*;
+use ark_plonk::testing::*;
+use num_traits::{One, Zero};
+
+/// Constrains `a * b` to be the public input `product`.
+fn mul_gadget<E, P>(
+    composer: &mut StandardComposer<E, P>,
+    a: E::Fr,
+    b: E::Fr,
+    product: E::Fr,
+) where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    let a = composer.add_input(a);
+    let b = composer.add_input(b);
+    let c = composer.mul(E::Fr::one(), a, b, E::Fr::zero(), None);
+    composer.constrain_to_constant(c, E::Fr::zero(), Some(-product));
+}
+
+fn test_gadget_tester<E, P>()
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    let (a, b) = (E::Fr::from(3u64), E::Fr::from(5u64));
+    gadget_tester(
+        |composer: &mut StandardComposer<E, P>| {
+            mul_gadget(composer, a, b, a * b)
+        },
+        32,
+    )
+    .unwrap();
+
+    // The parameters are cached for later tests
+    let pp = public_parameters::<E>(64).unwrap();
+    assert!(pp.max_degree() >= 64);
+    let smaller = public_parameters::<E>(16).unwrap();
+    assert!(std::sync::Arc::ptr_eq(&pp, &smaller));
+}
+
+fn test_prove_and_verify<E, P>()
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    let product = E::Fr::from(15u64);
+    let mut proved = 0;
+    prove_and_verify(
+        |composer: &mut StandardComposer<E, P>| {
+            proved += 1;
+            mul_gadget(composer, E::Fr::from(3u64), E::Fr::from(5u64), product)
+        },
+        // The verifier only knows the public product
+        |composer: &mut StandardComposer<E, P>| {
+            mul_gadget(composer, E::Fr::zero(), E::Fr::zero(), product)
+        },
+        32,
+    )
+    .unwrap();
+    // Once per proof mode
+    assert_eq!(proved, 2);
+}
+
+fn test_assert_unsatisfiable<E, P>()
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    assert_unsatisfiable(
+        |composer: &mut StandardComposer<E, P>| {
+            mul_gadget(
+                composer,
+                E::Fr::from(3u64),
+                E::Fr::from(5u64),
+                E::Fr::from(16u64),
+            )
+        },
+        32,
+    );
+}
+
+fn test_assert_unsatisfiable_accepts<E, P>()
+where
+    E: PairingEngine,
+    P: TEModelParameters<BaseField = E::Fr>,
+{
+    assert_unsatisfiable(
+        |composer: &mut StandardComposer<E, P>| {
+            dummy_gadget(10, composer);
+        },
+        32,
+    );
+}
+
+ark_plonk::batch_test_curves!(
+    [
+        test_gadget_tester,
+        test_prove_and_verify,
+        test_assert_unsatisfiable
+    ],
+    [test_assert_unsatisfiable_accepts]
+);
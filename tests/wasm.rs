@@ -0,0 +1,122 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+//
+// Copyright (c) ZK-INFRA. All rights reserved.
+
+//! Proves and verifies through the WebAssembly bindings in Node.
+//!
+//! Run with `make wasm-test`.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm-bindgen"))]
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ed_on_bls12_381::EdwardsParameters;
+use ark_plonk::prelude::*;
+use ark_plonk::wasm::{
+    WasmCircuit, WasmProof, WasmProverKey, WasmPublicParameters,
+    WasmVerifierData,
+};
+use ark_serialize::CanonicalSerialize;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Circuit proving knowledge of two scalars whose product is public.
+#[derive(Default)]
+struct MulCircuit {
+    a: Fr,
+    b: Fr,
+}
+
+impl Circuit<Bls12_381, EdwardsParameters> for MulCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0x0e; 32];
+
+    fn gadget(
+        &mut self,
+        composer: &mut StandardComposer<Bls12_381, EdwardsParameters>,
+    ) -> Result<(), Error> {
+        let a = composer.add_input(self.a);
+        let b = composer.add_input(self.b);
+        let product = composer.mul(Fr::from(1u64), a, b, Fr::default(), None);
+        composer.constrain_to_constant(
+            product,
+            Fr::default(),
+            Some(-(self.a * self.b)),
+        );
+        Ok(())
+    }
+
+    fn padded_circuit_size(&self) -> usize {
+        1 << 3
+    }
+}
+
+impl WasmCircuit for MulCircuit {
+    const NAME: &'static str = "mul";
+    const TRANSCRIPT_LABEL: &'static [u8] = b"mul";
+
+    fn from_witness(witness: &[Fr]) -> Result<Self, Error> {
+        match witness {
+            [a, b] => Ok(Self { a: *a, b: *b }),
+            _ => Err(Error::CircuitInputsNotFound),
+        }
+    }
+}
+
+ark_plonk::register_wasm_circuits!(MulCircuit);
+
+#[wasm_bindgen_test]
+fn prove_and_verify() {
+    let pp = PublicParameters::<Bls12_381>::setup(
+        1 << 4,
+        &mut StdRng::seed_from_u64(0),
+    )
+    .unwrap();
+    let (prover_key, verifier_data) =
+        MulCircuit::default().compile(&pp).unwrap();
+    let params = WasmPublicParameters::from(pp);
+
+    // Keys and proofs round trip through bytes
+    let mut prover_key_bytes = Vec::new();
+    prover_key.serialize(&mut prover_key_bytes).unwrap();
+    let prover_key = WasmProverKey::from_bytes(&prover_key_bytes).unwrap();
+    let verifier_data = WasmVerifierData::from_bytes(
+        &WasmVerifierData::from(verifier_data).to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    let proof = prove(
+        "mul",
+        &params,
+        &prover_key,
+        vec!["0x3".into(), "0x5".into()],
+    )
+    .unwrap();
+    let proof = WasmProof::from_bytes(&proof.to_bytes().unwrap()).unwrap();
+
+    assert!(
+        verify("mul", &params, &verifier_data, &proof, vec!["0xf".into()])
+            .unwrap()
+    );
+    assert!(!verify(
+        "mul",
+        &params,
+        &verifier_data,
+        &proof,
+        vec!["0x10".into()]
+    )
+    .unwrap());
+    assert!(
+        verify("add", &params, &verifier_data, &proof, vec!["0xf".into()])
+            .is_err()
+    );
+
+    // Malformed proofs and trailing bytes are refused
+    assert!(WasmProof::from_bytes(&[0; 4]).is_err());
+    let mut bytes = proof.to_bytes().unwrap();
+    bytes.push(0);
+    assert!(WasmProof::from_bytes(&bytes).is_err());
+}